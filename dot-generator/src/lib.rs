@@ -377,9 +377,9 @@ macro_rules! subgraph {
 #[macro_export]
 macro_rules! attrs {
     () => {GraphAttributes::Graph(vec![])};
-    (graph $attrs:expr) => {GraphAttributes::Graph($attrs)};
-    (node $attrs:expr) => {GraphAttributes::Node($attrs)};
-    (edge $attrs:expr) => {GraphAttributes::Edge($attrs)};
+    (graph $attrs:expr) => {GraphAttributes::Graph($attrs.into())};
+    (node $attrs:expr) => {GraphAttributes::Node($attrs.into())};
+    (edge $attrs:expr) => {GraphAttributes::Edge($attrs.into())};
     (graph; $($attrs:expr),+ ) => {{
         let mut attrs_vec = Vec::new();
         $( attrs_vec.push($attrs) ; )+
@@ -459,10 +459,10 @@ macro_rules! node {
         Node::new(NodeId(id!($i$id), None), attrs)
     }};
      ($i:ident $id:expr, $attrs:expr  ) => {
-        Node::new(NodeId(id!($i$id), None), $attrs)
+        Node::new(NodeId(id!($i$id), None), $attrs.into())
     };
     ($id:expr, $attrs:expr  ) => {
-        Node::new(NodeId(id!($id), None), $attrs)
+        Node::new(NodeId(id!($id), None), $attrs.into())
     };
     ( $id:expr; $($attr:expr),+ ) => {{
         let mut attrs = Vec::new();
@@ -470,7 +470,7 @@ macro_rules! node {
         Node::new(NodeId(id!( $id), None), attrs)
     }};
     ($i:ident $id:expr => $p:expr, $attrs:expr  ) => {
-        Node::new(NodeId(id!($i$id), Some($p)), $attrs)
+        Node::new(NodeId(id!($i$id), Some($p)), $attrs.into())
     };
     ($i:ident $id:expr => $p:expr; $($attr:expr),+ ) => {{
         let mut attrs = Vec::new();
@@ -478,7 +478,7 @@ macro_rules! node {
         Node::new(NodeId(id!($i$id), Some($p)), attrs)
     }};
     ( $id:expr => $p:expr, $attrs:expr  ) => {
-        Node::new(NodeId(id!($id), Some($p)), $attrs)
+        Node::new(NodeId(id!($id), Some($p)), $attrs.into())
     };
     ( $id:expr => $p:expr; $($attr:expr),+ ) => {{
         let mut attrs = Vec::new();
@@ -558,7 +558,7 @@ macro_rules! edge {
     }};
 
     ($l:expr => $r:expr, $attrs:expr) => {
-        Edge{ ty: EdgeTy::Pair(Vertex::from($l),Vertex::from($r)), attributes: $attrs };
+        Edge{ ty: EdgeTy::Pair(Vertex::from($l),Vertex::from($r)), attributes: $attrs.into() }
     };
     ($l:expr => $r:expr; $($attrs:expr),+) => {{
          let mut attrs_vec = Vec::new();
@@ -576,10 +576,10 @@ macro_rules! edge {
     }};
     ($l:expr => $r:expr $(=> $nexts:expr)+ , $attrs:expr) => {{
 
-         let mut edges_vec = vec![Vertex::from($l),Vertex::from($r)]
+         let mut edges_vec = vec![Vertex::from($l),Vertex::from($r)];
          $( edges_vec.push(Vertex::from($nexts)) ; )+
 
-        Edge{ ty: EdgeTy::Chain(edges_vec), attributes: $attrs }
+        Edge{ ty: EdgeTy::Chain(edges_vec), attributes: $attrs.into() }
     }};
 }
 
@@ -17,10 +17,32 @@
 //!        aa -> be -> d -> aaa       <= type of the edge is chain
 //!    }
 //! ```
+//!
+//! # Serde:
+//! The `serde` feature implements `Serialize` and `Deserialize` for all components.
+//! The representation is stable:
+//!  - the enums are tagged with the `type` field in the snake case and keep the content in the `value` field,
+//!    e.g. the [Id] is `{"type":"plain","value":"a"}` or `{"type":"escaped","value":"\"a b\""}`
+//!  - the [Graph] keeps the fields next to the tag: `{"type":"digraph","id":..,"strict":false,"stmts":[..]}`
+//!  - the tuple structs [Port], [NodeId] and [Attribute] are arrays, e.g. the attribute `color=red` is
+//!    `[{"type":"plain","value":"color"},{"type":"plain","value":"red"}]`
+//!  - the structs [Node], [Edge] and [Subgraph] are objects with the fields of the struct
+//!
+//! ```txt
+//!     digraph { a -> b }
+//!
+//!     {"type":"digraph","id":{"type":"anonymous","value":""},"strict":false,"stmts":[
+//!       {"type":"edge","value":{"ty":{"type":"pair","value":[
+//!         {"type":"node_id","value":[{"type":"plain","value":"a"},null]},
+//!         {"type":"node_id","value":[{"type":"plain","value":"b"},null]}
+//!       ]},"attributes":[]}}
+//!     ]}
+//! ```
 use std::fmt::{Display, Formatter};
 
 /// the component represents a port in the language.
 /// It contains from id and direction. All can be optional separately but not at the same time.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Debug, PartialEq, Clone, Eq, Hash)]
 pub struct Port(pub Option<Id>, pub Option<String>);
 
@@ -28,6 +50,11 @@ pub struct Port(pub Option<Id>, pub Option<String>);
 /// The Anonymous is a virtual component to keep the other components consistent in case
 /// when a node or subgraph is anonymous
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(tag = "type", content = "value", rename_all = "snake_case")
+)]
 pub enum Id {
     Html(String),
     Escaped(String),
@@ -48,15 +75,22 @@ impl Display for Id {
 
 /// the component represents a node_id in the language.
 /// The component turns up in the edges predominantly or as an id for a node.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Debug, PartialEq, Eq, Clone, Hash)]
 pub struct NodeId(pub Id, pub Option<Port>);
 
 /// the component represents a attribute in the language.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(PartialEq, Debug, Clone)]
 pub struct Attribute(pub Id, pub Id);
 
 /// the component represents a set of attributes with prefix denoting a type in the language.
 #[derive(PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(tag = "type", content = "value", rename_all = "snake_case")
+)]
 pub enum GraphAttributes {
     Graph(Vec<Attribute>),
     Node(Vec<Attribute>),
@@ -75,6 +109,7 @@ impl GraphAttributes {
 }
 
 /// the component represents a edge in the language.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Debug, PartialEq, Clone)]
 pub struct Edge {
     pub ty: EdgeTy,
@@ -90,12 +125,18 @@ impl Edge {
 /// the component depicts a type of the edge, namely it is a pair of chain.
 /// From the graph point of view, it impacts a compact display only.
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(tag = "type", content = "value", rename_all = "snake_case")
+)]
 pub enum EdgeTy {
     Pair(Vertex, Vertex),
     Chain(Vec<Vertex>),
 }
 
 /// the component represents the vital component, namely node in the lang.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Debug, PartialEq, Clone)]
 pub struct Node {
     pub id: NodeId,
@@ -110,10 +151,16 @@ impl Node {
 
 /// the component represents a wrapper to keep sustainability in subgraph and graph bodies.
 #[derive(PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(tag = "type", content = "value", rename_all = "snake_case")
+)]
 pub enum Stmt {
     Node(Node),
     Subgraph(Subgraph),
     Attribute(Attribute),
+    #[cfg_attr(feature = "serde", serde(rename = "graph_attributes"))]
     GAttribute(GraphAttributes),
     Edge(Edge),
 }
@@ -149,6 +196,7 @@ impl From<Subgraph> for Stmt {
 }
 
 /// the component represents a subgraph  in the lang.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(PartialEq, Debug, Clone)]
 pub struct Subgraph {
     pub id: Id,
@@ -163,8 +211,15 @@ impl Subgraph {
 
 /// the component represents an edge component.
 #[derive(PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(tag = "type", content = "value", rename_all = "snake_case")
+)]
 pub enum Vertex {
+    #[cfg_attr(feature = "serde", serde(rename = "node_id"))]
     N(NodeId),
+    #[cfg_attr(feature = "serde", serde(rename = "subgraph"))]
     S(Subgraph),
 }
 
@@ -182,6 +237,8 @@ impl From<Subgraph> for Vertex {
 
 /// the component represents a graph in the lang.
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "type", rename_all = "lowercase"))]
 pub enum Graph {
     Graph {
         id: Id,
@@ -203,3 +260,122 @@ impl Graph {
         }
     }
 }
+
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+    use serde_json::json;
+
+    use crate::*;
+
+    fn plain(v: &str) -> Id {
+        Id::Plain(v.to_string())
+    }
+
+    fn graph() -> Graph {
+        Graph::DiGraph {
+            id: Id::Escaped("\"g 1\"".to_string()),
+            strict: true,
+            stmts: vec![
+                Stmt::GAttribute(GraphAttributes::Node(vec![Attribute(
+                    plain("shape"),
+                    plain("box"),
+                )])),
+                Stmt::Attribute(Attribute(
+                    plain("label"),
+                    Id::Html("<<b>g</b>>".to_string()),
+                )),
+                Stmt::Node(Node::new(
+                    NodeId(
+                        plain("a"),
+                        Some(Port(Some(plain("p")), Some("n".to_string()))),
+                    ),
+                    vec![Attribute(plain("color"), plain("red"))],
+                )),
+                Stmt::Edge(Edge {
+                    ty: EdgeTy::Chain(vec![
+                        Vertex::N(NodeId(plain("a"), None)),
+                        Vertex::S(Subgraph {
+                            id: Id::Anonymous("anon1".to_string()),
+                            stmts: vec![Stmt::Node(Node::new(NodeId(plain("b"), None), vec![]))],
+                        }),
+                    ]),
+                    attributes: vec![],
+                }),
+            ],
+        }
+    }
+
+    #[test]
+    fn id_test() {
+        let ids = [
+            (plain("a"), json!({"type": "plain", "value": "a"})),
+            (
+                Id::Escaped("\"a\"".to_string()),
+                json!({"type": "escaped", "value": "\"a\""}),
+            ),
+            (
+                Id::Html("<a>".to_string()),
+                json!({"type": "html", "value": "<a>"}),
+            ),
+            (
+                Id::Anonymous("".to_string()),
+                json!({"type": "anonymous", "value": ""}),
+            ),
+        ];
+        for (id, expected) in ids {
+            assert_eq!(serde_json::to_value(&id).unwrap(), expected);
+            assert_eq!(serde_json::from_value::<Id>(expected).unwrap(), id);
+        }
+    }
+
+    #[test]
+    fn representation_test() {
+        let a = json!({"type": "plain", "value": "a"});
+        let b = json!({"type": "plain", "value": "b"});
+        let g = Graph::Graph {
+            id: Id::Anonymous("".to_string()),
+            strict: false,
+            stmts: vec![Stmt::Edge(Edge {
+                ty: EdgeTy::Pair(
+                    Vertex::N(NodeId(plain("a"), None)),
+                    Vertex::N(NodeId(plain("b"), Some(Port(None, Some("s".to_string()))))),
+                ),
+                attributes: vec![Attribute(plain("color"), plain("red"))],
+            })],
+        };
+        assert_eq!(
+            serde_json::to_value(&g).unwrap(),
+            json!({
+                "type": "graph",
+                "id": {"type": "anonymous", "value": ""},
+                "strict": false,
+                "stmts": [{"type": "edge", "value": {
+                    "ty": {"type": "pair", "value": [
+                        {"type": "node_id", "value": [a, null]},
+                        {"type": "node_id", "value": [b, [null, "s"]]}
+                    ]},
+                    "attributes": [[
+                        {"type": "plain", "value": "color"},
+                        {"type": "plain", "value": "red"}
+                    ]]
+                }}]
+            })
+        );
+    }
+
+    #[test]
+    fn round_trip_test() {
+        let g = graph();
+        let json = serde_json::to_string(&g).unwrap();
+        assert_eq!(serde_json::from_str::<Graph>(&json).unwrap(), g);
+
+        let value = serde_json::to_value(&g).unwrap();
+        assert_eq!(value["type"], "digraph");
+        assert_eq!(value["stmts"][0]["type"], "graph_attributes");
+        assert_eq!(value["stmts"][0]["value"]["type"], "node");
+        assert_eq!(
+            value["stmts"][3]["value"]["ty"]["value"][1]["type"],
+            "subgraph"
+        );
+    }
+}
@@ -5,7 +5,7 @@ use dot_generator::attr;
 use into_attr::IntoAttribute;
 use proc_macro::TokenStream;
 use quote::quote;
-use syn::{self, Data};
+use syn::{self, Data, Fields};
 
 #[proc_macro_derive(IntoAttribute)]
 pub fn into_attr_derive(input: TokenStream) -> TokenStream {
@@ -32,7 +32,7 @@ fn impl_into_attr_macro(ast: &syn::DeriveInput) -> TokenStream {
             quote! {
               impl IntoAttribute for #name {
                 fn into_attr(self) -> Attribute {
-                 attr!(#name_str,self.0)
+                 Attribute(id!(#name_str), into_attr::value_id(self.0.to_string()))
                 }
               }
             }
@@ -42,3 +42,72 @@ fn impl_into_attr_macro(ast: &syn::DeriveInput) -> TokenStream {
 
     gen.into()
 }
+
+#[proc_macro_derive(FromAttribute)]
+pub fn from_attr_derive(input: TokenStream) -> TokenStream {
+    let ast = syn::parse(input).unwrap();
+    impl_from_attr_macro(&ast)
+}
+
+fn impl_from_attr_macro(ast: &syn::DeriveInput) -> TokenStream {
+    let name = &ast.ident;
+    let name_str = name.to_string();
+    let parse = match &ast.data {
+        Data::Enum(de) => {
+            let variants: Vec<&syn::Ident> = de
+                .variants
+                .iter()
+                .map(|v| match v.fields {
+                    Fields::Unit => &v.ident,
+                    _ => panic!("only the unit variants are expected"),
+                })
+                .collect();
+            let values: Vec<String> = variants
+                .iter()
+                .map(|v| {
+                    let v = v.to_string();
+                    v.strip_suffix('_').unwrap_or(v.as_str()).to_string()
+                })
+                .collect();
+            quote! {
+                #(
+                    if value.eq_ignore_ascii_case(#values) {
+                        return Ok(#name::#variants);
+                    }
+                )*
+                Err(format!("the value '{}' of the attribute '{}' is unknown", value, #name_str))
+            }
+        }
+        Data::Struct(ds) => {
+            let ty = &ds
+                .fields
+                .iter()
+                .next()
+                .expect("the struct with one field is expected")
+                .ty;
+            if quote!(#ty).to_string() == "bool" {
+                quote! { into_attr::parse_bool(&value, #name_str).map(#name) }
+            } else {
+                quote! { into_attr::parse_value::<#ty>(&value, #name_str).map(#name) }
+            }
+        }
+        _ => panic!("the unions are unexpected"),
+    };
+    let gen = quote! {
+        impl FromAttribute for #name {
+            fn from_attr(attr: &Attribute) -> Result<Self, String> {
+                let value = into_attr::attr_value(attr, #name_str)?;
+                #parse
+            }
+        }
+        impl TryFrom<&Attribute> for #name {
+            type Error = String;
+
+            fn try_from(attr: &Attribute) -> Result<Self, Self::Error> {
+                <#name as FromAttribute>::from_attr(attr)
+            }
+        }
+    };
+
+    gen.into()
+}
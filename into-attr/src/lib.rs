@@ -1,4 +1,81 @@
-use dot_structures::Attribute;
+use dot_structures::{Attribute, Id};
+use std::str::FromStr;
+
 pub trait IntoAttribute {
     fn into_attr(self) -> Attribute;
 }
+
+/// The reverse of [IntoAttribute]: restores the typed attribute from the parsed one.
+pub trait FromAttribute: Sized {
+    fn from_attr(attr: &Attribute) -> Result<Self, String>;
+}
+
+/// Returns the text of the id without the quotes and the escaping of the quotes.
+pub fn unquote(id: &Id) -> String {
+    match id {
+        Id::Escaped(v) => v
+            .strip_prefix('"')
+            .and_then(|v| v.strip_suffix('"'))
+            .unwrap_or(v)
+            .replace("\\\"", "\""),
+        Id::Html(v) | Id::Plain(v) | Id::Anonymous(v) => v.clone(),
+    }
+}
+
+/// Returns the plain id if the value is a valid dot identifier or numeral and the quoted one otherwise.
+pub fn value_id(value: String) -> Id {
+    let mut chars = value.chars();
+    let ident = matches!(chars.next(), Some(c) if c.is_alphabetic() || c == '_')
+        && chars.all(|c| c.is_alphanumeric() || c == '_');
+    let digits = value.strip_prefix('-').unwrap_or(&value);
+    let numeral =
+        digits.parse::<f64>().is_ok() && digits.chars().all(|c| c.is_ascii_digit() || c == '.');
+    if ident || numeral {
+        Id::Plain(value)
+    } else {
+        Id::Escaped(format!("\"{}\"", value.replace('"', "\\\"")))
+    }
+}
+
+/// Returns the unquoted value of the attribute checking that the attribute has the given name.
+pub fn attr_value(attr: &Attribute, name: &str) -> Result<String, String> {
+    let key = unquote(&attr.0);
+    if key == name {
+        Ok(unquote(&attr.1))
+    } else {
+        Err(format!(
+            "the attribute '{}' is expected but '{}' is found",
+            name, key
+        ))
+    }
+}
+
+/// Parses the value of the attribute with the given name.
+pub fn parse_value<T>(value: &str, name: &str) -> Result<T, String>
+where
+    T: FromStr,
+    T::Err: std::fmt::Display,
+{
+    value.parse::<T>().map_err(|e| {
+        format!(
+            "the value '{}' of the attribute '{}' is invalid: {}",
+            value, name, e
+        )
+    })
+}
+
+/// Parses the boolean value of the attribute: `true` and `yes` or a non zero integer
+/// are true, `false` and `no` or zero are false regardless of the case.
+pub fn parse_bool(value: &str, name: &str) -> Result<bool, String> {
+    let v = value.trim().to_lowercase();
+    match v.as_str() {
+        "true" | "yes" => Ok(true),
+        "false" | "no" => Ok(false),
+        _ => v.parse::<i64>().map(|i| i != 0).map_err(|_| {
+            format!(
+                "the value '{}' of the attribute '{}' is not a boolean",
+                value, name
+            )
+        }),
+    }
+}
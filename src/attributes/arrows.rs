@@ -0,0 +1,326 @@
+//! The values of the [`arrowhead`] and [`arrowtail`] attributes.
+//!
+//! The arrow is made of up to four shapes drawn one after another from the node.
+//! Every shape can be open, `o`, and clipped to the left or the right half of the edge, `l` or `r`.
+//! Not every modifier applies to every shape: the `o` modifier is meaningless for the shapes
+//! drawn by the lines only and the half of `dot` is not defined.
+//!
+//! # Example:
+//! ```rust
+//! use graphviz_rust::attributes::{Arrow, ArrowShape, ArrowType};
+//!
+//! let arrow = ArrowType::new(vec![
+//!     Arrow::new(ArrowShape::Tee).left(),
+//!     Arrow::new(ArrowShape::Diamond).open().left(),
+//! ])
+//! .unwrap();
+//! assert_eq!(arrow.to_string(), "lteeoldiamond");
+//! assert_eq!(ArrowType::parse("lteeoldiamond").unwrap(), arrow);
+//! assert!(ArrowType::parse("ldot").is_err());
+//! ```
+//!
+//! [`arrowhead`]: https://graphviz.org/docs/attrs/arrowhead/
+//! [`arrowtail`]: https://graphviz.org/docs/attrs/arrowtail/
+use std::{
+    fmt::{Display, Formatter},
+    str::FromStr,
+};
+
+/// The primitive shape of the arrow.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArrowShape {
+    Box,
+    Crow,
+    Curve,
+    ICurve,
+    Diamond,
+    Dot,
+    Inv,
+    None,
+    Normal,
+    Tee,
+    Vee,
+}
+
+impl ArrowShape {
+    const ALL: [ArrowShape; 11] = [
+        ArrowShape::Box,
+        ArrowShape::Crow,
+        ArrowShape::Curve,
+        ArrowShape::ICurve,
+        ArrowShape::Diamond,
+        ArrowShape::Dot,
+        ArrowShape::Inv,
+        ArrowShape::None,
+        ArrowShape::Normal,
+        ArrowShape::Tee,
+        ArrowShape::Vee,
+    ];
+
+    fn name(&self) -> &'static str {
+        match self {
+            ArrowShape::Box => "box",
+            ArrowShape::Crow => "crow",
+            ArrowShape::Curve => "curve",
+            ArrowShape::ICurve => "icurve",
+            ArrowShape::Diamond => "diamond",
+            ArrowShape::Dot => "dot",
+            ArrowShape::Inv => "inv",
+            ArrowShape::None => "none",
+            ArrowShape::Normal => "normal",
+            ArrowShape::Tee => "tee",
+            ArrowShape::Vee => "vee",
+        }
+    }
+
+    /// Returns true if the shape has the filled and the open form.
+    pub fn can_be_open(&self) -> bool {
+        matches!(
+            self,
+            ArrowShape::Box
+                | ArrowShape::Diamond
+                | ArrowShape::Dot
+                | ArrowShape::Inv
+                | ArrowShape::Normal
+        )
+    }
+
+    /// Returns true if the shape can be clipped to the half.
+    pub fn can_be_half(&self) -> bool {
+        !matches!(self, ArrowShape::Dot | ArrowShape::None)
+    }
+}
+
+impl Display for ArrowShape {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// The half of the edge the shape is clipped to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArrowSide {
+    Left,
+    Right,
+}
+
+/// The shape with the modifiers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Arrow {
+    pub shape: ArrowShape,
+    pub open: bool,
+    pub side: Option<ArrowSide>,
+}
+
+impl Arrow {
+    pub fn new(shape: ArrowShape) -> Self {
+        Arrow {
+            shape,
+            open: false,
+            side: None,
+        }
+    }
+
+    pub fn open(mut self) -> Self {
+        self.open = true;
+        self
+    }
+
+    pub fn left(mut self) -> Self {
+        self.side = Some(ArrowSide::Left);
+        self
+    }
+
+    pub fn right(mut self) -> Self {
+        self.side = Some(ArrowSide::Right);
+        self
+    }
+
+    /// Checks that the modifiers apply to the shape.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.open && !self.shape.can_be_open() {
+            return Err(format!("the arrow shape '{}' can not be open", self.shape));
+        }
+        if self.side.is_some() && !self.shape.can_be_half() {
+            return Err(format!(
+                "the arrow shape '{}' can not be clipped to the half",
+                self.shape
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl Display for Arrow {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.open {
+            f.write_str("o")?;
+        }
+        match self.side {
+            Some(ArrowSide::Left) => f.write_str("l")?,
+            Some(ArrowSide::Right) => f.write_str("r")?,
+            None => {}
+        }
+        write!(f, "{}", self.shape)
+    }
+}
+
+impl From<ArrowShape> for Arrow {
+    fn from(shape: ArrowShape) -> Self {
+        Arrow::new(shape)
+    }
+}
+
+/// The arrow made of one to four shapes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArrowType(Vec<Arrow>);
+
+impl ArrowType {
+    /// Creates the arrow checking the number of the shapes and their modifiers.
+    pub fn new(arrows: Vec<Arrow>) -> Result<Self, String> {
+        if arrows.is_empty() || arrows.len() > 4 {
+            return Err(format!(
+                "the arrow should have from 1 to 4 shapes but has {}",
+                arrows.len()
+            ));
+        }
+        for arrow in arrows.iter() {
+            arrow.validate()?;
+        }
+        Ok(ArrowType(arrows))
+    }
+
+    pub fn arrows(&self) -> &[Arrow] {
+        &self.0
+    }
+
+    /// Parses the arrow name, the legacy names like `empty` or `ediamond` are accepted as well.
+    pub fn parse(value: &str) -> Result<ArrowType, String> {
+        let value = value.trim();
+        if let Some(arrow) = legacy(value) {
+            return ArrowType::new(vec![arrow]);
+        }
+        let mut rest = value;
+        let mut arrows = vec![];
+        while !rest.is_empty() {
+            let mut arrow = Arrow::new(ArrowShape::None);
+            if let Some(r) = rest.strip_prefix('o') {
+                arrow.open = true;
+                rest = r;
+            }
+            if let Some(r) = rest.strip_prefix('l') {
+                arrow.side = Some(ArrowSide::Left);
+                rest = r;
+            } else if let Some(r) = rest.strip_prefix('r') {
+                arrow.side = Some(ArrowSide::Right);
+                rest = r;
+            }
+            arrow.shape = *ArrowShape::ALL
+                .iter()
+                .find(|s| rest.starts_with(s.name()))
+                .ok_or_else(|| {
+                    format!("the arrow '{}' has the unknown shape at '{}'", value, rest)
+                })?;
+            rest = &rest[arrow.shape.name().len()..];
+            arrows.push(arrow);
+        }
+        ArrowType::new(arrows)
+    }
+}
+
+fn legacy(value: &str) -> Option<Arrow> {
+    match value {
+        "ediamond" => Some(Arrow::new(ArrowShape::Diamond).open()),
+        "open" => Some(Arrow::new(ArrowShape::Vee)),
+        "halfopen" => Some(Arrow::new(ArrowShape::Vee).left()),
+        "empty" => Some(Arrow::new(ArrowShape::Normal).open()),
+        "invempty" => Some(Arrow::new(ArrowShape::Inv).open()),
+        _ => None,
+    }
+}
+
+impl Display for ArrowType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for arrow in self.0.iter() {
+            write!(f, "{}", arrow)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for ArrowType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        ArrowType::parse(s)
+    }
+}
+
+impl From<ArrowShape> for ArrowType {
+    fn from(shape: ArrowShape) -> Self {
+        ArrowType(vec![Arrow::new(shape)])
+    }
+}
+
+impl TryFrom<Arrow> for ArrowType {
+    type Error = String;
+
+    fn try_from(arrow: Arrow) -> Result<Self, Self::Error> {
+        ArrowType::new(vec![arrow])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::attributes::{Arrow, ArrowShape, ArrowSide, ArrowType};
+
+    #[test]
+    fn parse_test() {
+        let arrow = ArrowType::parse("invodotnormal").unwrap();
+        assert_eq!(
+            arrow.arrows(),
+            &[
+                Arrow::new(ArrowShape::Inv),
+                Arrow::new(ArrowShape::Dot).open(),
+                Arrow::new(ArrowShape::Normal),
+            ]
+        );
+        assert_eq!(
+            ArrowType::parse("orbox").unwrap().arrows()[0],
+            Arrow {
+                shape: ArrowShape::Box,
+                open: true,
+                side: Some(ArrowSide::Right)
+            }
+        );
+        assert_eq!(
+            ArrowType::parse("empty").unwrap(),
+            ArrowType::try_from(Arrow::new(ArrowShape::Normal).open()).unwrap()
+        );
+        assert_eq!(ArrowType::parse("halfopen").unwrap().to_string(), "lvee");
+
+        for a in [
+            "normal",
+            "icurveodot",
+            "lteeoldiamond",
+            "crowcurve",
+            "boxnonebox",
+            "rveeoinvnonetee",
+        ] {
+            assert_eq!(ArrowType::parse(a).unwrap().to_string(), a);
+        }
+    }
+
+    #[test]
+    fn validity_test() {
+        assert!(ArrowType::parse("").is_err());
+        assert!(ArrowType::parse("normalnormalnormalnormalnormal").is_err());
+        assert!(ArrowType::parse("box_").is_err());
+        assert!(ArrowType::parse("otee").is_err());
+        assert!(ArrowType::parse("rdot").is_err());
+        assert!(ArrowType::parse("lnone").is_err());
+        assert!(ArrowType::new(vec![Arrow::new(ArrowShape::Crow).open()]).is_err());
+        assert!(ArrowType::try_from(Arrow::new(ArrowShape::Crow).left()).is_ok());
+        assert_eq!(ArrowType::from(ArrowShape::Box).to_string(), "box");
+    }
+}
@@ -0,0 +1,175 @@
+//! The typed sets of the attributes.
+//!
+//! Every attribute implements [AttributeOf] for the elements it appears on,
+//! therefore the set accepts only the attributes valid for the element and the mistakes like
+//! the arrowhead on the node are caught by the compiler. The `style` is given by the element
+//! specific [NodeStyle](crate::attributes::NodeStyle), [EdgeStyle](crate::attributes::EdgeStyle)
+//! and so on, thus the node style like `wedged` is rejected on the edge as well.
+//! The set converts into `Vec<Attribute>` and can be passed to the `node!`, `edge!` and `attrs!` macros.
+//!
+//! # Example:
+//! ```rust
+//! use dot_generator::*;
+//! use dot_structures::*;
+//! use graphviz_rust::attributes::{
+//!     arrowhead, color_name, fontcolor, shape, ArrowShape, EdgeAttrs, NodeAttrs,
+//! };
+//!
+//! let attrs = NodeAttrs::new()
+//!     .with(shape::box_)
+//!     .with(fontcolor(color_name::red.into()));
+//! assert_eq!(
+//!     node!("a", attrs),
+//!     node!("a"; attr!("shape", "box"), attr!("fontcolor", "red"))
+//! );
+//!
+//! let attrs = EdgeAttrs::new().with(arrowhead(ArrowShape::Dot.into()));
+//! assert_eq!(
+//!     edge!(node_id!("a") => node_id!("b"), attrs),
+//!     edge!(node_id!("a") => node_id!("b"); attr!("arrowhead", "dot"))
+//! );
+//! ```
+//! The attribute of the other element is rejected:
+//! ```compile_fail
+//! use graphviz_rust::attributes::{arrowhead, ArrowShape, NodeAttrs};
+//!
+//! NodeAttrs::new().with(arrowhead(ArrowShape::Dot.into()));
+//! ```
+//! ```compile_fail
+//! use graphviz_rust::attributes::{EdgeAttrs, NodeStyle};
+//!
+//! EdgeAttrs::new().with(NodeStyle::parse("wedged").unwrap());
+//! ```
+use std::{
+    fmt::{Debug, Formatter},
+    marker::PhantomData,
+};
+
+use dot_structures::Attribute;
+use into_attr::IntoAttribute;
+
+use crate::attributes::{EdgeAttributes, GraphAttributes, NodeAttributes, SubgraphAttributes};
+
+/// The marker of the attributes appearing on the element `O`,
+/// that is one of [NodeAttributes], [EdgeAttributes], [GraphAttributes] or [SubgraphAttributes].
+pub trait AttributeOf<O>: IntoAttribute {}
+
+/// The attributes of the element `O`.
+pub struct Attrs<O> {
+    attributes: Vec<Attribute>,
+    owner: PhantomData<O>,
+}
+
+pub type NodeAttrs = Attrs<NodeAttributes>;
+pub type EdgeAttrs = Attrs<EdgeAttributes>;
+pub type GraphAttrs = Attrs<GraphAttributes>;
+pub type SubgraphAttrs = Attrs<SubgraphAttributes>;
+
+impl<O> Attrs<O> {
+    pub fn new() -> Self {
+        Attrs {
+            attributes: vec![],
+            owner: PhantomData,
+        }
+    }
+
+    /// Adds the attribute replacing the previous one with the same name.
+    pub fn with(mut self, attr: impl AttributeOf<O>) -> Self {
+        let attr = attr.into_attr();
+        self.attributes.retain(|a| a.0 != attr.0);
+        self.attributes.push(attr);
+        self
+    }
+
+    /// Adds the attribute unknown to the library, e.g. the one of a custom renderer, unchecked.
+    pub fn with_custom(mut self, attr: Attribute) -> Self {
+        self.attributes.push(attr);
+        self
+    }
+
+    pub fn attributes(&self) -> &[Attribute] {
+        &self.attributes
+    }
+}
+
+impl<O> Clone for Attrs<O> {
+    fn clone(&self) -> Self {
+        Attrs {
+            attributes: self.attributes.clone(),
+            owner: PhantomData,
+        }
+    }
+}
+
+impl<O> PartialEq for Attrs<O> {
+    fn eq(&self, other: &Self) -> bool {
+        self.attributes == other.attributes
+    }
+}
+
+impl<O> Debug for Attrs<O> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Attrs").field(&self.attributes).finish()
+    }
+}
+
+impl<O> Default for Attrs<O> {
+    fn default() -> Self {
+        Attrs::new()
+    }
+}
+
+impl<O> From<Attrs<O>> for Vec<Attribute> {
+    fn from(value: Attrs<O>) -> Self {
+        value.attributes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use dot_generator::{attr, attrs, id, node, node_id};
+    use dot_structures::*;
+    use into_attr::IntoAttribute;
+
+    use crate::attributes::{
+        dir, rankdir, shape, width, EdgeAttrs, EdgeStyle, GraphAttrs, NodeAttrs, SubgraphAttrs,
+    };
+
+    #[test]
+    fn attrs_test() {
+        let attrs = NodeAttrs::new()
+            .with(shape::box_)
+            .with(width(1.5))
+            .with(shape::egg)
+            .with_custom(attr!("foo", "bar"));
+        assert_eq!(
+            attrs.attributes(),
+            &[
+                attr!("width", "1.5"),
+                attr!("shape", "egg"),
+                attr!("foo", "bar")
+            ]
+        );
+        let typed = [
+            width(1.5).into_attr(),
+            shape::egg.into_attr(),
+            attr!("foo", "bar"),
+        ];
+        let collected = Node::new(node_id!("a"), typed.into_iter().collect());
+        assert_eq!(collected, Node::new(node_id!("a"), attrs.clone().into()));
+        assert_eq!(collected, node!("a", attrs));
+
+        let attrs = EdgeAttrs::new()
+            .with(dir::both)
+            .with(EdgeStyle::parse("dashed").unwrap());
+        assert_eq!(
+            attrs!(edge attrs),
+            attrs!(edge; attr!("dir", "both"), attr!("style", "dashed"))
+        );
+        assert_eq!(
+            Vec::from(GraphAttrs::new().with(rankdir::LR)),
+            vec![attr!("rankdir", "LR")]
+        );
+        assert!(SubgraphAttrs::default().attributes().is_empty());
+    }
+}
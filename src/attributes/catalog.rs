@@ -0,0 +1,404 @@
+//! The catalogue of the graphviz [`attributes`] available at runtime.
+//!
+//! Every entry describes the attribute: the elements using it, the value types,
+//! the default value, the minimum, the layout engines and the output formats honoring it
+//! and the short description. The catalogue follows the graphviz list of the attributes
+//! and therefore contains the attributes that have no typed functions as well.
+//!
+//! # Example:
+//! ```rust
+//! use graphviz_rust::attributes::catalog::{lookup, lookup_for, Element};
+//!
+//! let penwidth = lookup("penwidth").unwrap();
+//! assert_eq!(penwidth.types, &["double"]);
+//! assert_eq!(penwidth.default, Some("1.0"));
+//! assert_eq!(penwidth.minimum, Some("0.0"));
+//! assert!(penwidth.applies_to(Element::Edge));
+//! assert!(!penwidth.applies_to(Element::Graph));
+//!
+//! assert_eq!(lookup("rank").unwrap().engines, &["dot"]);
+//! assert_eq!(lookup_for("orientation", Element::Graph).unwrap().types, &["string"]);
+//! assert!(lookup("unknown").is_none());
+//! ```
+//!
+//! [`attributes`]: https://graphviz.org/doc/info/attrs.html
+
+/// The element of the graph the attribute is used by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Element {
+    Graph,
+    Subgraph,
+    Cluster,
+    Node,
+    Edge,
+}
+
+/// The description of the attribute.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AttributeInfo {
+    pub name: &'static str,
+    pub used_by: &'static [Element],
+    /// the graphviz names of the accepted value types, e.g. `double` or `colorList`
+    pub types: &'static [&'static str],
+    pub default: Option<&'static str>,
+    pub minimum: Option<&'static str>,
+    /// the layout engines honoring the attribute, empty if all of them do
+    pub engines: &'static [&'static str],
+    /// the output formats honoring the attribute, empty if all of them do
+    pub outputs: &'static [&'static str],
+    pub description: &'static str,
+}
+
+impl AttributeInfo {
+    pub fn applies_to(&self, element: Element) -> bool {
+        self.used_by.contains(&element)
+    }
+
+    /// Returns true if the attribute is honored by the given layout engine, e.g. `neato`.
+    pub fn honored_by(&self, engine: &str) -> bool {
+        self.engines.is_empty() || self.engines.contains(&engine)
+    }
+}
+
+/// Returns the attribute with the given name.
+///
+/// The only attribute with two entries is `orientation` having the different meaning
+/// for the graphs and the nodes, see [lookup_for].
+pub fn lookup(name: &str) -> Option<&'static AttributeInfo> {
+    CATALOG.iter().find(|a| a.name == name)
+}
+
+/// Returns the attribute with the given name used by the given element.
+pub fn lookup_for(name: &str, element: Element) -> Option<&'static AttributeInfo> {
+    CATALOG
+        .iter()
+        .find(|a| a.name == name && a.applies_to(element))
+}
+
+/// Returns all the attributes in the alphabetical order.
+pub fn all() -> &'static [AttributeInfo] {
+    CATALOG
+}
+
+macro_rules! element {
+    (G) => {
+        Element::Graph
+    };
+    (S) => {
+        Element::Subgraph
+    };
+    (C) => {
+        Element::Cluster
+    };
+    (N) => {
+        Element::Node
+    };
+    (E) => {
+        Element::Edge
+    };
+}
+
+macro_rules! opt {
+    (-) => {
+        None
+    };
+    ($v:literal) => {
+        Some($v)
+    };
+}
+
+macro_rules! catalog {
+    ($($name:ident [$($el:ident)+] [$($ty:literal)+] $default:tt $min:tt [$($engine:literal)*] [$($output:literal)*] $desc:literal;)+) => {
+        static CATALOG: &[AttributeInfo] = &[
+            $(AttributeInfo {
+                name: stringify!($name),
+                used_by: &[$(element!($el)),+],
+                types: &[$($ty),+],
+                default: opt!($default),
+                minimum: opt!($min),
+                engines: &[$($engine),*],
+                outputs: &[$($output),*],
+                description: $desc,
+            },)+
+        ];
+    };
+}
+
+// name [used by] [types] default minimum [engines] [outputs] description
+catalog! {
+    //region attributes
+    _background [G] ["xdot"] - - [] [] "The xdot drawing rendered as the background of the graph.";
+    area [N C] ["double"] "1.0" ">0" ["patchwork"] [] "The preferred area of the node or the empty cluster.";
+    arrowhead [E] ["arrowType"] "normal" - [] [] "The style of the arrowhead at the head end of the edge.";
+    arrowsize [E] ["double"] "1.0" "0.0" [] [] "The multiplicative scale factor for the arrowheads.";
+    arrowtail [E] ["arrowType"] "normal" - [] [] "The style of the arrowhead at the tail end of the edge.";
+    bb [G] ["rect"] - - [] [] "The bounding box of the drawing in points, written by the layout.";
+    beautify [G] ["bool"] "false" - ["sfdp"] [] "Draws the leaf nodes uniformly in a circle around the root node.";
+    bgcolor [G C] ["color" "colorList"] - - [] [] "The background color of the drawing or the cluster.";
+    center [G] ["bool"] "false" - [] [] "Centers the drawing in the output canvas.";
+    charset [G] ["string"] "UTF-8" - [] [] "The character encoding used to interpret the text labels.";
+    class [E N C G] ["string"] "" - [] ["svg"] "The classnames attached to the svg element.";
+    cluster [C S] ["bool"] "false" - [] [] "Marks the subgraph as the cluster.";
+    clusterrank [G] ["clusterMode"] "local" - ["dot"] [] "The mode used for handling the clusters.";
+    color [E N C] ["color" "colorList"] "black" - [] [] "The basic drawing color for the graphics, not the text.";
+    colorscheme [E N C G] ["string"] "" - [] [] "The color scheme namespace used to interpret the color names.";
+    comment [E N G] ["string"] "" - [] [] "The comment inserted into the output.";
+    compound [G] ["bool"] "false" - ["dot"] [] "Allows the edges between the clusters.";
+    concentrate [G] ["bool"] "false" - [] [] "Merges the multiedges into one edge and the parallel parts of the edges.";
+    constraint [E] ["bool"] "true" - ["dot"] [] "Uses the edge in the ranking of the nodes.";
+    Damping [G] ["double"] "0.99" "0.0" ["neato"] [] "The factor damping the force motions on every iteration.";
+    decorate [E] ["bool"] "false" - [] [] "Connects the edge label to the edge with the line.";
+    defaultdist [G] ["double"] "1+(avg. len)*sqrt(|V|)" "epsilon" ["neato"] [] "The distance between the nodes in the separate connected components.";
+    dim [G] ["int"] "2" "2" ["neato" "fdp" "sfdp"] [] "The number of the dimensions used for the layout.";
+    dimen [G] ["int"] "2" "2" ["neato" "fdp" "sfdp"] [] "The number of the dimensions used for the rendering.";
+    dir [E] ["dirType"] "forward" - [] [] "The edge type for drawing the arrowheads, `none` in the undirected graphs.";
+    diredgeconstraints [G] ["string" "bool"] "false" - ["neato"] [] "Constrains most of the edges to point downwards.";
+    distortion [N] ["double"] "0.0" "-100.0" [] [] "The distortion factor of the polygon shapes.";
+    dpi [G] ["double"] "96.0" - [] ["svg" "bitmap"] "The expected number of the pixels per inch on the display device.";
+    edgehref [E] ["escString"] "" - [] ["svg" "map"] "The synonym for edgeURL.";
+    edgetarget [E] ["escString"] - - [] ["svg" "map"] "The browser window used for the edgeURL link.";
+    edgetooltip [E] ["escString"] "" - [] ["svg" "cmap"] "The tooltip annotation attached to the non-label part of the edge.";
+    edgeURL [E] ["escString"] "" - [] ["svg" "map"] "The link used for the non-label parts of the edge.";
+    epsilon [G] ["double"] ".0001 * # nodes" - ["neato"] [] "The terminating condition of the layout.";
+    esep [G] ["addDouble" "addPoint"] "+3" - ["neato" "fdp" "sfdp" "twopi" "circo"] [] "The margin around the polygons used by the spline edge routing.";
+    fillcolor [N E C] ["color" "colorList"] "lightgrey" - [] [] "The color used to fill the background of the node or the cluster.";
+    fixedsize [N] ["bool" "string"] "false" - [] [] "Uses the width and height as the node size regardless of the label.";
+    fontcolor [E N G C] ["color"] "black" - [] [] "The color used for the text.";
+    fontname [E N G C] ["string"] "Times-Roman" - [] [] "The font used for the text.";
+    fontnames [G] ["string"] "" - [] ["svg"] "The kind of the font names used in the svg output.";
+    fontpath [G] ["string"] - - [] [] "The directory list used to search for the bitmap fonts.";
+    fontsize [E N G C] ["double"] "14.0" "1.0" [] [] "The font size in points used for the text.";
+    forcelabels [G] ["bool"] "true" - [] [] "Places all the xlabels even if they overlap.";
+    gradientangle [N C G] ["int"] "" - [] [] "The angle of the gradient fill.";
+    group [N] ["string"] "" - ["dot"] [] "Keeps the edges between the nodes of the same group straight.";
+    head_lp [E] ["point"] - - [] [] "The position of the head label in points, written by the layout.";
+    headclip [E] ["bool"] "true" - [] [] "Clips the head of the edge to the boundary of the head node.";
+    headhref [E] ["escString"] "" - [] ["svg" "map"] "The synonym for headURL.";
+    headlabel [E] ["lblString"] "" - [] [] "The text label placed near the head of the edge.";
+    headport [E] ["portPos"] "center" - [] [] "The point of the head node the edge is aimed at.";
+    headtarget [E] ["escString"] - - [] ["svg" "map"] "The browser window used for the headURL link.";
+    headtooltip [E] ["escString"] "" - [] ["svg" "cmap"] "The tooltip annotation attached to the head of the edge.";
+    headURL [E] ["escString"] "" - [] ["svg" "map"] "The link used for the head of the edge.";
+    height [N] ["double"] "0.5" "0.02" [] [] "The height of the node in inches.";
+    href [G C N E] ["escString"] "" - [] ["svg" "postscript" "map"] "The synonym for URL.";
+    id [G C N E] ["escString"] "" - [] ["svg" "postscript" "map"] "The identifier of the graph element in the output.";
+    image [N] ["string"] "" - [] [] "The file containing the image displayed inside the node.";
+    imagepath [G] ["string"] "" - [] [] "The directory list used to search for the image files.";
+    imagepos [N] ["string"] "mc" - [] [] "The position of the image within the node.";
+    imagescale [N] ["bool" "string"] "false" - [] [] "The way the image fills the node.";
+    inputscale [G] ["double"] - - ["neato" "fdp"] [] "The scale of the input positions in points per inch.";
+    K [G C] ["double"] "0.3" "0" ["sfdp" "fdp"] [] "The spring constant used in the virtual physical model.";
+    label [E N G C] ["lblString"] "\\N" - [] [] "The text label attached to the element, the node name for the nodes.";
+    label_scheme [G] ["int"] "0" "0" ["sfdp"] [] "The treatment of the nodes with the name `|edgelabel|*`.";
+    labelangle [E] ["double"] "-25.0" "-180.0" [] [] "The angle in degrees of the head and tail labels from the edge.";
+    labeldistance [E] ["double"] "1.0" "0.0" [] [] "The scale factor of the distance of the head and tail labels from the node.";
+    labelfloat [E] ["bool"] "false" - [] [] "Allows the edge labels to be less constrained in position.";
+    labelfontcolor [E] ["color"] "black" - [] [] "The color used for the head and tail labels.";
+    labelfontname [E] ["string"] "Times-Roman" - [] [] "The font used for the head and tail labels.";
+    labelfontsize [E] ["double"] "14.0" "1.0" [] [] "The font size of the head and tail labels.";
+    labelhref [E] ["escString"] "" - [] ["svg" "map"] "The synonym for labelURL.";
+    labeljust [G C] ["string"] "c" - [] [] "The justification of the graph and cluster labels.";
+    labelloc [N G C] ["string"] "t" - [] [] "The vertical placement of the labels of the nodes, the graphs and the clusters.";
+    labeltarget [E] ["escString"] - - [] ["svg" "map"] "The browser window used for the labelURL link.";
+    labeltooltip [E] ["escString"] "" - [] ["svg" "cmap"] "The tooltip annotation attached to the label of the edge.";
+    labelURL [E] ["escString"] "" - [] ["svg" "map"] "The link used for the label of the edge.";
+    landscape [G] ["bool"] "false" - [] [] "Renders the graph in the landscape mode.";
+    layer [E N C] ["layerRange"] "" - [] [] "The layers the element is present in.";
+    layerlistsep [G] ["string"] "," - [] [] "The separator characters used to split the layer list into the ranges.";
+    layers [G] ["layerList"] "" - [] [] "The linearly ordered list of the layer names attached to the graph.";
+    layerselect [G] ["layerRange"] "" - [] [] "The layers to be emitted.";
+    layersep [G] ["string"] ":\t " - [] [] "The separator characters used to split the layers attribute into the names.";
+    layout [G] ["string"] "" - [] [] "The layout engine used for the graph.";
+    len [E] ["double"] "1.0" - ["neato" "fdp"] [] "The preferred edge length in inches.";
+    levels [G] ["int"] "2147483647" "0.0" ["sfdp"] [] "The number of the levels allowed in the multilevel scheme.";
+    levelsgap [G] ["double"] "0.0" - ["neato"] [] "The strictness of the level constraints in neato.";
+    lhead [E] ["string"] "" - ["dot"] [] "The logical head of the edge, the cluster the edge is clipped to.";
+    lheight [G C] ["double"] - - [] [] "The height of the graph or cluster label in inches, written by the layout.";
+    linelength [G] ["int"] "128" "60" [] [] "The length of the lines in the output before they are split.";
+    lp [E G C] ["point"] - - [] [] "The position of the label in points, written by the layout.";
+    ltail [E] ["string"] "" - ["dot"] [] "The logical tail of the edge, the cluster the edge is clipped to.";
+    lwidth [G C] ["double"] - - [] [] "The width of the graph or cluster label in inches, written by the layout.";
+    margin [N C G] ["double" "point"] - - [] [] "The margin around the drawing, the cluster or the node label.";
+    maxiter [G] ["int"] "100 * # nodes" - ["neato" "fdp"] [] "The number of the iterations used by the layout.";
+    mclimit [G] ["double"] "1.0" - ["dot"] [] "The scale factor of the iterations of the crossing minimization.";
+    mindist [G] ["double"] "1.0" "0.0" ["circo"] [] "The minimum separation between all the nodes.";
+    minlen [E] ["int"] "1" "0" ["dot"] [] "The minimum edge length in ranks.";
+    mode [G] ["string"] "major" - ["neato" "sfdp"] [] "The technique used to optimize the layout.";
+    model [G] ["string"] "shortpath" - ["neato"] [] "The way the distance matrix is computed.";
+    mosek [G] ["bool"] "false" - ["neato"] [] "Uses the mosek software for solving the optimization problems.";
+    newrank [G] ["bool"] "false" - ["dot"] [] "Uses the single global ranking ignoring the clusters.";
+    nodesep [G] ["double"] "0.25" "0.02" [] [] "The minimum space between the adjacent nodes in the same rank in inches.";
+    nojustify [G C N E] ["bool"] "false" - [] [] "Justifies the multiline labels against the whole element rather than the widest line.";
+    normalize [G] ["double" "bool"] "false" - ["neato" "fdp" "sfdp" "twopi" "circo"] [] "Normalizes the coordinates of the final layout.";
+    notranslate [G] ["bool"] "false" - ["neato"] [] "Keeps the layout from being translated to the origin.";
+    nslimit [G] ["double"] - - ["dot"] [] "The number of the iterations of the network simplex used for the placement.";
+    nslimit1 [G] ["double"] - - ["dot"] [] "The number of the iterations of the network simplex used for the ranking.";
+    oneblock [G] ["bool"] "false" - ["circo"] [] "Draws the circo graph around one circle.";
+    ordering [G N] ["string"] "" - ["dot"] [] "Constrains the left to right ordering of the node edges.";
+    orientation [N] ["double"] "0.0" "360.0" [] [] "The node rotation angle in degrees.";
+    orientation [G] ["string"] "" - [] [] "Rotates the drawing in the landscape mode if the value starts with `l` or `L`.";
+    outputorder [G] ["outputMode"] "breadthfirst" - [] [] "The order in which the nodes and the edges are drawn.";
+    overlap [G] ["string" "bool"] "true" - ["neato" "fdp" "sfdp" "twopi" "circo"] [] "The way the node overlaps are removed.";
+    overlap_scaling [G] ["double"] "-4" "-1.0e+10" ["neato" "fdp" "sfdp" "twopi" "circo"] [] "The scale of the layout reducing the node overlaps with the prism mode.";
+    overlap_shrink [G] ["bool"] "true" - ["neato" "fdp" "sfdp" "twopi" "circo"] [] "Compresses the layout after the prism overlap removal.";
+    pack [G] ["bool" "int"] "false" - [] [] "Lays out the connected components separately and packs them together.";
+    packmode [G] ["packMode"] "node" - [] [] "The way the connected components are packed.";
+    pad [G] ["double" "point"] "0.0555" - [] [] "The extra space around the drawing in inches.";
+    page [G] ["double" "point"] - - [] [] "The width and height of the output pages in inches.";
+    pagedir [G] ["pagedir"] "BL" - [] [] "The order in which the pages are emitted.";
+    pencolor [C] ["color"] "black" - [] [] "The color used to draw the bounding box around the cluster.";
+    penwidth [C N E] ["double"] "1.0" "0.0" [] [] "The width of the pen in points used to draw the lines and curves.";
+    peripheries [N C] ["int"] - "0" [] [] "The number of the peripheries used in the polygonal shapes and the cluster boundaries.";
+    pin [N] ["bool"] "false" - ["neato" "fdp"] [] "Keeps the node at the position given by pos.";
+    pos [E N] ["point" "splineType"] - - [] [] "The position of the node or the spline control points of the edge in points.";
+    quadtree [G] ["quadType" "bool"] "normal" - ["sfdp"] [] "The quadtree scheme used.";
+    quantum [G] ["double"] "0.0" "0.0" [] [] "Rounds the node label dimensions up to the multiples of the quantum.";
+    rank [S] ["rankType"] - - ["dot"] [] "The rank constraints on the nodes of the subgraph.";
+    rankdir [G] ["rankdir"] "TB" - ["dot"] [] "The direction of the graph layout.";
+    ranksep [G] ["double" "doubleList"] "0.5" "0.02" ["dot" "twopi"] [] "The separation between the ranks in inches.";
+    ratio [G] ["double" "string"] - - [] [] "The aspect ratio of the drawing.";
+    rects [N] ["rect"] - - [] [] "The rectangles of the fields in the record shape in points, written by the layout.";
+    regular [N] ["bool"] "false" - [] [] "Forces the polygon to be regular.";
+    remincross [G] ["bool"] "true" - ["dot"] [] "Runs the edge crossing minimization again with the clusters.";
+    repulsiveforce [G] ["double"] "1.0" "0.0" ["sfdp"] [] "The power of the repulsive force used in the spring electrical model.";
+    resolution [G] ["double"] "96.0" - [] ["svg" "bitmap"] "The synonym for dpi.";
+    root [G N] ["string" "bool"] - - ["twopi" "circo"] [] "The center node of the layout.";
+    rotate [G] ["int"] "0" - [] [] "Draws the graph in the landscape mode if the value is 90.";
+    rotation [G] ["double"] "0" - ["sfdp"] [] "Rotates the final layout counter-clockwise by the given degrees.";
+    samehead [E] ["string"] "" - ["dot"] [] "Aims the edges with the same value at the same point of the head node.";
+    sametail [E] ["string"] "" - ["dot"] [] "Aims the edges with the same value at the same point of the tail node.";
+    samplepoints [N] ["int"] "8" - [] [] "The number of the points used for the circle or ellipse node.";
+    scale [G] ["double" "point"] - - ["neato" "fdp" "sfdp" "twopi" "circo"] [] "Scales the layout by the given factor after the initial layout.";
+    searchsize [G] ["int"] "30" - ["dot"] [] "The maximum number of the edges with the negative cut values searched for the minimum one.";
+    sep [G] ["addDouble" "addPoint"] "+4" - ["neato" "fdp" "sfdp" "twopi" "circo"] [] "The margin left around the nodes when removing the node overlap.";
+    shape [N] ["shape"] "ellipse" - [] [] "The shape of the node.";
+    shapefile [N] ["string"] "" - [] [] "The file containing the user-supplied node content.";
+    showboxes [E N G] ["int"] "0" "0" ["dot"] [] "Prints the guide boxes for debugging.";
+    sides [N] ["int"] "4" "0" [] [] "The number of the sides of the polygon shape.";
+    size [G] ["double" "point"] - - [] [] "The maximum width and height of the drawing in inches.";
+    skew [N] ["double"] "0.0" "-100.0" [] [] "The skew factor of the polygon shapes.";
+    smoothing [G] ["smoothType"] "none" - ["sfdp"] [] "The post-processing step used to adjust the uneven distribution of the nodes.";
+    sortv [G C N] ["int"] "0" "0" [] [] "The sort order of the graph components when packing with the array mode.";
+    splines [G] ["bool" "string"] - - [] [] "The way the edges are represented.";
+    start [G] ["startType"] "" - ["neato" "fdp"] [] "The initial placement of the nodes.";
+    style [E N C G] ["style"] "" - [] [] "The style information for the components of the graph.";
+    stylesheet [G] ["string"] "" - [] ["svg"] "The url or pathname of the xml style sheet used in the svg output.";
+    tail_lp [E] ["point"] - - [] [] "The position of the tail label in points, written by the layout.";
+    tailclip [E] ["bool"] "true" - [] [] "Clips the tail of the edge to the boundary of the tail node.";
+    tailhref [E] ["escString"] "" - [] ["svg" "map"] "The synonym for tailURL.";
+    taillabel [E] ["lblString"] "" - [] [] "The text label placed near the tail of the edge.";
+    tailport [E] ["portPos"] "center" - [] [] "The point of the tail node the edge is aimed at.";
+    tailtarget [E] ["escString"] - - [] ["svg" "map"] "The browser window used for the tailURL link.";
+    tailtooltip [E] ["escString"] "" - [] ["svg" "cmap"] "The tooltip annotation attached to the tail of the edge.";
+    tailURL [E] ["escString"] "" - [] ["svg" "map"] "The link used for the tail of the edge.";
+    target [E N G C] ["escString" "string"] - - [] ["svg" "map"] "The browser window used for the URL link.";
+    TBbalance [G] ["string"] "" - ["dot"] [] "Moves the floating nodes to the top or the bottom ranks.";
+    tooltip [N E G C] ["escString"] "" - [] ["svg" "cmap"] "The tooltip annotation attached to the element.";
+    truecolor [G] ["bool"] - - [] ["bitmap"] "Uses the truecolor color model for the bitmap rendering.";
+    URL [E N G C] ["escString"] - - [] ["svg" "postscript" "map"] "The hyperlink included in the output.";
+    vertices [N] ["pointList"] - - [] [] "The coordinates of the vertices of the polygonal node in points, written by the layout.";
+    viewport [G] ["viewPort"] "" - [] [] "The clipping window on the final drawing.";
+    voro_margin [G] ["double"] "0.05" "0.0" ["neato" "fdp" "sfdp" "twopi" "circo"] [] "The factor of the bounding box expansion used by the voronoi overlap removal.";
+    weight [E] ["int" "double"] "1" "0" [] [] "The weight of the edge.";
+    width [N] ["double"] "0.75" "0.01" [] [] "The width of the node in inches.";
+    xdotversion [G] ["string"] - - [] ["xdot"] "The version of the xdot format used in the output.";
+    xlabel [E N] ["lblString"] "" - [] [] "The external label of the element placed outside of it.";
+    xlp [N E] ["point"] - - [] [] "The position of the external label in points, written by the layout.";
+    z [N] ["double"] "0.0" "-MAXFLOAT" [] [] "The z coordinate of the node in the 3D output.";
+    //endregion
+}
+
+#[cfg(test)]
+mod tests {
+    use dot_generator::{attr, id};
+    use dot_structures::*;
+    use into_attr::FromAttribute;
+
+    use crate::attributes::{
+        catalog::{all, lookup, lookup_for, Element},
+        TypedAttribute,
+    };
+
+    /// The pairs of the attribute name and the owner of the typed function from the declarations.
+    fn typed_owners() -> Vec<(String, String)> {
+        let source = include_str!("mod.rs");
+        let mut owners = vec![];
+        for line in source.lines() {
+            if let Some(rest) = line.strip_prefix("generate_attr!(") {
+                let decl = rest.split(';').next().unwrap();
+                if let Some((name, elems)) = decl.split_once(" for ") {
+                    let name = name.split_whitespace().last().unwrap();
+                    for elem in elems.split(',') {
+                        owners.push((name.to_string(), elem.trim().to_string()));
+                    }
+                }
+            }
+        }
+        for line in include_str!("styles.rs").lines() {
+            if let Some(rest) = line.strip_prefix("target_style!(") {
+                let elem = rest.trim_end_matches(");").split(", ").last().unwrap();
+                owners.push(("style".to_string(), elem.to_string()));
+            }
+        }
+        owners
+    }
+
+    #[test]
+    fn lookup_test() {
+        let arrowhead = lookup("arrowhead").unwrap();
+        assert_eq!(arrowhead.used_by, &[Element::Edge]);
+        assert_eq!(arrowhead.default, Some("normal"));
+        assert!(arrowhead.honored_by("neato"));
+        assert!(!lookup("rankdir").unwrap().honored_by("neato"));
+        assert_eq!(
+            lookup_for("orientation", Element::Node).unwrap().types,
+            &["double"]
+        );
+        assert!(lookup_for("shape", Element::Edge).is_none());
+        assert!(all()
+            .windows(2)
+            .all(|w| w[0].name.to_lowercase() <= w[1].name.to_lowercase()));
+    }
+
+    #[test]
+    fn typed_consistency_test() {
+        let owners = typed_owners();
+        assert!(owners.len() > 200);
+        for (name, owner) in owners {
+            let elements: &[Element] = match owner.as_str() {
+                "GraphAttributes" => &[Element::Graph],
+                "SubgraphAttributes" => &[Element::Subgraph, Element::Cluster],
+                "NodeAttributes" => &[Element::Node],
+                "EdgeAttributes" => &[Element::Edge],
+                o => panic!("unexpected owner {}", o),
+            };
+            assert!(
+                elements.iter().any(|e| lookup_for(&name, *e).is_some()),
+                "the attribute '{}' of {} is not in the catalogue",
+                name,
+                owner
+            );
+        }
+    }
+
+    #[test]
+    fn typed_default_test() {
+        for info in all() {
+            let typed = TypedAttribute::from_attr(&attr!(info.name, "x"));
+            if let Ok(TypedAttribute::Unknown(_)) = typed {
+                continue;
+            }
+            let default = match info.default {
+                Some(d) if !d.is_empty() && !d.contains(' ') && !d.contains('\\') => d,
+                _ => continue,
+            };
+            let attr = Attribute(id!(info.name), id!(esc default));
+            assert!(
+                TypedAttribute::from_attr(&attr).is_ok(),
+                "the default '{}' of the attribute '{}' is rejected",
+                default,
+                info.name
+            );
+        }
+    }
+}
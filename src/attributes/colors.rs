@@ -0,0 +1,337 @@
+//! The values of the color attributes.
+//!
+//! Graphviz accepts the [`color`] given by the name, possibly qualified with the color scheme,
+//! the rgb and rgba values in the hex notation and the hsv and hsva values. Some attributes
+//! accept also the [`colorList`], the list of the colors with the optional weights used for
+//! the gradients and the striped or wedged fills.
+//!
+//! [`color`]: https://graphviz.org/docs/attr-types/color/
+//! [`colorList`]: https://graphviz.org/docs/attr-types/colorList/
+use std::{
+    fmt::{Display, Formatter},
+    str::FromStr,
+};
+
+use crate::attributes::color_name;
+
+/// The color in one of the graphviz notations.
+///
+/// # Example:
+/// ```rust
+/// use graphviz_rust::attributes::{color_name, Color};
+///
+/// assert_eq!(Color::parse("#ff8800").unwrap(), Color::Rgb(255, 136, 0));
+/// assert_eq!(Color::parse("/blues9/3").unwrap(), Color::scheme("blues9", "3"));
+/// assert_eq!(Color::Rgba(0, 0, 255, 128).to_string(), "#0000ff80");
+/// assert_eq!(Color::from(color_name::red).to_string(), "red");
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub enum Color {
+    /// the name resolved in the current color scheme, x11 by default
+    Named(String),
+    /// the name in the given scheme, `/scheme/name`, the empty scheme denotes the default one
+    Scheme { scheme: String, name: String },
+    /// `#rrggbb`
+    Rgb(u8, u8, u8),
+    /// `#rrggbbaa`
+    Rgba(u8, u8, u8, u8),
+    /// `h s v` where every component is in the range 0..1
+    Hsv(f32, f32, f32),
+    /// `h s v a` where every component is in the range 0..1
+    Hsva(f32, f32, f32, f32),
+}
+
+impl Color {
+    pub fn named(name: &str) -> Color {
+        Color::Named(name.to_string())
+    }
+
+    pub fn scheme(scheme: &str, name: &str) -> Color {
+        Color::Scheme {
+            scheme: scheme.to_string(),
+            name: name.to_string(),
+        }
+    }
+
+    /// Parses the color in any of the graphviz notations.
+    pub fn parse(value: &str) -> Result<Color, String> {
+        let value = value.trim();
+        if let Some(hex) = value.strip_prefix('#') {
+            let byte = |i: usize| {
+                hex.get(i..i + 2)
+                    .and_then(|b| u8::from_str_radix(b, 16).ok())
+                    .ok_or_else(|| format!("the color '{}' is not a valid hex color", value))
+            };
+            return match hex.len() {
+                6 => Ok(Color::Rgb(byte(0)?, byte(2)?, byte(4)?)),
+                8 => Ok(Color::Rgba(byte(0)?, byte(2)?, byte(4)?, byte(6)?)),
+                _ => Err(format!("the color '{}' is not a valid hex color", value)),
+            };
+        }
+        if let Some(rest) = value.strip_prefix('/') {
+            return match rest.split_once('/') {
+                Some((scheme, name)) if !name.is_empty() && !name.contains('/') => {
+                    Ok(Color::scheme(scheme, name))
+                }
+                _ => Err(format!("the color '{}' is not a valid scheme color", value)),
+            };
+        }
+        let parts: Vec<&str> = value
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|p| !p.is_empty())
+            .collect();
+        if parts.len() == 3 || parts.len() == 4 {
+            let component = |p: &str| match p.parse::<f32>() {
+                Ok(v) if (0.0..=1.0).contains(&v) => Ok(v),
+                _ => Err(format!(
+                    "the hsv component '{}' in '{}' should be a number in the range 0..1",
+                    p, value
+                )),
+            };
+            let (h, s, v) = (
+                component(parts[0])?,
+                component(parts[1])?,
+                component(parts[2])?,
+            );
+            return match parts.get(3) {
+                Some(a) => Ok(Color::Hsva(h, s, v, component(a)?)),
+                None => Ok(Color::Hsv(h, s, v)),
+            };
+        }
+        let valid_name = !value.is_empty()
+            && value
+                .chars()
+                .all(|c| c.is_alphanumeric() || c == '_' || c == '-');
+        if valid_name {
+            Ok(Color::named(value))
+        } else {
+            Err(format!("the color '{}' is unknown", value))
+        }
+    }
+}
+
+impl FromStr for Color {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Color::parse(s)
+    }
+}
+
+impl Display for Color {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Color::Named(name) => f.write_str(name),
+            Color::Scheme { scheme, name } => write!(f, "/{}/{}", scheme, name),
+            Color::Rgb(r, g, b) => write!(f, "#{:02x}{:02x}{:02x}", r, g, b),
+            Color::Rgba(r, g, b, a) => write!(f, "#{:02x}{:02x}{:02x}{:02x}", r, g, b, a),
+            Color::Hsv(h, s, v) => write!(f, "{} {} {}", h, s, v),
+            Color::Hsva(h, s, v, a) => write!(f, "{} {} {} {}", h, s, v, a),
+        }
+    }
+}
+
+impl From<color_name> for Color {
+    fn from(value: color_name) -> Self {
+        Color::Named(value.to_string())
+    }
+}
+
+/// The color with the optional weight, the fraction of the area filled with the color.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WeightedColor {
+    pub color: Color,
+    pub weight: Option<f32>,
+}
+
+impl Display for WeightedColor {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.weight {
+            Some(w) => write!(f, "{};{}", self.color, w),
+            None => write!(f, "{}", self.color),
+        }
+    }
+}
+
+/// The list of the colors separated by `:` where every color can have the weight after `;`.
+///
+/// # Example:
+/// ```rust
+/// use graphviz_rust::attributes::{Color, ColorList};
+///
+/// let list = ColorList::parse("red;0.3:#0000ff").unwrap();
+/// assert_eq!(list.colors[0].weight, Some(0.3));
+/// assert_eq!(list.colors[1].color, Color::Rgb(0, 0, 255));
+/// assert_eq!(
+///     ColorList::new()
+///         .with_weighted(Color::named("red"), 0.3)
+///         .with(Color::Rgb(0, 0, 255))
+///         .to_string(),
+///     "red;0.3:#0000ff"
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ColorList {
+    pub colors: Vec<WeightedColor>,
+}
+
+impl ColorList {
+    pub fn new() -> Self {
+        ColorList::default()
+    }
+
+    pub fn with(mut self, color: Color) -> Self {
+        self.colors.push(WeightedColor {
+            color,
+            weight: None,
+        });
+        self
+    }
+
+    pub fn with_weighted(mut self, color: Color, weight: f32) -> Self {
+        self.colors.push(WeightedColor {
+            color,
+            weight: Some(weight),
+        });
+        self
+    }
+
+    /// Parses the list checking that the weights are in the range 0..1 and their sum does not exceed 1.
+    pub fn parse(value: &str) -> Result<ColorList, String> {
+        let mut colors = vec![];
+        let mut total = 0.0;
+        for item in value.split(':') {
+            let (color, weight) = match item.split_once(';') {
+                Some((color, weight)) => {
+                    let weight = match weight.trim().parse::<f32>() {
+                        Ok(w) if (0.0..=1.0).contains(&w) => w,
+                        _ => {
+                            return Err(format!(
+                                "the weight '{}' in '{}' should be a number in the range 0..1",
+                                weight, value
+                            ))
+                        }
+                    };
+                    total += weight;
+                    (color, Some(weight))
+                }
+                None => (item, None),
+            };
+            colors.push(WeightedColor {
+                color: Color::parse(color)?,
+                weight,
+            });
+        }
+        if total > 1.0 + f32::EPSILON {
+            return Err(format!("the sum of the weights in '{}' exceeds 1", value));
+        }
+        Ok(ColorList { colors })
+    }
+}
+
+impl FromStr for ColorList {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        ColorList::parse(s)
+    }
+}
+
+impl Display for ColorList {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let colors: Vec<String> = self.colors.iter().map(|c| c.to_string()).collect();
+        f.write_str(colors.join(":").as_str())
+    }
+}
+
+impl From<Color> for ColorList {
+    fn from(color: Color) -> Self {
+        ColorList::new().with(color)
+    }
+}
+
+impl From<color_name> for ColorList {
+    fn from(value: color_name) -> Self {
+        ColorList::from(Color::from(value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::attributes::{color_name, Color, ColorList, WeightedColor};
+
+    #[test]
+    fn color_test() {
+        assert_eq!(Color::parse("#FF8800").unwrap(), Color::Rgb(255, 136, 0));
+        assert_eq!(
+            Color::parse("#ff880080").unwrap(),
+            Color::Rgba(255, 136, 0, 128)
+        );
+        assert_eq!(
+            Color::parse("0.5 0.7,0.9").unwrap(),
+            Color::Hsv(0.5, 0.7, 0.9)
+        );
+        assert_eq!(
+            Color::parse("0.5,0.7,0.9,0.25").unwrap(),
+            Color::Hsva(0.5, 0.7, 0.9, 0.25)
+        );
+        assert_eq!(Color::parse("//red").unwrap(), Color::scheme("", "red"));
+        assert_eq!(
+            Color::parse("antiquewhite1").unwrap(),
+            Color::named("antiquewhite1")
+        );
+        assert_eq!(Color::from(color_name::black), Color::named("black"));
+
+        for c in [
+            "#ff8800",
+            "#0000ff80",
+            "0.5 0.7 0.9",
+            "0.5 0.7 0.9 0.25",
+            "/blues9/3",
+            "//red",
+            "red",
+        ] {
+            assert_eq!(Color::parse(c).unwrap().to_string(), c);
+        }
+    }
+
+    #[test]
+    fn color_error_test() {
+        assert!(Color::parse("#ff88").is_err());
+        assert!(Color::parse("#gg8800").is_err());
+        assert!(Color::parse("1.5 0 0").is_err());
+        assert!(Color::parse("0.5 0 0 2").is_err());
+        assert!(Color::parse("0.5 0 0 0 0").is_err());
+        assert!(Color::parse("/blues9").is_err());
+        assert!(Color::parse("").is_err());
+        assert!(Color::parse("red;0.3").is_err());
+    }
+
+    #[test]
+    fn color_list_test() {
+        let list: ColorList = "red;0.3:/blues9/3:#00ff00;0.2".parse().unwrap();
+        assert_eq!(
+            list.colors,
+            vec![
+                WeightedColor {
+                    color: Color::named("red"),
+                    weight: Some(0.3)
+                },
+                WeightedColor {
+                    color: Color::scheme("blues9", "3"),
+                    weight: None
+                },
+                WeightedColor {
+                    color: Color::Rgb(0, 255, 0),
+                    weight: Some(0.2)
+                },
+            ]
+        );
+        assert_eq!(list.to_string(), "red;0.3:/blues9/3:#00ff00;0.2");
+        assert_eq!(ColorList::from(color_name::red).to_string(), "red");
+
+        assert!(ColorList::parse("red;0.7:blue;0.5").is_err());
+        assert!(ColorList::parse("red;x").is_err());
+        assert!(ColorList::parse("red::blue").is_err());
+    }
+}
@@ -20,10 +20,11 @@ macro_rules! generate_attr {
    };
     (enum $name:tt for $($owners:tt),+; $($values:tt),+) =>{
          as_item! {
-             #[derive(Debug,PartialEq, IntoAttribute)]
+             #[derive(Debug,PartialEq, IntoAttribute, FromAttribute)]
              pub enum $name { $($values),+ }
          }
 
+         $(impl $crate::attributes::AttributeOf<$owners> for $name {})+
          $(impl $owners {
                  pub fn $name(elem:$name) -> Attribute {
                       elem.into_attr()
@@ -34,7 +35,7 @@ macro_rules! generate_attr {
    };
     (enum $name:tt for $($owners:tt),+; $($values:tt),+;$default:tt ) =>{
          as_item! {
-             #[derive(Debug,PartialEq, IntoAttribute)]
+             #[derive(Debug,PartialEq, IntoAttribute, FromAttribute)]
              pub enum $name { $($values),+ }
          }
 
@@ -42,6 +43,7 @@ macro_rules! generate_attr {
              fn default() -> Self { $name::$default }
          }
 
+         $(impl $crate::attributes::AttributeOf<$owners> for $name {})+
          $(impl $owners{
                  pub fn $name(elem:$name) -> Attribute {
                       elem.into_attr()
@@ -51,11 +53,49 @@ macro_rules! generate_attr {
 
 
    };
+    (struct $name:tt for $($owners:tt),+; into $ty:tt) =>{
+        as_item! {
+             #[derive(Debug,PartialEq)]
+             pub struct $name (pub $ty);
+        }
+        impl IntoAttribute for $name {
+            fn into_attr(self) -> Attribute {
+                Attribute(id!(stringify!($name)), $crate::attributes::value_id(self.0.to_string()))
+            }
+        }
+        impl FromAttribute for $name {
+            fn from_attr(attr: &Attribute) -> Result<Self, String> {
+                let value = into_attr::attr_value(attr, stringify!($name))?;
+                into_attr::parse_value::<$ty>(&value, stringify!($name)).map($name)
+            }
+        }
+        impl TryFrom<&Attribute> for $name {
+            type Error = String;
+
+            fn try_from(attr: &Attribute) -> Result<Self, Self::Error> {
+                $name::from_attr(attr)
+            }
+        }
+        $(impl $crate::attributes::AttributeOf<$owners> for $name {})+
+        $(impl $owners {
+                 pub fn $name(elem: impl Into<$ty>) -> Attribute {
+                     $name(elem.into()).into_attr()
+                  }
+             }
+        )+
+    };
+    (struct $name:tt for $($owners:tt),+; into $ty:tt; $default:expr) =>{
+        generate_attr!(struct $name for $($owners),+; into $ty);
+        impl Default for $name{
+                 fn default() -> Self { $name($default) }
+        }
+    };
     (struct $name:tt for $($owners:tt),+; $ty:tt) =>{
         as_item! {
-             #[derive(Debug,PartialEq, IntoAttribute)]
-             pub struct $name ($ty);
+             #[derive(Debug,PartialEq, IntoAttribute, FromAttribute)]
+             pub struct $name (pub $ty);
         }
+        $(impl $crate::attributes::AttributeOf<$owners> for $name {})+
         $(impl $owners {
                  pub fn $name(elem:$ty) -> Attribute {
                      $name(elem).into_attr()
@@ -66,12 +106,13 @@ macro_rules! generate_attr {
     }; 
     (struct $name:tt for $($owners:tt),+; $ty:tt; $default:expr) =>{
          as_item! {
-             #[derive(Debug,PartialEq, IntoAttribute)]
-             pub struct $name ($ty);
+             #[derive(Debug,PartialEq, IntoAttribute, FromAttribute)]
+             pub struct $name (pub $ty);
          }
         impl Default for $name{
                  fn default() -> Self { $name($default) }
         }
+         $(impl $crate::attributes::AttributeOf<$owners> for $name {})+
          $(
              impl $owners{
                  pub fn $name(elem:$ty) -> Attribute {
@@ -82,3 +123,42 @@ macro_rules! generate_attr {
 
    };
 }
+
+#[macro_export]
+macro_rules! typed_attributes {
+    ($($name:tt),+) => {
+        /// The attribute with the typed value, the attributes unknown to graphviz are kept as is.
+        #[allow(non_camel_case_types)]
+        #[derive(Debug, PartialEq)]
+        pub enum TypedAttribute {
+            $($name($name),)+
+            Unknown(Attribute),
+        }
+
+        impl FromAttribute for TypedAttribute {
+            fn from_attr(attr: &Attribute) -> Result<Self, String> {
+                match into_attr::unquote(&attr.0).as_str() {
+                    $(stringify!($name) => $name::from_attr(attr).map(TypedAttribute::$name),)+
+                    _ => Ok(TypedAttribute::Unknown(attr.clone())),
+                }
+            }
+        }
+
+        impl TryFrom<&Attribute> for TypedAttribute {
+            type Error = String;
+
+            fn try_from(attr: &Attribute) -> Result<Self, Self::Error> {
+                TypedAttribute::from_attr(attr)
+            }
+        }
+
+        impl IntoAttribute for TypedAttribute {
+            fn into_attr(self) -> Attribute {
+                match self {
+                    $(TypedAttribute::$name(v) => v.into_attr(),)+
+                    TypedAttribute::Unknown(attr) => attr,
+                }
+            }
+        }
+    };
+}
@@ -0,0 +1,1669 @@
+//! The builder of the graphviz [`HTML-like labels`].
+//!
+//! The label is either the formatted text or the table possibly wrapped into the font elements.
+//! The tree of the label follows the graphviz grammar so most of the nesting rules are
+//! enforced by the types, the rest of them, e.g. the horizontal rule between the rows only,
+//! the legal attributes or the unique ports, is checked by [Label::validate].
+//! The text and the attribute values are escaped when the label is printed
+//! and unescaped when it is parsed, the named entities like `&copy;` are decoded
+//! into the characters.
+//!
+//! # Example:
+//! ```rust
+//! use dot_generator::*;
+//! use dot_structures::*;
+//! use graphviz_rust::attributes::html::{Cell, Label, Row, Table, Text};
+//!
+//! let label = Label::table(
+//!     Table::new()
+//!         .border(0)
+//!         .cellborder(1)
+//!         .row(Row::new().cell(Cell::text("a & b").port("in")).cell(Cell::text("c")))
+//!         .rule()
+//!         .row(Row::new().cell(Cell::new(Text::new().bold("x").br().str("y")).port("out"))),
+//! );
+//! assert_eq!(label.ports(), vec!["in", "out"]);
+//! assert_eq!(
+//!     label.to_id().unwrap(),
+//!     id!(html r#"<<TABLE BORDER="0" CELLBORDER="1"><TR><TD PORT="in">a &amp; b</TD><TD>c</TD></TR><HR/><TR><TD PORT="out"><B>x</B><BR/>y</TD></TR></TABLE>>"#)
+//! );
+//! ```
+//! The labels of the parsed graph are read back with [Label::from_id],
+//! the elements keep their positions relative to the label, see [Span]:
+//! ```rust
+//! use dot_generator::*;
+//! use dot_structures::*;
+//! use graphviz_rust::attributes::html::{Label, Span};
+//!
+//! let label = Label::from_id(&id!(html "<<TABLE><TR><TD PORT=\"p\"><B>x</B></TD></TR></TABLE>>")).unwrap();
+//! assert_eq!(label.ports(), vec!["p"]);
+//! if let Label::Table(_, table) = &label {
+//!     assert_eq!(table.span, Some(Span { start: 0, end: 50 }));
+//! }
+//! assert!(Label::parse("<TABLE><TR></TR></TABLE>").is_err());
+//! ```
+//! The labels can be read directly from the dot source with the positions in the source:
+//! ```rust
+//! use graphviz_rust::attributes::html::{parse_labels, Span};
+//!
+//! let dot = "digraph { a [label=<<B>x</B>>] }";
+//! let labels = parse_labels(dot).unwrap();
+//! assert_eq!(labels[0].attribute, "label");
+//! assert_eq!(&dot[labels[0].span.start..labels[0].span.end], "<<B>x</B>>");
+//! assert_eq!(labels[0].source_span(Span { start: 3, end: 4 }), Span { start: 23, end: 24 });
+//! ```
+//!
+//! [`HTML-like labels`]: https://graphviz.org/doc/info/shapes.html#html
+use std::{
+    fmt::{Display, Formatter},
+    str::FromStr,
+};
+
+use dot_structures::{Attribute, Id};
+
+const TABLE_ATTRIBUTES: &[&str] = &[
+    "ALIGN",
+    "BGCOLOR",
+    "BORDER",
+    "CELLBORDER",
+    "CELLPADDING",
+    "CELLSPACING",
+    "COLOR",
+    "COLUMNS",
+    "FIXEDSIZE",
+    "GRADIENTANGLE",
+    "HEIGHT",
+    "HREF",
+    "ID",
+    "PORT",
+    "ROWS",
+    "SIDES",
+    "STYLE",
+    "TARGET",
+    "TITLE",
+    "TOOLTIP",
+    "VALIGN",
+    "WIDTH",
+];
+const CELL_ATTRIBUTES: &[&str] = &[
+    "ALIGN",
+    "BALIGN",
+    "BGCOLOR",
+    "BORDER",
+    "CELLPADDING",
+    "CELLSPACING",
+    "COLOR",
+    "COLSPAN",
+    "FIXEDSIZE",
+    "GRADIENTANGLE",
+    "HEIGHT",
+    "HREF",
+    "ID",
+    "PORT",
+    "ROWSPAN",
+    "SIDES",
+    "STYLE",
+    "TARGET",
+    "TITLE",
+    "TOOLTIP",
+    "VALIGN",
+    "WIDTH",
+];
+const FONT_ATTRIBUTES: &[&str] = &["COLOR", "FACE", "POINT-SIZE"];
+const BR_ATTRIBUTES: &[&str] = &["ALIGN"];
+const IMG_ATTRIBUTES: &[&str] = &["SCALE", "SRC"];
+
+/// The attributes of the element in the order they were set, the names are in the upper case.
+pub type Attributes = Vec<(String, String)>;
+
+/// The byte range of the element in the parsed label.
+///
+/// The range is relative to the label itself, that is the text inside the outer brackets
+/// of the html id, rather than to the dot source since the parsed graph keeps no positions
+/// of the ids. The labels read by [parse_labels] convert it into the range of the source
+/// with [SourceLabel::source_span].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+}
+
+impl From<pest::Span<'_>> for Span {
+    fn from(span: pest::Span<'_>) -> Self {
+        Span {
+            start: span.start(),
+            end: span.end(),
+        }
+    }
+}
+
+fn set(attributes: &mut Attributes, name: &str, value: String) {
+    match attributes.iter_mut().find(|(n, _)| n == name) {
+        Some(attr) => attr.1 = value,
+        None => attributes.push((name.to_string(), value)),
+    }
+}
+
+fn set_legal(
+    attributes: &mut Attributes,
+    tag: &str,
+    legal: &[&str],
+    name: &str,
+    value: String,
+) -> Result<(), String> {
+    let name = name.to_uppercase();
+    if legal.contains(&name.as_str()) {
+        set(attributes, &name, value);
+        Ok(())
+    } else {
+        Err(format!(
+            "the attribute '{}' is not allowed in the element '{}'",
+            name, tag
+        ))
+    }
+}
+
+fn check_legal(attributes: &Attributes, tag: &str, legal: &[&str]) -> Result<(), String> {
+    match attributes
+        .iter()
+        .find(|(n, _)| !legal.contains(&n.as_str()))
+    {
+        Some((n, _)) => Err(format!(
+            "the attribute '{}' is not allowed in the element '{}'",
+            n, tag
+        )),
+        None => Ok(()),
+    }
+}
+
+fn escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn write_open(f: &mut Formatter<'_>, tag: &str, attributes: &Attributes) -> std::fmt::Result {
+    write!(f, "<{}", tag)?;
+    for (name, value) in attributes.iter() {
+        write!(f, " {}=\"{}\"", name, escape(value))?;
+    }
+    f.write_str(">")
+}
+
+fn write_empty(f: &mut Formatter<'_>, tag: &str, attributes: &Attributes) -> std::fmt::Result {
+    write!(f, "<{}", tag)?;
+    for (name, value) in attributes.iter() {
+        write!(f, " {}=\"{}\"", name, escape(value))?;
+    }
+    f.write_str("/>")
+}
+
+macro_rules! setters {
+    ($($method:ident => $name:literal),+) => {
+        $(
+            pub fn $method(mut self, value: impl Display) -> Self {
+                set(&mut self.attributes, $name, value.to_string());
+                self
+            }
+        )+
+    };
+}
+
+/// The HTML-like label.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Label {
+    Text(Text),
+    /// the table wrapped into the font elements from the outermost one
+    Table(Vec<Format>, Table),
+}
+
+impl Label {
+    pub fn text(text: impl Into<Text>) -> Self {
+        Label::Text(text.into())
+    }
+
+    pub fn table(table: Table) -> Self {
+        Label::Table(vec![], table)
+    }
+
+    /// Wraps the table into the font element, only `FONT`, `B`, `I`, `U` and `O` are allowed.
+    pub fn wrapped_table(wrappers: Vec<Format>, table: Table) -> Self {
+        Label::Table(wrappers, table)
+    }
+
+    /// Returns the ports declared in the tables and the cells of the label.
+    pub fn ports(&self) -> Vec<&str> {
+        let mut ports = vec![];
+        self.collect_ports(&mut ports);
+        ports
+    }
+
+    fn collect_ports<'a>(&'a self, ports: &mut Vec<&'a str>) {
+        if let Label::Table(_, table) = self {
+            table.collect_ports(ports);
+        }
+    }
+
+    /// Checks the nesting rules, the attributes and the uniqueness of the ports.
+    pub fn validate(&self) -> Result<(), String> {
+        self.check()?;
+        let mut ports = self.ports();
+        ports.sort();
+        match ports.windows(2).find(|w| w[0] == w[1]) {
+            Some(w) => Err(format!("the port '{}' is declared more than once", w[0])),
+            None => Ok(()),
+        }
+    }
+
+    fn check(&self) -> Result<(), String> {
+        match self {
+            Label::Text(text) => text.check(),
+            Label::Table(wrappers, table) => {
+                for w in wrappers.iter() {
+                    if !w.wraps_table() {
+                        return Err(format!(
+                            "the element '{}' can not contain the table",
+                            w.tag()
+                        ));
+                    }
+                    w.check()?;
+                }
+                table.check()
+            }
+        }
+    }
+
+    /// Parses the label, that is the html string without the outer brackets,
+    /// and checks it like [Label::validate].
+    pub fn parse(value: &str) -> Result<Label, String> {
+        parse_label(value)
+    }
+
+    /// Parses the label of the html id, the spans are counted from the text inside the brackets.
+    pub fn from_id(id: &Id) -> Result<Label, String> {
+        match id {
+            Id::Html(v) => match v.strip_prefix('<').and_then(|v| v.strip_suffix('>')) {
+                Some(v) => parse_label(v),
+                None => Err(format!("the html id '{}' has no brackets", v)),
+            },
+            _ => Err(format!("the id '{}' is not an html string", id)),
+        }
+    }
+
+    /// Validates the label and returns the html id.
+    pub fn to_id(&self) -> Result<Id, String> {
+        self.validate()?;
+        Ok(Id::Html(format!("<{}>", self)))
+    }
+}
+
+impl Display for Label {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Label::Text(text) => write!(f, "{}", text),
+            Label::Table(wrappers, table) => {
+                for w in wrappers.iter() {
+                    w.write_open(f)?;
+                }
+                write!(f, "{}", table)?;
+                for w in wrappers.iter().rev() {
+                    write!(f, "</{}>", w.tag())?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl TryFrom<Label> for Id {
+    type Error = String;
+
+    fn try_from(label: Label) -> Result<Self, Self::Error> {
+        label.to_id()
+    }
+}
+
+impl FromStr for Label {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Label::parse(s)
+    }
+}
+
+impl TryFrom<&Id> for Label {
+    type Error = String;
+
+    fn try_from(id: &Id) -> Result<Self, Self::Error> {
+        Label::from_id(id)
+    }
+}
+
+impl From<Text> for Label {
+    fn from(text: Text) -> Self {
+        Label::Text(text)
+    }
+}
+
+impl From<Table> for Label {
+    fn from(table: Table) -> Self {
+        Label::table(table)
+    }
+}
+
+/// The element formatting the text.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Format {
+    Font(Font),
+    Bold,
+    Italic,
+    Underline,
+    Overline,
+    Strike,
+    Sub,
+    Sup,
+}
+
+impl Format {
+    fn tag(&self) -> &'static str {
+        match self {
+            Format::Font(_) => "FONT",
+            Format::Bold => "B",
+            Format::Italic => "I",
+            Format::Underline => "U",
+            Format::Overline => "O",
+            Format::Strike => "S",
+            Format::Sub => "SUB",
+            Format::Sup => "SUP",
+        }
+    }
+
+    fn wraps_table(&self) -> bool {
+        !matches!(self, Format::Strike | Format::Sub | Format::Sup)
+    }
+
+    fn check(&self) -> Result<(), String> {
+        match self {
+            Format::Font(font) => check_legal(&font.attributes, "FONT", FONT_ATTRIBUTES),
+            _ => Ok(()),
+        }
+    }
+
+    fn write_open(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Format::Font(font) => write_open(f, "FONT", &font.attributes),
+            _ => write!(f, "<{}>", self.tag()),
+        }
+    }
+}
+
+/// The `FONT` element.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Font {
+    pub attributes: Attributes,
+}
+
+impl Font {
+    pub fn new() -> Self {
+        Font::default()
+    }
+
+    /// Sets the attribute checking that it is allowed in the element.
+    pub fn attr(mut self, name: &str, value: impl Display) -> Result<Self, String> {
+        set_legal(
+            &mut self.attributes,
+            "FONT",
+            FONT_ATTRIBUTES,
+            name,
+            value.to_string(),
+        )?;
+        Ok(self)
+    }
+
+    setters!(color => "COLOR", face => "FACE", point_size => "POINT-SIZE");
+}
+
+/// The item of the text.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TextItem {
+    /// the literal string, escaped when printed
+    Str(String),
+    /// the line break with the attributes of `BR`
+    Br(Attributes),
+    Formatted(Format, Text),
+}
+
+/// The formatted text.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Text {
+    pub items: Vec<TextItem>,
+    /// the position in the parsed label, none for the built one
+    pub span: Option<Span>,
+}
+
+impl Text {
+    pub fn new() -> Self {
+        Text::default()
+    }
+
+    pub fn str(mut self, value: &str) -> Self {
+        self.items.push(TextItem::Str(value.to_string()));
+        self
+    }
+
+    pub fn br(mut self) -> Self {
+        self.items.push(TextItem::Br(vec![]));
+        self
+    }
+
+    /// Adds the line break justifying the previous line, `LEFT`, `RIGHT` or `CENTER`.
+    pub fn br_aligned(mut self, align: &str) -> Self {
+        self.items
+            .push(TextItem::Br(vec![("ALIGN".to_string(), align.to_string())]));
+        self
+    }
+
+    pub fn formatted(mut self, format: Format, text: impl Into<Text>) -> Self {
+        self.items.push(TextItem::Formatted(format, text.into()));
+        self
+    }
+
+    pub fn font(self, font: Font, text: impl Into<Text>) -> Self {
+        self.formatted(Format::Font(font), text)
+    }
+
+    pub fn bold(self, text: impl Into<Text>) -> Self {
+        self.formatted(Format::Bold, text)
+    }
+
+    pub fn italic(self, text: impl Into<Text>) -> Self {
+        self.formatted(Format::Italic, text)
+    }
+
+    pub fn underline(self, text: impl Into<Text>) -> Self {
+        self.formatted(Format::Underline, text)
+    }
+
+    pub fn overline(self, text: impl Into<Text>) -> Self {
+        self.formatted(Format::Overline, text)
+    }
+
+    pub fn strike(self, text: impl Into<Text>) -> Self {
+        self.formatted(Format::Strike, text)
+    }
+
+    pub fn subscript(self, text: impl Into<Text>) -> Self {
+        self.formatted(Format::Sub, text)
+    }
+
+    pub fn superscript(self, text: impl Into<Text>) -> Self {
+        self.formatted(Format::Sup, text)
+    }
+
+    fn check(&self) -> Result<(), String> {
+        for item in self.items.iter() {
+            match item {
+                TextItem::Str(_) => {}
+                TextItem::Br(attributes) => check_legal(attributes, "BR", BR_ATTRIBUTES)?,
+                TextItem::Formatted(format, text) => {
+                    format.check()?;
+                    text.check()?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Display for Text {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for item in self.items.iter() {
+            match item {
+                TextItem::Str(s) => f.write_str(&escape(s))?,
+                TextItem::Br(attributes) => write_empty(f, "BR", attributes)?,
+                TextItem::Formatted(format, text) => {
+                    format.write_open(f)?;
+                    write!(f, "{}</{}>", text, format.tag())?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl From<&str> for Text {
+    fn from(value: &str) -> Self {
+        Text::new().str(value)
+    }
+}
+
+/// The item of the table.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TableItem {
+    Row(Row),
+    /// the horizontal rule between the rows, `HR`
+    Rule,
+}
+
+/// The `TABLE` element.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Table {
+    pub attributes: Attributes,
+    pub items: Vec<TableItem>,
+    /// the position in the parsed label, none for the built one
+    pub span: Option<Span>,
+}
+
+impl Table {
+    pub fn new() -> Self {
+        Table::default()
+    }
+
+    /// Sets the attribute checking that it is allowed in the element.
+    pub fn attr(mut self, name: &str, value: impl Display) -> Result<Self, String> {
+        set_legal(
+            &mut self.attributes,
+            "TABLE",
+            TABLE_ATTRIBUTES,
+            name,
+            value.to_string(),
+        )?;
+        Ok(self)
+    }
+
+    setters!(
+        align => "ALIGN", bgcolor => "BGCOLOR", border => "BORDER", cellborder => "CELLBORDER",
+        cellpadding => "CELLPADDING", cellspacing => "CELLSPACING", color => "COLOR",
+        columns => "COLUMNS", fixedsize => "FIXEDSIZE", gradientangle => "GRADIENTANGLE",
+        height => "HEIGHT", href => "HREF", id => "ID", port => "PORT", rows => "ROWS",
+        sides => "SIDES", style => "STYLE", target => "TARGET", title => "TITLE",
+        tooltip => "TOOLTIP", valign => "VALIGN", width => "WIDTH"
+    );
+
+    pub fn row(mut self, row: Row) -> Self {
+        self.items.push(TableItem::Row(row));
+        self
+    }
+
+    /// Adds the horizontal rule between the previous and the next rows.
+    pub fn rule(mut self) -> Self {
+        self.items.push(TableItem::Rule);
+        self
+    }
+
+    fn collect_ports<'a>(&'a self, ports: &mut Vec<&'a str>) {
+        ports.extend(port(&self.attributes));
+        for item in self.items.iter() {
+            if let TableItem::Row(row) = item {
+                for item in row.items.iter() {
+                    if let RowItem::Cell(cell) = item {
+                        cell.collect_ports(ports);
+                    }
+                }
+            }
+        }
+    }
+
+    fn check(&self) -> Result<(), String> {
+        check_legal(&self.attributes, "TABLE", TABLE_ATTRIBUTES)?;
+        check_rules(
+            self.items.iter().map(|i| matches!(i, TableItem::Rule)),
+            "HR",
+            "TABLE",
+        )?;
+        for item in self.items.iter() {
+            if let TableItem::Row(row) = item {
+                row.check()?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn port(attributes: &Attributes) -> Option<&str> {
+    attributes
+        .iter()
+        .find(|(n, _)| n == "PORT")
+        .map(|(_, v)| v.as_str())
+}
+
+/// Checks that the element has items and the rules are only between them.
+fn check_rules(rules: impl Iterator<Item = bool>, rule: &str, tag: &str) -> Result<(), String> {
+    let mut prev_rule = true;
+    let mut empty = true;
+    for is_rule in rules {
+        if is_rule && prev_rule {
+            return Err(format!(
+                "the element '{}' should be placed between the items of the element '{}'",
+                rule, tag
+            ));
+        }
+        prev_rule = is_rule;
+        empty = false;
+    }
+    if empty {
+        Err(format!("the element '{}' should not be empty", tag))
+    } else if prev_rule {
+        Err(format!(
+            "the element '{}' should be placed between the items of the element '{}'",
+            rule, tag
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+impl Display for Table {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write_open(f, "TABLE", &self.attributes)?;
+        for item in self.items.iter() {
+            match item {
+                TableItem::Row(row) => write!(f, "{}", row)?,
+                TableItem::Rule => f.write_str("<HR/>")?,
+            }
+        }
+        f.write_str("</TABLE>")
+    }
+}
+
+/// The item of the row.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RowItem {
+    Cell(Cell),
+    /// the vertical rule between the cells, `VR`
+    Rule,
+}
+
+/// The `TR` element.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Row {
+    pub items: Vec<RowItem>,
+    /// the position in the parsed label, none for the built one
+    pub span: Option<Span>,
+}
+
+impl Row {
+    pub fn new() -> Self {
+        Row::default()
+    }
+
+    pub fn cell(mut self, cell: Cell) -> Self {
+        self.items.push(RowItem::Cell(cell));
+        self
+    }
+
+    /// Adds the vertical rule between the previous and the next cells.
+    pub fn rule(mut self) -> Self {
+        self.items.push(RowItem::Rule);
+        self
+    }
+
+    fn check(&self) -> Result<(), String> {
+        check_rules(
+            self.items.iter().map(|i| matches!(i, RowItem::Rule)),
+            "VR",
+            "TR",
+        )?;
+        for item in self.items.iter() {
+            if let RowItem::Cell(cell) = item {
+                cell.check()?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Display for Row {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str("<TR>")?;
+        for item in self.items.iter() {
+            match item {
+                RowItem::Cell(cell) => write!(f, "{}", cell)?,
+                RowItem::Rule => f.write_str("<VR/>")?,
+            }
+        }
+        f.write_str("</TR>")
+    }
+}
+
+/// The content of the cell.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CellContent {
+    Label(Label),
+    Image(Image),
+}
+
+/// The `TD` element.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cell {
+    pub attributes: Attributes,
+    pub content: CellContent,
+    /// the position in the parsed label, none for the built one
+    pub span: Option<Span>,
+}
+
+impl Cell {
+    /// Creates the cell with the text or the nested table.
+    pub fn new(label: impl Into<Label>) -> Self {
+        Cell {
+            attributes: vec![],
+            content: CellContent::Label(label.into()),
+            span: None,
+        }
+    }
+
+    pub fn text(value: &str) -> Self {
+        Cell::new(Text::from(value))
+    }
+
+    pub fn image(image: Image) -> Self {
+        Cell {
+            attributes: vec![],
+            content: CellContent::Image(image),
+            span: None,
+        }
+    }
+
+    /// Sets the attribute checking that it is allowed in the element.
+    pub fn attr(mut self, name: &str, value: impl Display) -> Result<Self, String> {
+        set_legal(
+            &mut self.attributes,
+            "TD",
+            CELL_ATTRIBUTES,
+            name,
+            value.to_string(),
+        )?;
+        Ok(self)
+    }
+
+    setters!(
+        align => "ALIGN", balign => "BALIGN", bgcolor => "BGCOLOR", border => "BORDER",
+        cellpadding => "CELLPADDING", cellspacing => "CELLSPACING", color => "COLOR",
+        colspan => "COLSPAN", fixedsize => "FIXEDSIZE", gradientangle => "GRADIENTANGLE",
+        height => "HEIGHT", href => "HREF", id => "ID", port => "PORT", rowspan => "ROWSPAN",
+        sides => "SIDES", style => "STYLE", target => "TARGET", title => "TITLE",
+        tooltip => "TOOLTIP", valign => "VALIGN", width => "WIDTH"
+    );
+
+    fn collect_ports<'a>(&'a self, ports: &mut Vec<&'a str>) {
+        ports.extend(port(&self.attributes));
+        if let CellContent::Label(label) = &self.content {
+            label.collect_ports(ports);
+        }
+    }
+
+    fn check(&self) -> Result<(), String> {
+        check_legal(&self.attributes, "TD", CELL_ATTRIBUTES)?;
+        match &self.content {
+            CellContent::Label(label) => label.check(),
+            CellContent::Image(image) => image.check(),
+        }
+    }
+}
+
+impl Display for Cell {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write_open(f, "TD", &self.attributes)?;
+        match &self.content {
+            CellContent::Label(label) => write!(f, "{}", label)?,
+            CellContent::Image(image) => write_empty(f, "IMG", &image.attributes)?,
+        }
+        f.write_str("</TD>")
+    }
+}
+
+/// The `IMG` element.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Image {
+    pub attributes: Attributes,
+    /// the position in the parsed label, none for the built one
+    pub span: Option<Span>,
+}
+
+impl Image {
+    pub fn new(src: &str) -> Self {
+        Image {
+            attributes: vec![("SRC".to_string(), src.to_string())],
+            span: None,
+        }
+    }
+
+    /// Sets the scaling of the image: `FALSE`, `TRUE`, `WIDTH`, `HEIGHT` or `BOTH`.
+    pub fn scale(mut self, value: &str) -> Self {
+        set(&mut self.attributes, "SCALE", value.to_string());
+        self
+    }
+
+    fn check(&self) -> Result<(), String> {
+        check_legal(&self.attributes, "IMG", IMG_ATTRIBUTES)?;
+        if self.attributes.iter().any(|(n, _)| n == "SRC") {
+            Ok(())
+        } else {
+            Err("the element 'IMG' should have the attribute 'SRC'".to_string())
+        }
+    }
+}
+
+/// The html label of the attribute in the dot source.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SourceLabel {
+    /// the name of the attribute, e.g. `label` or `xlabel`
+    pub attribute: String,
+    /// the byte range of the html id including the outer brackets in the dot source
+    pub span: Span,
+    pub label: Label,
+}
+
+impl SourceLabel {
+    /// Converts the span of the element of the label into the byte range in the dot source.
+    pub fn source_span(&self, span: Span) -> Span {
+        let offset = self.span.start + 1;
+        Span {
+            start: offset + span.start,
+            end: offset + span.end,
+        }
+    }
+}
+
+/// Parses the dot source and returns the html labels of the attributes in the order of appearance.
+///
+/// Unlike [Label::from_id] the labels keep the position in the source,
+/// the error of any label is reported with its offset in the source.
+pub fn parse_labels(dot: &str) -> Result<Vec<SourceLabel>, String> {
+    crate::parser::parse_html_attributes(dot)?
+        .into_iter()
+        .map(|(Attribute(key, value), range)| {
+            let label = Label::from_id(&value)
+                .map_err(|e| format!("the label at {} is invalid: {}", range.start, e))?;
+            Ok(SourceLabel {
+                attribute: into_attr::unquote(&key),
+                span: Span {
+                    start: range.start,
+                    end: range.end,
+                },
+                label,
+            })
+        })
+        .collect()
+}
+
+mod grammar {
+    #[derive(Parser)]
+    #[grammar = "grammar/html.pest"]
+    pub(super) struct HtmlParser;
+}
+
+use grammar::{HtmlParser, Rule};
+use pest::{iterators::Pair, Parser};
+
+/// The parsed element before it is checked against the place it appears in.
+struct Element<'a> {
+    tag: String,
+    attributes: Attributes,
+    children: Vec<Pair<'a, Rule>>,
+    span: Span,
+    content: Span,
+}
+
+fn parse_label(value: &str) -> Result<Label, String> {
+    let label = HtmlParser::parse(Rule::label, value)
+        .map_err(|e| e.to_string())?
+        .next()
+        .unwrap();
+    let children: Vec<_> = label
+        .into_inner()
+        .filter(|p| p.as_rule() != Rule::EOI)
+        .collect();
+    let label = process_label(
+        children,
+        Span {
+            start: 0,
+            end: value.len(),
+        },
+    )?;
+    label.validate()?;
+    Ok(label)
+}
+
+fn element(pair: Pair<Rule>) -> Result<Element, String> {
+    let span = Span::from(pair.as_span());
+    let mut attributes = vec![];
+    let mut children = vec![];
+    let mut inner = pair.into_inner();
+    let tag = inner.next().unwrap().as_str().to_uppercase();
+    let mut end = span.end;
+    for p in inner {
+        match p.as_rule() {
+            Rule::attribute => {
+                let mut attr = p.into_inner();
+                let name = attr.next().unwrap().as_str().to_uppercase();
+                let value = attr.next().unwrap().as_str();
+                attributes.push((name, unescape(&value[1..value.len() - 1])));
+            }
+            Rule::tag => {
+                if !p.as_str().eq_ignore_ascii_case(&tag) {
+                    return Err(format!(
+                        "the element '{}' is closed by '{}' at {}",
+                        tag,
+                        p.as_str(),
+                        p.as_span().start()
+                    ));
+                }
+                end = p.as_span().start() - 2;
+            }
+            _ => children.push(p),
+        }
+    }
+    let start = children.first().map(|p| p.as_span().start()).unwrap_or(end);
+    Ok(Element {
+        tag,
+        attributes,
+        children,
+        span,
+        content: Span { start, end },
+    })
+}
+
+fn not_allowed(el: &Element, parent: &str) -> String {
+    format!(
+        "the element '{}' is not allowed in {} at {}",
+        el.tag, parent, el.span.start
+    )
+}
+
+fn legal(el: &Element, legal: &[&str]) -> Result<(), String> {
+    check_legal(&el.attributes, &el.tag, legal).map_err(|e| format!("{} at {}", e, el.span.start))
+}
+
+fn is_blank(pair: &Pair<Rule>) -> bool {
+    pair.as_rule() == Rule::text && pair.as_str().trim().is_empty()
+}
+
+fn process_format(el: &Element) -> Result<Option<Format>, String> {
+    let format = match el.tag.as_str() {
+        "FONT" => {
+            legal(el, FONT_ATTRIBUTES)?;
+            Format::Font(Font {
+                attributes: el.attributes.clone(),
+            })
+        }
+        "B" => Format::Bold,
+        "I" => Format::Italic,
+        "U" => Format::Underline,
+        "O" => Format::Overline,
+        "S" => Format::Strike,
+        "SUB" => Format::Sub,
+        "SUP" => Format::Sup,
+        _ => return Ok(None),
+    };
+    if !matches!(format, Format::Font(_)) {
+        legal(el, &[])?;
+    }
+    Ok(Some(format))
+}
+
+fn process_label(children: Vec<Pair<Rule>>, span: Span) -> Result<Label, String> {
+    let significant: Vec<_> = children.iter().filter(|p| !is_blank(p)).collect();
+    if let [single] = significant.as_slice() {
+        if single.as_rule() == Rule::element {
+            if let Some((wrappers, table)) = process_wrapped_table(element((*single).clone())?)? {
+                return Ok(Label::Table(wrappers, table));
+            }
+        }
+    }
+    process_text(children, span).map(Label::Text)
+}
+
+fn process_wrapped_table(el: Element) -> Result<Option<(Vec<Format>, Table)>, String> {
+    if el.tag == "TABLE" {
+        return process_table(el).map(|t| Some((vec![], t)));
+    }
+    let wrapper = match process_format(&el)? {
+        Some(f) => f,
+        None => return Ok(None),
+    };
+    let significant: Vec<_> = el.children.into_iter().filter(|p| !is_blank(p)).collect();
+    match significant.as_slice() {
+        [single] if single.as_rule() == Rule::element => Ok(process_wrapped_table(element(
+            single.clone(),
+        )?)?
+        .map(|(mut wrappers, table)| {
+            wrappers.insert(0, wrapper);
+            (wrappers, table)
+        })),
+        _ => Ok(None),
+    }
+}
+
+fn process_text(children: Vec<Pair<Rule>>, span: Span) -> Result<Text, String> {
+    let mut items = vec![];
+    for p in children {
+        match p.as_rule() {
+            Rule::text => items.push(TextItem::Str(unescape(p.as_str()))),
+            Rule::empty_element => {
+                let el = element(p)?;
+                if el.tag != "BR" {
+                    return Err(not_allowed(&el, "the text"));
+                }
+                legal(&el, BR_ATTRIBUTES)?;
+                items.push(TextItem::Br(el.attributes));
+            }
+            _ => {
+                let el = element(p)?;
+                let format = process_format(&el)?.ok_or_else(|| not_allowed(&el, "the text"))?;
+                let text = process_text(el.children, el.content)?;
+                items.push(TextItem::Formatted(format, text));
+            }
+        }
+    }
+    Ok(Text {
+        items,
+        span: Some(span),
+    })
+}
+
+fn process_table(el: Element) -> Result<Table, String> {
+    legal(&el, TABLE_ATTRIBUTES)?;
+    let mut items = vec![];
+    for p in el.children.into_iter().filter(|p| !is_blank(p)) {
+        if p.as_rule() == Rule::text {
+            return Err(format!(
+                "the text is not allowed in the element 'TABLE' at {}",
+                p.as_span().start()
+            ));
+        }
+        let child = element(p)?;
+        match (child.tag.as_str(), child.children.is_empty()) {
+            ("TR", _) => items.push(TableItem::Row(process_row(child)?)),
+            ("HR", true) => items.push(TableItem::Rule),
+            _ => return Err(not_allowed(&child, "the element 'TABLE'")),
+        }
+    }
+    Ok(Table {
+        attributes: el.attributes,
+        items,
+        span: Some(el.span),
+    })
+}
+
+fn process_row(el: Element) -> Result<Row, String> {
+    legal(&el, &[])?;
+    let mut items = vec![];
+    for p in el.children.into_iter().filter(|p| !is_blank(p)) {
+        if p.as_rule() == Rule::text {
+            return Err(format!(
+                "the text is not allowed in the element 'TR' at {}",
+                p.as_span().start()
+            ));
+        }
+        let child = element(p)?;
+        match (child.tag.as_str(), child.children.is_empty()) {
+            ("TD", _) => items.push(RowItem::Cell(process_cell(child)?)),
+            ("VR", true) => items.push(RowItem::Rule),
+            _ => return Err(not_allowed(&child, "the element 'TR'")),
+        }
+    }
+    Ok(Row {
+        items,
+        span: Some(el.span),
+    })
+}
+
+fn process_cell(el: Element) -> Result<Cell, String> {
+    legal(&el, CELL_ATTRIBUTES)?;
+    let significant: Vec<_> = el.children.iter().filter(|p| !is_blank(p)).collect();
+    let content = match significant.as_slice() {
+        [single] if single.as_rule() == Rule::empty_element => {
+            let child = element((*single).clone())?;
+            if child.tag == "IMG" {
+                legal(&child, IMG_ATTRIBUTES)?;
+                Some(CellContent::Image(Image {
+                    attributes: child.attributes,
+                    span: Some(child.span),
+                }))
+            } else {
+                None
+            }
+        }
+        _ => None,
+    };
+    let content = match content {
+        Some(c) => c,
+        None => CellContent::Label(process_label(el.children, el.content)?),
+    };
+    Ok(Cell {
+        attributes: el.attributes,
+        content,
+        span: Some(el.span),
+    })
+}
+
+/// Replaces the character references and the named entities of html 4 supported by graphviz,
+/// the unknown entities are left as is.
+fn unescape(value: &str) -> String {
+    let mut result = String::new();
+    let mut rest = value;
+    while let Some(i) = rest.find('&') {
+        result.push_str(&rest[..i]);
+        rest = &rest[i..];
+        match rest
+            .find(';')
+            .and_then(|end| entity(&rest[1..end]).map(|c| (c, end)))
+        {
+            Some((c, end)) => {
+                result.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                result.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    result.push_str(rest);
+    result
+}
+
+fn entity(name: &str) -> Option<char> {
+    match name.strip_prefix('#') {
+        Some(code) => {
+            let code = match code.strip_prefix(['x', 'X']) {
+                Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+                None => code.parse().ok()?,
+            };
+            char::from_u32(code)
+        }
+        None => ENTITIES
+            .binary_search_by(|(n, _)| n.cmp(&name))
+            .ok()
+            .map(|i| ENTITIES[i].1),
+    }
+}
+
+/// The named entities sorted by the name.
+const ENTITIES: &[(&str, char)] = &[
+    ("AElig", '\u{c6}'),
+    ("Aacute", '\u{c1}'),
+    ("Acirc", '\u{c2}'),
+    ("Agrave", '\u{c0}'),
+    ("Alpha", '\u{391}'),
+    ("Aring", '\u{c5}'),
+    ("Atilde", '\u{c3}'),
+    ("Auml", '\u{c4}'),
+    ("Beta", '\u{392}'),
+    ("Ccedil", '\u{c7}'),
+    ("Chi", '\u{3a7}'),
+    ("Dagger", '\u{2021}'),
+    ("Delta", '\u{394}'),
+    ("ETH", '\u{d0}'),
+    ("Eacute", '\u{c9}'),
+    ("Ecirc", '\u{ca}'),
+    ("Egrave", '\u{c8}'),
+    ("Epsilon", '\u{395}'),
+    ("Eta", '\u{397}'),
+    ("Euml", '\u{cb}'),
+    ("Gamma", '\u{393}'),
+    ("Iacute", '\u{cd}'),
+    ("Icirc", '\u{ce}'),
+    ("Igrave", '\u{cc}'),
+    ("Iota", '\u{399}'),
+    ("Iuml", '\u{cf}'),
+    ("Kappa", '\u{39a}'),
+    ("Lambda", '\u{39b}'),
+    ("Mu", '\u{39c}'),
+    ("Ntilde", '\u{d1}'),
+    ("Nu", '\u{39d}'),
+    ("OElig", '\u{152}'),
+    ("Oacute", '\u{d3}'),
+    ("Ocirc", '\u{d4}'),
+    ("Ograve", '\u{d2}'),
+    ("Omega", '\u{3a9}'),
+    ("Omicron", '\u{39f}'),
+    ("Oslash", '\u{d8}'),
+    ("Otilde", '\u{d5}'),
+    ("Ouml", '\u{d6}'),
+    ("Phi", '\u{3a6}'),
+    ("Pi", '\u{3a0}'),
+    ("Prime", '\u{2033}'),
+    ("Psi", '\u{3a8}'),
+    ("Rho", '\u{3a1}'),
+    ("Scaron", '\u{160}'),
+    ("Sigma", '\u{3a3}'),
+    ("THORN", '\u{de}'),
+    ("Tau", '\u{3a4}'),
+    ("Theta", '\u{398}'),
+    ("Uacute", '\u{da}'),
+    ("Ucirc", '\u{db}'),
+    ("Ugrave", '\u{d9}'),
+    ("Upsilon", '\u{3a5}'),
+    ("Uuml", '\u{dc}'),
+    ("Xi", '\u{39e}'),
+    ("Yacute", '\u{dd}'),
+    ("Yuml", '\u{178}'),
+    ("Zeta", '\u{396}'),
+    ("aacute", '\u{e1}'),
+    ("acirc", '\u{e2}'),
+    ("acute", '\u{b4}'),
+    ("aelig", '\u{e6}'),
+    ("agrave", '\u{e0}'),
+    ("alefsym", '\u{2135}'),
+    ("alpha", '\u{3b1}'),
+    ("amp", '\u{26}'),
+    ("and", '\u{2227}'),
+    ("ang", '\u{2220}'),
+    ("apos", '\u{27}'),
+    ("aring", '\u{e5}'),
+    ("asymp", '\u{2248}'),
+    ("atilde", '\u{e3}'),
+    ("auml", '\u{e4}'),
+    ("bdquo", '\u{201e}'),
+    ("beta", '\u{3b2}'),
+    ("brvbar", '\u{a6}'),
+    ("bull", '\u{2022}'),
+    ("cap", '\u{2229}'),
+    ("ccedil", '\u{e7}'),
+    ("cedil", '\u{b8}'),
+    ("cent", '\u{a2}'),
+    ("chi", '\u{3c7}'),
+    ("circ", '\u{2c6}'),
+    ("clubs", '\u{2663}'),
+    ("cong", '\u{2245}'),
+    ("copy", '\u{a9}'),
+    ("crarr", '\u{21b5}'),
+    ("cup", '\u{222a}'),
+    ("curren", '\u{a4}'),
+    ("dArr", '\u{21d3}'),
+    ("dagger", '\u{2020}'),
+    ("darr", '\u{2193}'),
+    ("deg", '\u{b0}'),
+    ("delta", '\u{3b4}'),
+    ("diams", '\u{2666}'),
+    ("divide", '\u{f7}'),
+    ("eacute", '\u{e9}'),
+    ("ecirc", '\u{ea}'),
+    ("egrave", '\u{e8}'),
+    ("empty", '\u{2205}'),
+    ("emsp", '\u{2003}'),
+    ("ensp", '\u{2002}'),
+    ("epsilon", '\u{3b5}'),
+    ("equiv", '\u{2261}'),
+    ("eta", '\u{3b7}'),
+    ("eth", '\u{f0}'),
+    ("euml", '\u{eb}'),
+    ("euro", '\u{20ac}'),
+    ("exist", '\u{2203}'),
+    ("fnof", '\u{192}'),
+    ("forall", '\u{2200}'),
+    ("frac12", '\u{bd}'),
+    ("frac14", '\u{bc}'),
+    ("frac34", '\u{be}'),
+    ("frasl", '\u{2044}'),
+    ("gamma", '\u{3b3}'),
+    ("ge", '\u{2265}'),
+    ("gt", '\u{3e}'),
+    ("hArr", '\u{21d4}'),
+    ("harr", '\u{2194}'),
+    ("hearts", '\u{2665}'),
+    ("hellip", '\u{2026}'),
+    ("iacute", '\u{ed}'),
+    ("icirc", '\u{ee}'),
+    ("iexcl", '\u{a1}'),
+    ("igrave", '\u{ec}'),
+    ("image", '\u{2111}'),
+    ("infin", '\u{221e}'),
+    ("int", '\u{222b}'),
+    ("iota", '\u{3b9}'),
+    ("iquest", '\u{bf}'),
+    ("isin", '\u{2208}'),
+    ("iuml", '\u{ef}'),
+    ("kappa", '\u{3ba}'),
+    ("lArr", '\u{21d0}'),
+    ("lambda", '\u{3bb}'),
+    ("lang", '\u{2329}'),
+    ("laquo", '\u{ab}'),
+    ("larr", '\u{2190}'),
+    ("lceil", '\u{2308}'),
+    ("ldquo", '\u{201c}'),
+    ("le", '\u{2264}'),
+    ("lfloor", '\u{230a}'),
+    ("lowast", '\u{2217}'),
+    ("loz", '\u{25ca}'),
+    ("lrm", '\u{200e}'),
+    ("lsaquo", '\u{2039}'),
+    ("lsquo", '\u{2018}'),
+    ("lt", '\u{3c}'),
+    ("macr", '\u{af}'),
+    ("mdash", '\u{2014}'),
+    ("micro", '\u{b5}'),
+    ("middot", '\u{b7}'),
+    ("minus", '\u{2212}'),
+    ("mu", '\u{3bc}'),
+    ("nabla", '\u{2207}'),
+    ("nbsp", '\u{a0}'),
+    ("ndash", '\u{2013}'),
+    ("ne", '\u{2260}'),
+    ("ni", '\u{220b}'),
+    ("not", '\u{ac}'),
+    ("notin", '\u{2209}'),
+    ("nsub", '\u{2284}'),
+    ("ntilde", '\u{f1}'),
+    ("nu", '\u{3bd}'),
+    ("oacute", '\u{f3}'),
+    ("ocirc", '\u{f4}'),
+    ("oelig", '\u{153}'),
+    ("ograve", '\u{f2}'),
+    ("oline", '\u{203e}'),
+    ("omega", '\u{3c9}'),
+    ("omicron", '\u{3bf}'),
+    ("oplus", '\u{2295}'),
+    ("or", '\u{2228}'),
+    ("ordf", '\u{aa}'),
+    ("ordm", '\u{ba}'),
+    ("oslash", '\u{f8}'),
+    ("otilde", '\u{f5}'),
+    ("otimes", '\u{2297}'),
+    ("ouml", '\u{f6}'),
+    ("para", '\u{b6}'),
+    ("part", '\u{2202}'),
+    ("permil", '\u{2030}'),
+    ("perp", '\u{22a5}'),
+    ("phi", '\u{3c6}'),
+    ("pi", '\u{3c0}'),
+    ("piv", '\u{3d6}'),
+    ("plusmn", '\u{b1}'),
+    ("pound", '\u{a3}'),
+    ("prime", '\u{2032}'),
+    ("prod", '\u{220f}'),
+    ("prop", '\u{221d}'),
+    ("psi", '\u{3c8}'),
+    ("quot", '\u{22}'),
+    ("rArr", '\u{21d2}'),
+    ("radic", '\u{221a}'),
+    ("rang", '\u{232a}'),
+    ("raquo", '\u{bb}'),
+    ("rarr", '\u{2192}'),
+    ("rceil", '\u{2309}'),
+    ("rdquo", '\u{201d}'),
+    ("real", '\u{211c}'),
+    ("reg", '\u{ae}'),
+    ("rfloor", '\u{230b}'),
+    ("rho", '\u{3c1}'),
+    ("rlm", '\u{200f}'),
+    ("rsaquo", '\u{203a}'),
+    ("rsquo", '\u{2019}'),
+    ("sbquo", '\u{201a}'),
+    ("scaron", '\u{161}'),
+    ("sdot", '\u{22c5}'),
+    ("sect", '\u{a7}'),
+    ("shy", '\u{ad}'),
+    ("sigma", '\u{3c3}'),
+    ("sigmaf", '\u{3c2}'),
+    ("sim", '\u{223c}'),
+    ("spades", '\u{2660}'),
+    ("sub", '\u{2282}'),
+    ("sube", '\u{2286}'),
+    ("sum", '\u{2211}'),
+    ("sup", '\u{2283}'),
+    ("sup1", '\u{b9}'),
+    ("sup2", '\u{b2}'),
+    ("sup3", '\u{b3}'),
+    ("supe", '\u{2287}'),
+    ("szlig", '\u{df}'),
+    ("tau", '\u{3c4}'),
+    ("there4", '\u{2234}'),
+    ("theta", '\u{3b8}'),
+    ("thetasym", '\u{3d1}'),
+    ("thinsp", '\u{2009}'),
+    ("thorn", '\u{fe}'),
+    ("tilde", '\u{2dc}'),
+    ("times", '\u{d7}'),
+    ("trade", '\u{2122}'),
+    ("uArr", '\u{21d1}'),
+    ("uacute", '\u{fa}'),
+    ("uarr", '\u{2191}'),
+    ("ucirc", '\u{fb}'),
+    ("ugrave", '\u{f9}'),
+    ("uml", '\u{a8}'),
+    ("upsih", '\u{3d2}'),
+    ("upsilon", '\u{3c5}'),
+    ("uuml", '\u{fc}'),
+    ("weierp", '\u{2118}'),
+    ("xi", '\u{3be}'),
+    ("yacute", '\u{fd}'),
+    ("yen", '\u{a5}'),
+    ("yuml", '\u{ff}'),
+    ("zeta", '\u{3b6}'),
+    ("zwj", '\u{200d}'),
+    ("zwnj", '\u{200c}'),
+];
+
+#[cfg(test)]
+mod tests {
+    use dot_generator::id;
+    use dot_structures::Id;
+
+    use crate::attributes::html::{
+        parse_labels, Cell, CellContent, Font, Format, Image, Label, Row, RowItem, Span, Table,
+        TableItem, Text, TextItem,
+    };
+
+    #[test]
+    fn text_test() {
+        let label = Label::text(Text::new().str("a < b").br_aligned("LEFT").font(
+            Font::new().color("red").point_size(10),
+            Text::new().italic("\"x\"").subscript("2"),
+        ));
+        assert_eq!(
+            label.to_id().unwrap(),
+            id!(html r#"<a &lt; b<BR ALIGN="LEFT"/><FONT COLOR="red" POINT-SIZE="10"><I>&quot;x&quot;</I><SUB>2</SUB></FONT>>"#)
+        );
+        assert!(label.ports().is_empty());
+        assert_eq!(Id::try_from(Label::text("")).unwrap(), id!(html "<>"));
+    }
+
+    #[test]
+    fn table_test() {
+        let inner = Table::new()
+            .port("inner")
+            .row(Row::new().cell(Cell::image(Image::new("a.png").scale("TRUE"))));
+        let label = Label::wrapped_table(
+            vec![Format::Bold],
+            Table::new().attr("cellspacing", 0).unwrap().row(
+                Row::new()
+                    .cell(Cell::text("a").port("a").colspan(2))
+                    .rule()
+                    .cell(Cell::new(inner)),
+            ),
+        );
+        assert_eq!(label.ports(), vec!["a", "inner"]);
+        assert_eq!(
+            label.to_string(),
+            r#"<B><TABLE CELLSPACING="0"><TR><TD PORT="a" COLSPAN="2">a</TD><VR/><TD><TABLE PORT="inner"><TR><TD><IMG SRC="a.png" SCALE="TRUE"/></TD></TR></TABLE></TD></TR></TABLE></B>"#
+        );
+        assert!(label.validate().is_ok());
+    }
+
+    #[test]
+    fn validate_test() {
+        let row = || Row::new().cell(Cell::text("a"));
+        assert!(Table::new().attr("face", "x").is_err());
+        assert!(Cell::text("a").attr("rowspan", 2).is_ok());
+        assert!(Font::new().attr("point-size", 2).is_ok());
+
+        assert_eq!(
+            Label::table(Table::new()).validate().unwrap_err(),
+            "the element 'TABLE' should not be empty"
+        );
+        assert!(Label::table(Table::new().rule().row(row()))
+            .validate()
+            .is_err());
+        assert!(
+            Label::table(Table::new().row(row()).rule().rule().row(row()))
+                .validate()
+                .is_err()
+        );
+        assert!(Label::table(Table::new().row(row().rule()))
+            .validate()
+            .is_err());
+        assert!(Label::table(Table::new().row(Row::new()))
+            .validate()
+            .is_err());
+        assert_eq!(
+            Label::wrapped_table(vec![Format::Sub], Table::new().row(row()))
+                .validate()
+                .unwrap_err(),
+            "the element 'SUB' can not contain the table"
+        );
+        assert_eq!(
+            Label::table(
+                Table::new()
+                    .port("p")
+                    .row(Row::new().cell(Cell::text("a").port("p")))
+            )
+            .to_id()
+            .unwrap_err(),
+            "the port 'p' is declared more than once"
+        );
+        let table = Table {
+            attributes: vec![("FACE".to_string(), "x".to_string())],
+            items: vec![TableItem::Row(Row {
+                items: vec![RowItem::Cell(Cell::text("a"))],
+                span: None,
+            })],
+            span: None,
+        };
+        assert!(Label::table(table).validate().is_err());
+    }
+
+    #[test]
+    fn parse_test() {
+        let label =
+            Label::parse(r#"<b>a &amp;&#65;&#x42; <i>b</i></b><br align='left'/>&copy; &lt;c&gt;"#)
+                .unwrap();
+        assert_eq!(
+            label.to_string(),
+            r#"<B>a &amp;AB <I>b</I></B><BR ALIGN="left"/>© &lt;c&gt;"#
+        );
+        match label {
+            Label::Text(text) => {
+                assert_eq!(text.span, Some(Span { start: 0, end: 68 }));
+                match &text.items[0] {
+                    TextItem::Formatted(Format::Bold, inner) => {
+                        assert_eq!(inner.span, Some(Span { start: 3, end: 30 }))
+                    }
+                    i => panic!("unexpected {:?}", i),
+                }
+            }
+            l => panic!("unexpected {:?}", l),
+        }
+
+        let label = Label::parse(
+            r#"&copy;&eacute;&hellip; &Omega; &unknown;<font face='&laquo;'>x</font>"#,
+        )
+        .unwrap();
+        let printed = label.to_string();
+        assert_eq!(printed, "©é… Ω &amp;unknown;<FONT FACE=\"«\">x</FONT>");
+        match Label::parse(&printed).unwrap() {
+            Label::Text(text) => {
+                assert_eq!(text.items[0], TextItem::Str("©é… Ω &unknown;".to_string()))
+            }
+            l => panic!("unexpected {:?}", l),
+        }
+        assert_eq!(Label::parse(&printed).unwrap().to_string(), printed);
+
+        let label = Label::from_id(&id!(html r#"<
+            <FONT COLOR="red"><I>
+            <TABLE BORDER="0" PORT="t">
+              <!-- the header -->
+              <TR><TD PORT="a">a</TD><VR/><TD><IMG SRC="x.png"/></TD></TR>
+              <HR/>
+              <TR><TD>
+                <TABLE><TR><TD PORT="b"></TD></TR></TABLE>
+              </TD></TR>
+            </TABLE>
+            </I></FONT>
+            >"#))
+        .unwrap();
+        assert_eq!(label.ports(), vec!["t", "a", "b"]);
+        match &label {
+            Label::Table(wrappers, table) => {
+                assert_eq!(wrappers.len(), 2);
+                assert_eq!(table.items.len(), 3);
+                match &table.items[0] {
+                    TableItem::Row(row) => {
+                        assert!(matches!(
+                            &row.items[2],
+                            RowItem::Cell(Cell {
+                                content: CellContent::Image(_),
+                                ..
+                            })
+                        ))
+                    }
+                    i => panic!("unexpected {:?}", i),
+                }
+            }
+            l => panic!("unexpected {:?}", l),
+        }
+        assert_eq!(
+            Label::parse(&label.to_string()).unwrap().to_string(),
+            label.to_string()
+        );
+
+        let built = Label::table(
+            Table::new()
+                .border(0)
+                .row(Row::new().cell(Cell::new(Text::new().italic("\"x\" & y")).port("p"))),
+        );
+        assert_eq!(
+            Label::parse(&built.to_string()).unwrap().to_string(),
+            built.to_string()
+        );
+    }
+
+    #[test]
+    fn parse_error_test() {
+        assert!(Label::parse("<b>x</i>").is_err());
+        assert!(Label::parse("<b>x").is_err());
+        assert!(Label::parse("a<TABLE><TR><TD>x</TD></TR></TABLE>").is_err());
+        assert!(Label::parse("<TABLE>x<TR><TD>x</TD></TR></TABLE>").is_err());
+        assert!(Label::parse("<TABLE><TD>x</TD></TABLE>").is_err());
+        assert!(Label::parse("<TABLE><TR><TD>x</TD></TR><HR/></TABLE>").is_err());
+        assert!(Label::parse("<TABLE><TR><TD FACE=\"x\">x</TD></TR></TABLE>").is_err());
+        assert!(Label::parse("<SUB><TABLE><TR><TD>x</TD></TR></TABLE></SUB>").is_err());
+        assert!(Label::parse("<IMG SRC=\"x\"/>").is_err());
+        assert_eq!(
+            Label::parse("a<TR><TD>x</TD></TR>").unwrap_err(),
+            "the element 'TR' is not allowed in the text at 1"
+        );
+        assert!(Label::from_id(&id!("a")).is_err());
+    }
+
+    #[test]
+    fn parse_labels_test() {
+        let dot = r#"digraph {
+            node [label=<<I>n</I>>]
+            <x> [label="x"]
+            a -> b ["xlabel"=<<TABLE><TR><TD PORT="p">e</TD></TR></TABLE>>]
+        }"#;
+        let labels = parse_labels(dot).unwrap();
+        assert_eq!(labels.len(), 2);
+        assert_eq!(labels[1].attribute, "xlabel");
+        let source = |span: Span| &dot[span.start..span.end];
+        assert_eq!(source(labels[0].span), "<<I>n</I>>");
+        let table = match &labels[1].label {
+            Label::Table(_, table) => labels[1].source_span(table.span.unwrap()),
+            _ => panic!("the table is expected"),
+        };
+        assert_eq!(
+            source(table),
+            r#"<TABLE><TR><TD PORT="p">e</TD></TR></TABLE>"#
+        );
+        assert_eq!(labels[1].label.ports(), vec!["p"]);
+
+        let err = parse_labels("digraph { a [label=<<B>x</I>>] }").unwrap_err();
+        assert!(err.starts_with("the label at 19 is invalid"));
+        assert!(parse_labels("digraph { a [label=<x>").is_err());
+    }
+}
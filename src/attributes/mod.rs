@@ -17,15 +17,49 @@
 //!     assert_eq!(color::default().into_attr(), attr!("color", "black"));
 //! }
 //! ```
+//! The parsed attributes are turned back into the typed ones with [FromAttribute](into_attr::FromAttribute),
+//! the [TypedAttribute] covers all the known attributes and keeps the unknown ones as is.
+//! ```rust
+//! use dot_generator::*;
+//! use dot_structures::*;
+//! use graphviz_rust::attributes::{shape, TypedAttribute};
+//! use into_attr::FromAttribute;
+//!
+//! assert_eq!(shape::from_attr(&attr!("shape", "box")), Ok(shape::box_));
+//! assert_eq!(
+//!     TypedAttribute::from_attr(&attr!("shape", "egg")),
+//!     Ok(TypedAttribute::shape(shape::egg))
+//! );
+//! assert!(TypedAttribute::from_attr(&attr!("fontcolor", "#zz0000")).is_err());
+//! ```
+mod arrows;
+mod attrs;
+pub mod catalog;
+mod colors;
 mod generate;
+pub mod html;
+pub mod record;
+mod styles;
+mod values;
 use std::fmt::{Display, Formatter};
 
+pub use arrows::{Arrow, ArrowShape, ArrowSide, ArrowType};
+pub use attrs::{AttributeOf, Attrs, EdgeAttrs, GraphAttrs, NodeAttrs, SubgraphAttrs};
+pub use colors::{Color, ColorList, WeightedColor};
+pub use styles::{ClusterStyle, EdgeStyle, GraphStyle, NodeStyle, Style, StyleItem, StyleTarget};
+pub use values::{
+    AddPoint, Compass, EdgeSpline, FixedSize, ImageScale, Layer, LayerList, LayerRange, Normalize,
+    Orientation, Overlap, Pack, Point, PointList, PointOrDouble, PortPos, Pos, RankSep, Ratio,
+    Rect, RectList, SplineType, Splines, ViewPort, ViewPortCenter,
+};
+
 use dot_generator::{attr, id};
 use dot_structures::*;
-use into_attr::IntoAttribute;
-use into_attr_derive::IntoAttribute;
+pub(crate) use into_attr::value_id;
+use into_attr::{FromAttribute, IntoAttribute};
+use into_attr_derive::{FromAttribute, IntoAttribute};
 
-use crate::{as_item, generate_attr};
+use crate::{as_item, generate_attr, typed_attributes};
 
 /// The attributes appearing on the node
 pub enum NodeAttributes {}
@@ -38,24 +72,14 @@ pub enum SubgraphAttributes {}
 
 generate_attr!(struct _background for GraphAttributes; String; "<none>".to_string() );
 generate_attr!(struct area for NodeAttributes, SubgraphAttributes; f32; 1.0);
-generate_attr!(enum arrowhead for EdgeAttributes;
-    //region values
-    normal,dot,odot,none,empty,diamond,ediamond,odiamond,box_,open,vee,halfopen,obox,
-    crow,invempty,tee,invodot,invdot,inv;
-    normal
-    //endregion
-);
-generate_attr!(enum arrowtail for EdgeAttributes;
-    //region values
-    normal,dot,odot,none,empty,diamond,ediamond,odiamond,box_,open,vee,halfopen,obox,
-    crow,invempty,tee,invodot,invdot,inv;
-    normal
-    //endregion
-);
-generate_attr!(struct arrowsize for EdgeAttributes; f32; 0.0);
-generate_attr!(struct bb for GraphAttributes; String);
+generate_attr!(struct arrowhead for EdgeAttributes; into ArrowType; ArrowType::from(ArrowShape::Normal));
+generate_attr!(struct arrowtail for EdgeAttributes; into ArrowType; ArrowType::from(ArrowShape::Normal));
+generate_attr!(struct arrowsize for EdgeAttributes; f32; 1.0);
+generate_attr!(struct bb for GraphAttributes; into Rect);
+generate_attr!(struct beautify for GraphAttributes; bool;false);
 generate_attr!(struct center for GraphAttributes; bool;false);
 generate_attr!(struct charset for GraphAttributes; String;"UTF-8".to_string());
+generate_attr!(struct cluster for SubgraphAttributes; bool;false);
 generate_attr!(struct class for GraphAttributes,NodeAttributes,EdgeAttributes,SubgraphAttributes;String;"".to_string());
 generate_attr!(struct colorscheme for GraphAttributes,NodeAttributes,EdgeAttributes,SubgraphAttributes;String;"".to_string());
 generate_attr!(struct comment for GraphAttributes,NodeAttributes,EdgeAttributes;String;"".to_string());
@@ -72,13 +96,13 @@ generate_attr!(enum dir for EdgeAttributes; forward,back,both,none; forward);
 generate_attr!(struct diredgeconstraints for GraphAttributes; bool; false);
 generate_attr!(struct distortion for NodeAttributes; f32; 0.0);
 generate_attr!(struct dpi for GraphAttributes; f32; 96.0);
-generate_attr!(struct dedgehrefpi for EdgeAttributes; String);
+generate_attr!(struct edgehref for EdgeAttributes; String);
 generate_attr!(struct edgetarget for EdgeAttributes; String );
 generate_attr!(struct edgetooltip for EdgeAttributes; String );
 generate_attr!(struct edgeURL for EdgeAttributes; String );
 generate_attr!(struct epsilon for GraphAttributes; f32);
-generate_attr!(struct esep for GraphAttributes; f32;3.0);
-generate_attr!(struct fixedsize for NodeAttributes; bool;false);
+generate_attr!(struct esep for GraphAttributes; into AddPoint;AddPoint::add(3.));
+generate_attr!(struct fixedsize for NodeAttributes; into FixedSize;FixedSize::False);
 generate_attr!(struct fontname for NodeAttributes,GraphAttributes,EdgeAttributes,SubgraphAttributes; String;"Times-Roman".to_string());
 generate_attr!(struct href for NodeAttributes,GraphAttributes,EdgeAttributes,SubgraphAttributes; String;"".to_string());
 generate_attr!(struct id for NodeAttributes,GraphAttributes,EdgeAttributes,SubgraphAttributes; String;"".to_string());
@@ -88,8 +112,9 @@ generate_attr!(struct fontpath for GraphAttributes; String;"<system-dependent>".
 generate_attr!(struct forcelabels for GraphAttributes; bool;true);
 generate_attr!(struct gradientangle for NodeAttributes,GraphAttributes,SubgraphAttributes; i32);
 generate_attr!(struct group for NodeAttributes; String);
-generate_attr!(struct head_lp for EdgeAttributes; String);
+generate_attr!(struct head_lp for EdgeAttributes; into Point);
 generate_attr!(struct headclip for EdgeAttributes; bool;true);
+generate_attr!(struct headport for EdgeAttributes; into PortPos;PortPos::from(Compass::Center));
 generate_attr!(struct headhref for EdgeAttributes; String;"".to_string());
 generate_attr!(struct headlabel for EdgeAttributes; String;"".to_string());
 generate_attr!(struct headtarget for EdgeAttributes; String;"".to_string());
@@ -98,7 +123,7 @@ generate_attr!(struct headURL for EdgeAttributes; String;"".to_string());
 generate_attr!(struct height for NodeAttributes; f32;0.5);
 generate_attr!(struct image for NodeAttributes; String;"".to_string());
 generate_attr!(struct imagepos for NodeAttributes; String;"mc".to_string());
-generate_attr!(struct imagescale for NodeAttributes; bool;false);
+generate_attr!(struct imagescale for NodeAttributes; into ImageScale;ImageScale::False);
 generate_attr!(struct imagepath for GraphAttributes; String;"".to_string());
 generate_attr!(struct inputscale for GraphAttributes; f32);
 generate_attr!(struct K for GraphAttributes,SubgraphAttributes; f32;0.3);
@@ -116,20 +141,21 @@ generate_attr!(struct labeltarget for EdgeAttributes; String);
 generate_attr!(struct labeltooltip for EdgeAttributes; String);
 generate_attr!(struct labelURL for EdgeAttributes; String);
 generate_attr!(struct landscape for GraphAttributes; bool;false);
-generate_attr!(struct layer for EdgeAttributes,NodeAttributes,SubgraphAttributes; String);
+generate_attr!(struct layer for EdgeAttributes,NodeAttributes,SubgraphAttributes; into LayerRange);
 generate_attr!(struct layerlistsep for GraphAttributes; String;",".to_string());
-generate_attr!(struct layers for GraphAttributes; String);
-generate_attr!(struct layerselect for GraphAttributes; String);
+generate_attr!(struct layers for GraphAttributes; into LayerList);
+generate_attr!(struct layerselect for GraphAttributes; into LayerRange);
 generate_attr!(struct layersep for GraphAttributes; String;":\t ".to_string());
 generate_attr!(struct layout for GraphAttributes; String);
 generate_attr!(struct len for EdgeAttributes; f32);
 generate_attr!(struct levels for GraphAttributes; i32);
 generate_attr!(struct levelsgap for GraphAttributes; f32;0.0);
+generate_attr!(struct linelength for GraphAttributes; i32;128);
 generate_attr!(struct lhead for EdgeAttributes; String);
 generate_attr!(struct lheight for GraphAttributes,SubgraphAttributes; f32);
 generate_attr!(struct lwidth for GraphAttributes,SubgraphAttributes; f32);
-generate_attr!(struct lp for GraphAttributes,SubgraphAttributes,EdgeAttributes; String);
-generate_attr!(struct margin for GraphAttributes,SubgraphAttributes,NodeAttributes; f32);
+generate_attr!(struct lp for GraphAttributes,SubgraphAttributes,EdgeAttributes; into Point);
+generate_attr!(struct margin for GraphAttributes,SubgraphAttributes,NodeAttributes; into PointOrDouble);
 generate_attr!(struct ltail for EdgeAttributes; String);
 generate_attr!(struct maxiter for GraphAttributes; i32);
 generate_attr!(struct mclimit for GraphAttributes; f32;1.);
@@ -140,33 +166,34 @@ generate_attr!(struct model for GraphAttributes; String;"shortpath".to_string())
 generate_attr!(struct mosek for GraphAttributes; bool;false);
 generate_attr!(struct newrank for GraphAttributes; bool;false);
 generate_attr!(struct nodesep for GraphAttributes; f32;0.25);
-generate_attr!(struct normalize for GraphAttributes; bool;false);
+generate_attr!(struct normalize for GraphAttributes; into Normalize;Normalize::Enabled(false));
 generate_attr!(struct notranslate for GraphAttributes; bool;false);
 generate_attr!(struct nslimit for GraphAttributes; f32);
 generate_attr!(struct nslimit1 for GraphAttributes; f32);
+generate_attr!(struct oneblock for GraphAttributes; bool;false);
 generate_attr!(struct ordering for GraphAttributes,NodeAttributes; String);
-generate_attr!(struct orientation for GraphAttributes,NodeAttributes; f32;0.);
+generate_attr!(struct orientation for GraphAttributes,NodeAttributes; into Orientation;Orientation::Angle(0.));
 generate_attr!(enum outputorder for GraphAttributes; breadthfirst,nodesfirst,edgesfirst;breadthfirst);
 generate_attr!(struct nojustify for GraphAttributes,NodeAttributes,SubgraphAttributes,EdgeAttributes; bool;false);
-generate_attr!(struct overlap for GraphAttributes; bool;true);
+generate_attr!(struct overlap for GraphAttributes; into Overlap;Overlap::True);
 generate_attr!(struct overlap_shrink for GraphAttributes; bool;true);
-generate_attr!(struct pack for GraphAttributes; bool;false);
+generate_attr!(struct pack for GraphAttributes; into Pack;Pack::Enabled(false));
 generate_attr!(struct overlap_scaling for GraphAttributes; f32;-4.);
-generate_attr!(struct pad for GraphAttributes; f32;0.0555);
-generate_attr!(struct page for GraphAttributes; f32);
+generate_attr!(struct pad for GraphAttributes; into PointOrDouble;PointOrDouble::Double(0.0555));
+generate_attr!(struct page for GraphAttributes; into PointOrDouble);
 generate_attr!(enum packmode for GraphAttributes; node,clust,graph,array;node);
 generate_attr!(enum pagedir for GraphAttributes; BL,BR,TL,TR,RB,RT,LB,LT;BL);
 generate_attr!(struct penwidth for SubgraphAttributes,NodeAttributes,EdgeAttributes; f32;1.);
 generate_attr!(struct peripheries for SubgraphAttributes,NodeAttributes; i32);
 generate_attr!(struct pin for NodeAttributes; bool;false);
-generate_attr!(struct pos for NodeAttributes,EdgeAttributes; String);
+generate_attr!(struct pos for NodeAttributes,EdgeAttributes; into Pos);
 generate_attr!(enum quadtree for GraphAttributes; normal,fast,none;normal);
 generate_attr!(struct quantum for GraphAttributes; f32;0.);
 generate_attr!(enum rank for SubgraphAttributes; same,min,source,max,sink);
 generate_attr!(enum rankdir for GraphAttributes; TB,BT,LR,RL;TB);
-generate_attr!(struct ranksep for GraphAttributes; f32;0.5);
-generate_attr!(struct ratio for GraphAttributes; f32);
-generate_attr!(struct rects for NodeAttributes; String);
+generate_attr!(struct ranksep for GraphAttributes; into RankSep;RankSep::from(0.5));
+generate_attr!(struct ratio for GraphAttributes; into Ratio);
+generate_attr!(struct rects for NodeAttributes; into RectList);
 generate_attr!(struct regular for NodeAttributes; bool;false);
 generate_attr!(struct remincross for GraphAttributes; bool;true);
 generate_attr!(struct repulsiveforce for GraphAttributes; f32;1.);
@@ -178,8 +205,8 @@ generate_attr!(struct samehead for EdgeAttributes; String);
 generate_attr!(struct sametail for EdgeAttributes; String);
 generate_attr!(struct samplepoints for NodeAttributes; i32;8);
 generate_attr!(struct searchsize for GraphAttributes; i32;30);
-generate_attr!(struct scale for GraphAttributes; f32);
-generate_attr!(struct sep for GraphAttributes; f32;4.);
+generate_attr!(struct scale for GraphAttributes; into PointOrDouble);
+generate_attr!(struct sep for GraphAttributes; into AddPoint;AddPoint::add(4.));
 generate_attr!(struct shapefile for NodeAttributes; String);
 generate_attr!(enum shape for NodeAttributes;
     //region values
@@ -188,7 +215,7 @@ generate_attr!(enum shape for NodeAttributes;
     invtriangle,invtrapezium,invhouse,Mdiamond,Msquare,Mcircle,rect,rectangle,square,star,none,
     underline,cylinder,note,tab,folder,box3d,component,promoter,cds,terminator,utr,primersite,
     restrictionsite,fivepoverhang,threepoverhang,noverhang,assembly,signature,insulator,ribosite,
-    rnastab,proteasesite,proteinstab,rpromoter,rarrow,larrow,lpromoter;
+    rnastab,proteasesite,proteinstab,rpromoter,rarrow,larrow,lpromoter,record,Mrecord;
     ellipse
     //endregion
 );
@@ -196,13 +223,55 @@ generate_attr!(struct showboxes for NodeAttributes,GraphAttributes,EdgeAttribute
 generate_attr!(struct sides for NodeAttributes; i32;4);
 generate_attr!(struct skew for NodeAttributes; f32;0.);
 generate_attr!(enum smoothing for GraphAttributes; none,avg_dist,graph_dist,power_dist,rng,spring,triangle;none);
-generate_attr!(struct size for GraphAttributes; f32);
+generate_attr!(struct size for GraphAttributes; into PointOrDouble);
 generate_attr!(struct sortv for GraphAttributes,SubgraphAttributes,NodeAttributes; i32;0);
-generate_attr!(struct splines for GraphAttributes; bool);
+generate_attr!(struct splines for GraphAttributes; into Splines);
 generate_attr!(struct start for GraphAttributes; String);
-generate_attr!(struct style for GraphAttributes,EdgeAttributes,NodeAttributes,SubgraphAttributes; String);
+/// The style of any element as it is parsed, see [TypedAttribute].
+/// The attribute sets accept only the element specific styles: [NodeStyle], [EdgeStyle],
+/// [ClusterStyle] and [GraphStyle].
+#[derive(Debug, PartialEq)]
+pub struct style(pub Style);
+impl IntoAttribute for style {
+    fn into_attr(self) -> Attribute {
+        Attribute(id!("style"), value_id(self.0.to_string()))
+    }
+}
+impl FromAttribute for style {
+    fn from_attr(attr: &Attribute) -> Result<Self, String> {
+        let value = into_attr::attr_value(attr, "style")?;
+        into_attr::parse_value::<Style>(&value, "style").map(style)
+    }
+}
+impl TryFrom<&Attribute> for style {
+    type Error = String;
+
+    fn try_from(attr: &Attribute) -> Result<Self, Self::Error> {
+        style::from_attr(attr)
+    }
+}
+impl GraphAttributes {
+    pub fn style(elem: GraphStyle) -> Attribute {
+        elem.into_attr()
+    }
+}
+impl SubgraphAttributes {
+    pub fn style(elem: ClusterStyle) -> Attribute {
+        elem.into_attr()
+    }
+}
+impl NodeAttributes {
+    pub fn style(elem: NodeStyle) -> Attribute {
+        elem.into_attr()
+    }
+}
+impl EdgeAttributes {
+    pub fn style(elem: EdgeStyle) -> Attribute {
+        elem.into_attr()
+    }
+}
 generate_attr!(struct stylesheet for GraphAttributes; String);
-generate_attr!(struct tail_lp for EdgeAttributes; String);
+generate_attr!(struct tail_lp for EdgeAttributes; into Point);
 generate_attr!(struct tailhref for EdgeAttributes; String);
 generate_attr!(struct taillabel for EdgeAttributes; String);
 generate_attr!(struct tailtarget for EdgeAttributes; String);
@@ -212,22 +281,24 @@ generate_attr!(struct target for EdgeAttributes,GraphAttributes,NodeAttributes,S
 generate_attr!(struct tooltip for EdgeAttributes,GraphAttributes,NodeAttributes,SubgraphAttributes; String);
 generate_attr!(struct URL for EdgeAttributes,GraphAttributes,NodeAttributes,SubgraphAttributes; String);
 generate_attr!(struct tailclip for EdgeAttributes; bool;true);
+generate_attr!(struct tailport for EdgeAttributes; into PortPos;PortPos::from(Compass::Center));
+generate_attr!(enum TBbalance for GraphAttributes; min,max);
 generate_attr!(struct truecolor for GraphAttributes; bool);
-generate_attr!(struct vertices for NodeAttributes; String);
-generate_attr!(struct viewport for GraphAttributes; String);
+generate_attr!(struct vertices for NodeAttributes; into PointList);
+generate_attr!(struct viewport for GraphAttributes; into ViewPort);
 generate_attr!(struct voro_margin for GraphAttributes; f32;0.05);
-generate_attr!(struct weight for EdgeAttributes; i32;1);
+generate_attr!(struct weight for EdgeAttributes; f32;1.);
 generate_attr!(struct width for NodeAttributes; f32;0.75);
 generate_attr!(struct xdotversion for GraphAttributes; String);
 generate_attr!(struct xlabel for EdgeAttributes,NodeAttributes; String);
-generate_attr!(struct xlp for EdgeAttributes,NodeAttributes; String);
+generate_attr!(struct xlp for EdgeAttributes,NodeAttributes; into Point);
 generate_attr!(struct z for NodeAttributes; f32;0.);
-generate_attr!(struct bgcolor for GraphAttributes,SubgraphAttributes; color_name);
-generate_attr!(struct color for EdgeAttributes,SubgraphAttributes,NodeAttributes; color_name;color_name::black);
-generate_attr!(struct fillcolor for EdgeAttributes,SubgraphAttributes,NodeAttributes; color_name);
-generate_attr!(struct fontcolor for GraphAttributes,EdgeAttributes,SubgraphAttributes,NodeAttributes; color_name);
-generate_attr!(struct labelfontcolor for EdgeAttributes; color_name;color_name::black);
-generate_attr!(struct pencolor for SubgraphAttributes; color_name;color_name::black);
+generate_attr!(struct bgcolor for GraphAttributes,SubgraphAttributes; into ColorList);
+generate_attr!(struct color for EdgeAttributes,SubgraphAttributes,NodeAttributes; into ColorList;ColorList::from(color_name::black));
+generate_attr!(struct fillcolor for EdgeAttributes,SubgraphAttributes,NodeAttributes; into ColorList);
+generate_attr!(struct fontcolor for GraphAttributes,EdgeAttributes,SubgraphAttributes,NodeAttributes; into Color);
+generate_attr!(struct labelfontcolor for EdgeAttributes; into Color;Color::from(color_name::black));
+generate_attr!(struct pencolor for SubgraphAttributes; into Color;Color::from(color_name::black));
 
 // support of the x11 color scheme
 generate_attr!(enum color_name;
@@ -305,6 +376,29 @@ generate_attr!(enum color_name;
     //endregion
 );
 
+typed_attributes! {
+    //region attributes
+    _background,area,arrowhead,arrowtail,arrowsize,bb,beautify,center,charset,cluster,class,
+    colorscheme,comment,compound,concentrate,Damping,decorate,defaultdist,constraint,dim,dimen,
+    clusterrank,dir,diredgeconstraints,distortion,dpi,edgehref,edgetarget,edgetooltip,edgeURL,
+    epsilon,esep,fixedsize,fontname,href,id,fontsize,fontnames,fontpath,forcelabels,
+    gradientangle,group,head_lp,headclip,headport,headhref,headlabel,headtarget,headtooltip,
+    headURL,height,image,imagepos,imagescale,imagepath,inputscale,K,label,label_scheme,
+    labelangle,labeldistance,labelfloat,labelfontname,labelfontsize,labelhref,labeljust,
+    labelloc,labeltarget,labeltooltip,labelURL,landscape,layer,layerlistsep,layers,layerselect,
+    layersep,layout,len,levels,levelsgap,linelength,lhead,lheight,lwidth,lp,margin,ltail,
+    maxiter,mclimit,mindist,minlen,mode,model,mosek,newrank,nodesep,normalize,notranslate,
+    nslimit,nslimit1,oneblock,ordering,orientation,outputorder,nojustify,overlap,overlap_shrink,
+    pack,overlap_scaling,pad,page,packmode,pagedir,penwidth,peripheries,pin,pos,quadtree,
+    quantum,rank,rankdir,ranksep,ratio,rects,regular,remincross,repulsiveforce,resolution,root,
+    rotate,rotation,samehead,sametail,samplepoints,searchsize,scale,sep,shapefile,shape,
+    showboxes,sides,skew,smoothing,size,sortv,splines,start,style,stylesheet,tail_lp,tailhref,
+    taillabel,tailtarget,tailtooltip,tailURL,target,tooltip,URL,tailclip,tailport,TBbalance,
+    truecolor,vertices,viewport,voro_margin,weight,width,xdotversion,xlabel,xlp,z,bgcolor,color,
+    fillcolor,fontcolor,labelfontcolor,pencolor
+    //endregion
+}
+
 impl NodeAttributes {
     pub fn margin_separate(horizontal: f32, vertical: f32) -> Attribute {
         Attribute(
@@ -316,7 +410,7 @@ impl NodeAttributes {
 
 #[cfg(test)]
 pub mod tests {
-    use dot_generator::attr;
+    use dot_generator::{attr, id};
     use into_attr::IntoAttribute;
 
     use crate::attributes::*;
@@ -333,4 +427,191 @@ pub mod tests {
         assert_eq!(NodeAttributes::margin(1.0), attr!("margin", "1"));
         assert_eq!(NodeAttributes::margin_separate(1.0, 2.0), attr!("margin", "1,2"));
     }
+
+    #[test]
+    fn color_test() {
+        assert_eq!(
+            NodeAttributes::fillcolor(Color::Rgb(255, 136, 0)),
+            attr!("fillcolor", esc "#ff8800")
+        );
+        assert_eq!(
+            NodeAttributes::color(ColorList::parse("red;0.3:blue").unwrap()),
+            attr!("color", esc "red;0.3:blue")
+        );
+        assert_eq!(
+            EdgeAttributes::fontcolor(Color::Hsv(0.5, 0.7, 0.9)),
+            attr!("fontcolor", esc "0.5 0.7 0.9")
+        );
+        assert_eq!(value_id("1.5".to_string()), id!("1.5"));
+    }
+
+    #[test]
+    fn values_test() {
+        assert_eq!(
+            GraphAttributes::splines(Splines::Ortho),
+            attr!("splines", "ortho")
+        );
+        assert_eq!(GraphAttributes::splines(true), attr!("splines", "spline"));
+        assert_eq!(GraphAttributes::ratio(Ratio::Fill), attr!("ratio", "fill"));
+        assert_eq!(
+            GraphAttributes::size(Point::new(7.5, 10.).fixed()),
+            attr!("size", esc "7.5,10!")
+        );
+        assert_eq!(
+            GraphAttributes::overlap(Overlap::Prism(None)),
+            attr!("overlap", "prism")
+        );
+        assert_eq!(GraphAttributes::pad(0.5), attr!("pad", "0.5"));
+        assert_eq!(sep::default().into_attr(), attr!("sep", esc "+4"));
+        assert_eq!(
+            NodeAttributes::pos(Point::new(1., 2.).fixed()),
+            attr!("pos", esc "1,2!")
+        );
+        assert_eq!(
+            GraphAttributes::layers(LayerList::parse("a:b").unwrap()),
+            attr!("layers", esc "a:b")
+        );
+        assert_eq!(value_id("a\"b".to_string()), id!(esc "a\\\"b"));
+
+        assert_eq!(
+            EdgeAttributes::headport(PortPos::port("f0").with_compass(Compass::N)),
+            attr!("headport", esc "f0:n")
+        );
+        assert_eq!(
+            EdgeAttributes::tailport(Compass::SW),
+            attr!("tailport", "sw")
+        );
+        assert_eq!(
+            GraphAttributes::TBbalance(TBbalance::min),
+            attr!("TBbalance", "min")
+        );
+        assert_eq!(GraphAttributes::linelength(60), attr!("linelength", 60));
+        assert_eq!(SubgraphAttributes::cluster(true), attr!("cluster", true));
+        assert_eq!(arrowsize::default().into_attr(), attr!("arrowsize", 1.0));
+        assert_eq!(EdgeAttributes::weight(0.5), attr!("weight", 0.5));
+        assert_eq!(
+            GraphAttributes::orientation(Orientation::Landscape),
+            attr!("orientation", "landscape")
+        );
+        assert_eq!(NodeAttributes::orientation(45.), attr!("orientation", 45));
+        assert_eq!(
+            orientation::from_attr(&attr!("orientation", "landscape")),
+            Ok(orientation(Orientation::Landscape))
+        );
+        let fields = RectList(vec![
+            Rect::new(0., 0., 10., 20.),
+            Rect::new(10., 0., 20., 20.),
+        ]);
+        assert_eq!(
+            rects::from_attr(&attr!("rects", esc "0,0,10,20 10,0,20,20")),
+            Ok(rects(fields))
+        );
+    }
+
+    #[test]
+    fn style_test() {
+        assert_eq!(
+            NodeAttributes::style(NodeStyle::parse("filled,rounded").unwrap()),
+            attr!("style", esc "filled,rounded")
+        );
+        assert_eq!(
+            EdgeAttributes::style(EdgeStyle::new(StyleItem::Dashed).unwrap()),
+            attr!("style", "dashed")
+        );
+    }
+
+    #[test]
+    fn arrow_test() {
+        assert_eq!(
+            EdgeAttributes::arrowhead(ArrowShape::Dot),
+            attr!("arrowhead", "dot")
+        );
+        assert_eq!(
+            EdgeAttributes::arrowtail(ArrowType::parse("lteeoldiamond").unwrap()),
+            attr!("arrowtail", "lteeoldiamond")
+        );
+        assert_eq!(
+            arrowhead::default().into_attr(),
+            attr!("arrowhead", "normal")
+        );
+    }
+
+    #[test]
+    fn from_attr_test() {
+        let g = crate::parse(
+            r#"digraph { a [shape=box, color="red:blue", regular=yes, width="1.5", foo=bar, style="filled,rounded"] }"#,
+        )
+        .unwrap();
+        let attrs = match g {
+            Graph::DiGraph { stmts, .. } => match &stmts[0] {
+                Stmt::Node(n) => n.attributes.clone(),
+                _ => panic!("the node is expected"),
+            },
+            _ => panic!("the digraph is expected"),
+        };
+        let typed: Vec<TypedAttribute> = attrs
+            .iter()
+            .map(|a| TypedAttribute::try_from(a).unwrap())
+            .collect();
+        assert_eq!(
+            typed,
+            vec![
+                TypedAttribute::shape(shape::box_),
+                TypedAttribute::color(color(ColorList::parse("red:blue").unwrap())),
+                TypedAttribute::regular(regular(true)),
+                TypedAttribute::width(width(1.5)),
+                TypedAttribute::Unknown(attr!("foo", "bar")),
+                TypedAttribute::style(style(Style::parse("filled,rounded").unwrap())),
+            ]
+        );
+        assert_eq!(
+            typed.into_iter().nth(1).unwrap().into_attr(),
+            attr!("color", esc "red:blue")
+        );
+
+        assert_eq!(pagedir::from_attr(&attr!("pagedir", "tl")), Ok(pagedir::TL));
+        assert_eq!(
+            shape::from_attr(&attr!("shape", "blob")).unwrap_err(),
+            "the value 'blob' of the attribute 'shape' is unknown"
+        );
+        assert!(shape::from_attr(&attr!("color", "box")).is_err());
+        assert!(fontcolor::from_attr(&attr!("fontcolor", "#zz0000")).is_err());
+        assert!(width::from_attr(&attr!("width", "wide")).is_err());
+        assert_eq!(
+            label::from_attr(&attr!("label", esc "a \\\"b\\\"")),
+            Ok(label("a \"b\"".to_string()))
+        );
+    }
+
+    #[test]
+    fn round_trip_test() {
+        use crate::printer::{DotPrinter, PrinterContext};
+
+        let node_attrs = |g: &Graph| match g {
+            Graph::DiGraph { stmts, .. } => match &stmts[0] {
+                Stmt::Node(n) => n
+                    .attributes
+                    .iter()
+                    .map(|a| TypedAttribute::try_from(a).unwrap())
+                    .collect::<Vec<_>>(),
+                _ => panic!("the node is expected"),
+            },
+            _ => panic!("the digraph is expected"),
+        };
+        let g = crate::parse(
+            r#"digraph { a [label="hello world", xlabel="x;y", fontname=Arial, width=1.5] }"#,
+        )
+        .unwrap();
+        let typed = node_attrs(&g);
+        let attributes: Vec<Attribute> =
+            node_attrs(&g).into_iter().map(|a| a.into_attr()).collect();
+        let printed = Graph::DiGraph {
+            id: id!(),
+            strict: false,
+            stmts: vec![Stmt::Node(Node::new(NodeId(id!("a"), None), attributes))],
+        }
+        .print(&mut PrinterContext::default());
+        assert!(printed.contains(r#"label="hello world""#));
+        assert_eq!(node_attrs(&crate::parse(&printed).unwrap()), typed);
+    }
 }
@@ -0,0 +1,315 @@
+//! The builder and the parser of the labels of the [`record`] shapes, `record` and `Mrecord`.
+//!
+//! The record is the list of the fields separated by `|`, the field is either the text
+//! with the optional port, `<port> text`, or the nested record in the braces
+//! laid out in the direction flipped relative to the enclosing one.
+//! The characters `{`, `}`, `|`, `<` and `>` are escaped in the text and the ports when the label is printed
+//! and unescaped when it is parsed, the other escape sequences like `\l` are left as is.
+//!
+//! # Example:
+//! ```rust
+//! use dot_generator::*;
+//! use dot_structures::*;
+//! use graphviz_rust::attributes::record::Record;
+//!
+//! let record = Record::new()
+//!     .field("a")
+//!     .port_field("p1", "b|c")
+//!     .flipped(Record::new().field("d").port_field("p2", ""));
+//! assert_eq!(record.to_string(), r#"a|<p1> b\|c|{d|<p2>}"#);
+//! assert_eq!(record.ports(), vec!["p1", "p2"]);
+//! assert_eq!(Record::parse(r#"a|<p1> b\|c|{ d | <p2> }"#).unwrap(), record);
+//!
+//! let edge_port = port!(id!("p1"));
+//! assert!(record.check_port(&edge_port).is_ok());
+//! assert!(record.check_port(&port!(id!("p3"), "n")).is_err());
+//! ```
+//!
+//! [`record`]: https://graphviz.org/doc/info/shapes.html#record
+use std::{
+    fmt::{Display, Formatter},
+    str::FromStr,
+};
+
+use dot_structures::{Id, Port};
+use into_attr::unquote;
+use pest::{iterators::Pair, Parser};
+
+mod grammar {
+    #[derive(Parser)]
+    #[grammar = "grammar/record.pest"]
+    pub(super) struct RecordParser;
+}
+
+use grammar::{RecordParser, Rule};
+
+const SPECIAL: [char; 5] = ['{', '}', '|', '<', '>'];
+
+fn escape(value: &str) -> String {
+    let mut result = String::new();
+    for c in value.chars() {
+        if SPECIAL.contains(&c) {
+            result.push('\\');
+        }
+        result.push(c);
+    }
+    result
+}
+
+fn unescape(value: &str) -> String {
+    let mut result = String::new();
+    let mut chars = value.chars().peekable();
+    while let Some(c) = chars.next() {
+        match chars.peek() {
+            Some(n) if c == '\\' && SPECIAL.contains(n) => {
+                result.push(*n);
+                chars.next();
+            }
+            _ => result.push(c),
+        }
+    }
+    result
+}
+
+/// The field with the text and the optional port.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextField {
+    pub port: Option<String>,
+    pub text: String,
+}
+
+impl Display for TextField {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match (&self.port, self.text.is_empty()) {
+            (Some(port), true) => write!(f, "<{}>", escape(port)),
+            (Some(port), false) => write!(f, "<{}> {}", escape(port), escape(&self.text)),
+            (None, _) => f.write_str(&escape(&self.text)),
+        }
+    }
+}
+
+/// The field of the record.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Field {
+    Text(TextField),
+    /// the nested record laid out in the flipped direction
+    Flipped(Record),
+}
+
+impl Display for Field {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Field::Text(field) => write!(f, "{}", field),
+            Field::Flipped(record) => write!(f, "{{{}}}", record),
+        }
+    }
+}
+
+/// The label of the record shape.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Record {
+    pub fields: Vec<Field>,
+}
+
+impl Record {
+    pub fn new() -> Self {
+        Record::default()
+    }
+
+    pub fn field(mut self, text: &str) -> Self {
+        self.fields.push(Field::Text(TextField {
+            port: None,
+            text: text.to_string(),
+        }));
+        self
+    }
+
+    pub fn port_field(mut self, port: &str, text: &str) -> Self {
+        self.fields.push(Field::Text(TextField {
+            port: Some(port.to_string()),
+            text: text.to_string(),
+        }));
+        self
+    }
+
+    /// Adds the nested record laid out in the flipped direction.
+    pub fn flipped(mut self, record: Record) -> Self {
+        self.fields.push(Field::Flipped(record));
+        self
+    }
+
+    /// Returns the ports of the fields including the nested ones.
+    pub fn ports(&self) -> Vec<&str> {
+        let mut ports = vec![];
+        self.collect_ports(&mut ports);
+        ports
+    }
+
+    fn collect_ports<'a>(&'a self, ports: &mut Vec<&'a str>) {
+        for field in self.fields.iter() {
+            match field {
+                Field::Text(field) => ports.extend(field.port.as_deref()),
+                Field::Flipped(record) => record.collect_ports(ports),
+            }
+        }
+    }
+
+    /// Checks that the port of the edge end refers to a field of the record,
+    /// the compass point is not checked.
+    pub fn check_port(&self, port: &Port) -> Result<(), String> {
+        match &port.0 {
+            Some(id) => {
+                let name = unquote(id);
+                if self.ports().contains(&name.as_str()) {
+                    Ok(())
+                } else {
+                    Err(format!("the record has no port '{}'", name))
+                }
+            }
+            None => Ok(()),
+        }
+    }
+
+    /// Parses the record label, the text of the fields is trimmed.
+    pub fn parse(value: &str) -> Result<Record, String> {
+        let record = RecordParser::parse(Rule::record, value)
+            .map_err(|e| e.to_string())?
+            .next()
+            .unwrap();
+        Ok(process_fields(record.into_inner().next().unwrap()))
+    }
+
+    /// Parses the label of the id.
+    pub fn from_id(id: &Id) -> Result<Record, String> {
+        match id {
+            Id::Html(_) => Err(format!("the id '{}' is an html string", id)),
+            _ => Record::parse(&unquote(id)),
+        }
+    }
+
+    pub fn to_id(&self) -> Id {
+        Id::Escaped(format!("\"{}\"", self.to_string().replace('"', "\\\"")))
+    }
+}
+
+fn process_fields(rule: Pair<Rule>) -> Record {
+    let mut fields = vec![];
+    for p in rule.into_inner() {
+        match p.as_rule() {
+            Rule::fields => fields.push(Field::Flipped(process_fields(p))),
+            _ => fields.push(Field::Text(process_text_field(p))),
+        }
+    }
+    Record { fields }
+}
+
+fn process_text_field(rule: Pair<Rule>) -> TextField {
+    let mut port = None;
+    let mut text = String::new();
+    for p in rule.into_inner() {
+        match p.as_rule() {
+            Rule::port => port = Some(unescape(p.as_str().trim())),
+            _ => text = unescape(p.as_str().trim()),
+        }
+    }
+    TextField { port, text }
+}
+
+impl Display for Record {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let fields: Vec<String> = self.fields.iter().map(|f| f.to_string()).collect();
+        f.write_str(&fields.join("|"))
+    }
+}
+
+impl FromStr for Record {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Record::parse(s)
+    }
+}
+
+impl TryFrom<&Id> for Record {
+    type Error = String;
+
+    fn try_from(id: &Id) -> Result<Self, Self::Error> {
+        Record::from_id(id)
+    }
+}
+
+impl From<Record> for Id {
+    fn from(record: Record) -> Self {
+        record.to_id()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use dot_generator::{attr, id, node, port};
+    use dot_structures::*;
+
+    use crate::attributes::{
+        record::{Field, Record, TextField},
+        shape, NodeAttrs,
+    };
+
+    #[test]
+    fn parse_test() {
+        let record = Record::parse(r#"{a|<p1> b|{c|<p\>2>d \{e\}}}"#).unwrap();
+        assert_eq!(
+            record,
+            Record::new().flipped(
+                Record::new()
+                    .field("a")
+                    .port_field("p1", "b")
+                    .flipped(Record::new().field("c").port_field("p>2", "d {e}"))
+            )
+        );
+        assert_eq!(record.to_string(), r#"{a|<p1> b|{c|<p\>2> d \{e\}}}"#);
+        assert_eq!(record.ports(), vec!["p1", "p>2"]);
+
+        let record = Record::parse(r"left\l||  <f0>  ").unwrap();
+        assert_eq!(
+            record.fields,
+            vec![
+                Field::Text(TextField {
+                    port: None,
+                    text: r"left\l".to_string()
+                }),
+                Field::Text(TextField {
+                    port: None,
+                    text: "".to_string()
+                }),
+                Field::Text(TextField {
+                    port: Some("f0".to_string()),
+                    text: "".to_string()
+                }),
+            ]
+        );
+
+        assert!(Record::parse("a|{b").is_err());
+        assert!(Record::parse("a}").is_err());
+        assert!(Record::parse("<a").is_err());
+        assert!(Record::from_id(&id!(html "<<b>a</b>>")).is_err());
+    }
+
+    #[test]
+    fn id_test() {
+        let record = Record::new().port_field("p", "say \"hi\"").field("x");
+        let id = record.to_id();
+        assert_eq!(id, id!(esc r#"<p> say \"hi\"|x"#));
+        assert_eq!(Record::from_id(&id).unwrap(), record);
+        assert!(record.check_port(&port!(id!(esc "p"), "s")).is_ok());
+        assert!(record.check_port(&port!(, "s")).is_ok());
+        assert!(record.check_port(&port!(id!("x"))).is_err());
+
+        let attrs = NodeAttrs::new()
+            .with(shape::Mrecord)
+            .with_custom(Attribute(id!("label"), id.clone()));
+        assert_eq!(
+            node!("n", attrs),
+            node!("n"; attr!("shape", "Mrecord"), Attribute(id!("label"), id))
+        );
+    }
+}
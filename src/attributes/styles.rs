@@ -0,0 +1,318 @@
+//! The values of the [`style`] attribute.
+//!
+//! The style is the combination of the [StyleItem]s separated by commas.
+//! Not every item applies to every element: `wedged` or `diagonals` are drawn for the nodes only,
+//! `tapered` for the edges only and `radial` for the nodes, the clusters and the root graph.
+//! Therefore the attribute accepts the element specific styles: [NodeStyle], [EdgeStyle],
+//! [ClusterStyle] and [GraphStyle] that can be created only from the applicable items.
+//!
+//! # Example:
+//! ```rust
+//! use graphviz_rust::attributes::{EdgeStyle, NodeStyle, Style, StyleItem};
+//!
+//! let style = Style::parse("filled, rounded,setlinewidth(2)").unwrap();
+//! assert_eq!(style.items()[2], StyleItem::SetLineWidth(2.0));
+//! assert_eq!(style.to_string(), "filled,rounded,setlinewidth(2)");
+//! assert!(NodeStyle::new(style).is_ok());
+//! assert!(EdgeStyle::parse("dashed,wedged").is_err());
+//! ```
+//!
+//! [`style`]: https://graphviz.org/docs/attr-types/style/
+use std::{
+    fmt::{Display, Formatter},
+    str::FromStr,
+};
+
+use dot_structures::Attribute;
+use into_attr::IntoAttribute;
+
+use crate::attributes::{
+    AttributeOf, EdgeAttributes, GraphAttributes, NodeAttributes, SubgraphAttributes,
+};
+
+/// The single style.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StyleItem {
+    Filled,
+    Invisible,
+    Solid,
+    Dashed,
+    Dotted,
+    Bold,
+    Rounded,
+    Diagonals,
+    Striped,
+    Wedged,
+    Radial,
+    Tapered,
+    /// the width of the pen in points, `penwidth` is the preferred way to set it
+    SetLineWidth(f32),
+}
+
+/// The element the style is applied to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StyleTarget {
+    Node,
+    Edge,
+    Cluster,
+    Graph,
+}
+
+impl Display for StyleTarget {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StyleTarget::Node => f.write_str("node"),
+            StyleTarget::Edge => f.write_str("edge"),
+            StyleTarget::Cluster => f.write_str("cluster"),
+            StyleTarget::Graph => f.write_str("graph"),
+        }
+    }
+}
+
+impl StyleItem {
+    pub fn parse(value: &str) -> Result<StyleItem, String> {
+        let value = value.trim();
+        let item = match value.to_lowercase().as_str() {
+            "filled" => StyleItem::Filled,
+            "invis" => StyleItem::Invisible,
+            "solid" => StyleItem::Solid,
+            "dashed" => StyleItem::Dashed,
+            "dotted" => StyleItem::Dotted,
+            "bold" => StyleItem::Bold,
+            "rounded" => StyleItem::Rounded,
+            "diagonals" => StyleItem::Diagonals,
+            "striped" => StyleItem::Striped,
+            "wedged" => StyleItem::Wedged,
+            "radial" => StyleItem::Radial,
+            "tapered" => StyleItem::Tapered,
+            v => {
+                let width = v
+                    .strip_prefix("setlinewidth(")
+                    .and_then(|w| w.strip_suffix(')'))
+                    .ok_or_else(|| format!("the style '{}' is unknown", value))?;
+                let width = width
+                    .trim()
+                    .parse::<f32>()
+                    .map_err(|_| format!("the line width in '{}' is not a number", value))?;
+                StyleItem::SetLineWidth(width)
+            }
+        };
+        Ok(item)
+    }
+
+    /// Returns true if the style is drawn for the given element.
+    pub fn applies_to(&self, target: StyleTarget) -> bool {
+        use StyleItem::*;
+        match target {
+            StyleTarget::Node => !matches!(self, Tapered),
+            StyleTarget::Edge => matches!(
+                self,
+                Invisible | Solid | Dashed | Dotted | Bold | Tapered | SetLineWidth(_)
+            ),
+            StyleTarget::Cluster => matches!(
+                self,
+                Filled
+                    | Invisible
+                    | Solid
+                    | Dashed
+                    | Dotted
+                    | Bold
+                    | Rounded
+                    | Striped
+                    | Radial
+                    | SetLineWidth(_)
+            ),
+            StyleTarget::Graph => matches!(self, Radial),
+        }
+    }
+}
+
+impl Display for StyleItem {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StyleItem::Filled => f.write_str("filled"),
+            StyleItem::Invisible => f.write_str("invis"),
+            StyleItem::Solid => f.write_str("solid"),
+            StyleItem::Dashed => f.write_str("dashed"),
+            StyleItem::Dotted => f.write_str("dotted"),
+            StyleItem::Bold => f.write_str("bold"),
+            StyleItem::Rounded => f.write_str("rounded"),
+            StyleItem::Diagonals => f.write_str("diagonals"),
+            StyleItem::Striped => f.write_str("striped"),
+            StyleItem::Wedged => f.write_str("wedged"),
+            StyleItem::Radial => f.write_str("radial"),
+            StyleItem::Tapered => f.write_str("tapered"),
+            StyleItem::SetLineWidth(w) => write!(f, "setlinewidth({})", w),
+        }
+    }
+}
+
+/// The combination of the styles.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Style(Vec<StyleItem>);
+
+impl Style {
+    pub fn new() -> Self {
+        Style::default()
+    }
+
+    /// Adds the item if it is not in the style yet.
+    pub fn with(mut self, item: StyleItem) -> Self {
+        if !self.0.contains(&item) {
+            self.0.push(item);
+        }
+        self
+    }
+
+    pub fn items(&self) -> &[StyleItem] {
+        &self.0
+    }
+
+    /// Parses the comma separated styles.
+    pub fn parse(value: &str) -> Result<Style, String> {
+        let mut style = Style::new();
+        for item in value.split(',').filter(|i| !i.trim().is_empty()) {
+            style = style.with(StyleItem::parse(item)?);
+        }
+        Ok(style)
+    }
+
+    /// Checks that every item applies to the given element.
+    pub fn validate(&self, target: StyleTarget) -> Result<(), String> {
+        match self.0.iter().find(|i| !i.applies_to(target)) {
+            Some(item) => Err(format!(
+                "the style '{}' is not applicable to the {}",
+                item, target
+            )),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Display for Style {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let items: Vec<String> = self.0.iter().map(|i| i.to_string()).collect();
+        f.write_str(items.join(",").as_str())
+    }
+}
+
+impl FromStr for Style {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Style::parse(s)
+    }
+}
+
+impl From<StyleItem> for Style {
+    fn from(item: StyleItem) -> Self {
+        Style::new().with(item)
+    }
+}
+
+impl From<Vec<StyleItem>> for Style {
+    fn from(items: Vec<StyleItem>) -> Self {
+        items.into_iter().fold(Style::new(), Style::with)
+    }
+}
+
+macro_rules! target_style {
+    ($name:tt, $target:expr, $owner:tt) => {
+        #[derive(Debug, Clone, PartialEq)]
+        pub struct $name(Style);
+
+        impl $name {
+            /// Creates the style checking that every item applies to the element.
+            pub fn new(style: impl Into<Style>) -> Result<Self, String> {
+                let style = style.into();
+                style.validate($target)?;
+                Ok($name(style))
+            }
+
+            pub fn parse(value: &str) -> Result<Self, String> {
+                $name::new(Style::parse(value)?)
+            }
+
+            pub fn style(&self) -> &Style {
+                &self.0
+            }
+        }
+
+        impl TryFrom<Style> for $name {
+            type Error = String;
+
+            fn try_from(style: Style) -> Result<Self, Self::Error> {
+                $name::new(style)
+            }
+        }
+
+        impl From<$name> for Style {
+            fn from(value: $name) -> Self {
+                value.0
+            }
+        }
+
+        impl Display for $name {
+            fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+                write!(f, "{}", self.0)
+            }
+        }
+
+        impl IntoAttribute for $name {
+            fn into_attr(self) -> Attribute {
+                crate::attributes::style(self.0).into_attr()
+            }
+        }
+
+        impl AttributeOf<$owner> for $name {}
+    };
+}
+
+target_style!(NodeStyle, StyleTarget::Node, NodeAttributes);
+target_style!(EdgeStyle, StyleTarget::Edge, EdgeAttributes);
+target_style!(ClusterStyle, StyleTarget::Cluster, SubgraphAttributes);
+target_style!(GraphStyle, StyleTarget::Graph, GraphAttributes);
+
+#[cfg(test)]
+mod tests {
+    use crate::attributes::{
+        ClusterStyle, EdgeStyle, GraphStyle, NodeStyle, Style, StyleItem, StyleTarget,
+    };
+
+    #[test]
+    fn parse_test() {
+        let style = Style::parse("filled , Dashed,setlinewidth( 1.5 ),filled").unwrap();
+        assert_eq!(
+            style.items(),
+            &[
+                StyleItem::Filled,
+                StyleItem::Dashed,
+                StyleItem::SetLineWidth(1.5)
+            ]
+        );
+        assert_eq!(style.to_string(), "filled,dashed,setlinewidth(1.5)");
+        assert_eq!(Style::parse("").unwrap(), Style::new());
+        assert!(Style::parse("filled,shiny").is_err());
+        assert!(Style::parse("setlinewidth(x)").is_err());
+    }
+
+    #[test]
+    fn validity_test() {
+        assert!(StyleItem::Wedged.applies_to(StyleTarget::Node));
+        assert!(!StyleItem::Wedged.applies_to(StyleTarget::Edge));
+        assert!(!StyleItem::Tapered.applies_to(StyleTarget::Node));
+        assert!(!StyleItem::Diagonals.applies_to(StyleTarget::Cluster));
+
+        assert!(NodeStyle::parse("filled,wedged,diagonals").is_ok());
+        assert_eq!(
+            EdgeStyle::parse("bold,wedged").unwrap_err(),
+            "the style 'wedged' is not applicable to the edge"
+        );
+        assert!(EdgeStyle::new(StyleItem::Tapered).is_ok());
+        assert!(ClusterStyle::new(vec![StyleItem::Filled, StyleItem::Striped]).is_ok());
+        assert!(ClusterStyle::parse("invis").is_ok());
+        assert!(GraphStyle::parse("radial").is_ok());
+        assert!(GraphStyle::parse("filled").is_err());
+        assert!(NodeStyle::try_from(Style::parse("tapered").unwrap()).is_err());
+    }
+}
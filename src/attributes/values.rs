@@ -0,0 +1,1220 @@
+//! The composite values of the attributes following the graphviz [`types`].
+//!
+//! Every value can be parsed from and printed into the graphviz notation.
+//! The values accepting several forms implement `From` for every form,
+//! e.g. [PointOrDouble] can be created from `f32` and from [Point].
+//!
+//! [`types`]: https://graphviz.org/docs/attr-types/
+use std::{
+    fmt::{Display, Formatter},
+    str::FromStr,
+};
+
+fn number(value: &str) -> Result<f32, String> {
+    value
+        .trim()
+        .parse::<f32>()
+        .map_err(|_| format!("'{}' is not a number", value))
+}
+
+fn numbers(value: &str) -> Result<Vec<f32>, String> {
+    value.split(',').map(number).collect()
+}
+
+/// Parses the boolean form of the value: `yes`, `no` and the integers are accepted as well.
+fn boolean(value: &str, name: &str) -> Result<bool, String> {
+    into_attr::parse_bool(value, name)
+        .map_err(|_| format!("the value '{}' is unknown for {}", value, name))
+}
+
+macro_rules! from_str {
+    ($($ty:tt),+) => {
+        $(impl FromStr for $ty {
+            type Err = String;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                $ty::parse(s)
+            }
+        })+
+    };
+}
+
+from_str!(
+    Point,
+    PointOrDouble,
+    AddPoint,
+    Rect,
+    PointList,
+    RectList,
+    SplineType,
+    Pos,
+    Splines,
+    Ratio,
+    Overlap,
+    LayerRange,
+    LayerList,
+    ViewPort,
+    RankSep,
+    Pack,
+    FixedSize,
+    ImageScale,
+    Normalize,
+    Compass,
+    PortPos,
+    Orientation
+);
+
+/// The [`point`] `x,y[,z][!]` in inches or points depending on the attribute.
+/// The `!` denotes that the position is fixed.
+///
+/// [`point`]: https://graphviz.org/docs/attr-types/point/
+#[derive(Debug, Clone, PartialEq)]
+pub struct Point {
+    pub x: f32,
+    pub y: f32,
+    pub z: Option<f32>,
+    pub fixed: bool,
+}
+
+impl Point {
+    pub fn new(x: f32, y: f32) -> Self {
+        Point {
+            x,
+            y,
+            z: None,
+            fixed: false,
+        }
+    }
+
+    pub fn new_3d(x: f32, y: f32, z: f32) -> Self {
+        Point {
+            x,
+            y,
+            z: Some(z),
+            fixed: false,
+        }
+    }
+
+    /// Marks the point as fixed adding `!`.
+    pub fn fixed(mut self) -> Self {
+        self.fixed = true;
+        self
+    }
+
+    pub fn parse(value: &str) -> Result<Point, String> {
+        let trimmed = value.trim();
+        let (coords, fixed) = match trimmed.strip_suffix('!') {
+            Some(coords) => (coords, true),
+            None => (trimmed, false),
+        };
+        let point = match numbers(coords)?.as_slice() {
+            [x, y] => Point::new(*x, *y),
+            [x, y, z] => Point::new_3d(*x, *y, *z),
+            _ => {
+                return Err(format!(
+                    "the point '{}' should have 2 or 3 coordinates",
+                    value
+                ))
+            }
+        };
+        Ok(Point { fixed, ..point })
+    }
+}
+
+impl Display for Point {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{},{}", self.x, self.y)?;
+        if let Some(z) = self.z {
+            write!(f, ",{}", z)?;
+        }
+        if self.fixed {
+            f.write_str("!")?;
+        }
+        Ok(())
+    }
+}
+
+/// The value given either as the single number for both dimensions or as the [Point],
+/// e.g. `margin`, `pad`, `page`, `size` and `scale`.
+///
+/// The single number with `!` is parsed into the fixed point with equal coordinates.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PointOrDouble {
+    Double(f32),
+    Point(Point),
+}
+
+impl PointOrDouble {
+    pub fn parse(value: &str) -> Result<PointOrDouble, String> {
+        let trimmed = value.trim();
+        if trimmed.contains(',') {
+            Ok(PointOrDouble::Point(Point::parse(trimmed)?))
+        } else if let Some(v) = trimmed.strip_suffix('!') {
+            let v = number(v)?;
+            Ok(PointOrDouble::Point(Point::new(v, v).fixed()))
+        } else {
+            Ok(PointOrDouble::Double(number(trimmed)?))
+        }
+    }
+}
+
+impl Display for PointOrDouble {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PointOrDouble::Double(v) => write!(f, "{}", v),
+            PointOrDouble::Point(p) => write!(f, "{}", p),
+        }
+    }
+}
+
+impl From<f32> for PointOrDouble {
+    fn from(value: f32) -> Self {
+        PointOrDouble::Double(value)
+    }
+}
+
+impl From<Point> for PointOrDouble {
+    fn from(value: Point) -> Self {
+        PointOrDouble::Point(value)
+    }
+}
+
+/// The [`addDouble`] or [`addPoint`] value where the leading `+` denotes
+/// that the value is added to the default one, e.g. `sep` and `esep`.
+///
+/// [`addDouble`]: https://graphviz.org/docs/attr-types/addDouble/
+/// [`addPoint`]: https://graphviz.org/docs/attr-types/addPoint/
+#[derive(Debug, Clone, PartialEq)]
+pub struct AddPoint {
+    pub value: PointOrDouble,
+    pub additive: bool,
+}
+
+impl AddPoint {
+    /// Creates the value added to the default one.
+    pub fn add(value: impl Into<PointOrDouble>) -> Self {
+        AddPoint {
+            value: value.into(),
+            additive: true,
+        }
+    }
+
+    pub fn parse(value: &str) -> Result<AddPoint, String> {
+        let trimmed = value.trim();
+        match trimmed.strip_prefix('+') {
+            Some(v) => Ok(AddPoint::add(PointOrDouble::parse(v)?)),
+            None => Ok(AddPoint::from(PointOrDouble::parse(trimmed)?)),
+        }
+    }
+}
+
+impl Display for AddPoint {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.additive {
+            f.write_str("+")?;
+        }
+        write!(f, "{}", self.value)
+    }
+}
+
+impl From<PointOrDouble> for AddPoint {
+    fn from(value: PointOrDouble) -> Self {
+        AddPoint {
+            value,
+            additive: false,
+        }
+    }
+}
+
+impl From<f32> for AddPoint {
+    fn from(value: f32) -> Self {
+        AddPoint::from(PointOrDouble::from(value))
+    }
+}
+
+impl From<Point> for AddPoint {
+    fn from(value: Point) -> Self {
+        AddPoint::from(PointOrDouble::from(value))
+    }
+}
+
+/// The [`rect`] `llx,lly,urx,ury` given by the lower left and the upper right corners.
+///
+/// [`rect`]: https://graphviz.org/docs/attr-types/rect/
+#[derive(Debug, Clone, PartialEq)]
+pub struct Rect {
+    pub llx: f32,
+    pub lly: f32,
+    pub urx: f32,
+    pub ury: f32,
+}
+
+impl Rect {
+    pub fn new(llx: f32, lly: f32, urx: f32, ury: f32) -> Self {
+        Rect { llx, lly, urx, ury }
+    }
+
+    pub fn parse(value: &str) -> Result<Rect, String> {
+        match numbers(value)?.as_slice() {
+            [llx, lly, urx, ury] => Ok(Rect::new(*llx, *lly, *urx, *ury)),
+            _ => Err(format!("the rect '{}' should have 4 coordinates", value)),
+        }
+    }
+}
+
+impl Display for Rect {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{},{},{},{}", self.llx, self.lly, self.urx, self.ury)
+    }
+}
+
+/// The [`pointList`] separated by spaces, e.g. `vertices`.
+///
+/// [`pointList`]: https://graphviz.org/docs/attr-types/pointList/
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct PointList(pub Vec<Point>);
+
+impl PointList {
+    pub fn parse(value: &str) -> Result<PointList, String> {
+        value
+            .split_whitespace()
+            .map(Point::parse)
+            .collect::<Result<Vec<_>, _>>()
+            .map(PointList)
+    }
+}
+
+impl Display for PointList {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let points: Vec<String> = self.0.iter().map(|p| p.to_string()).collect();
+        f.write_str(points.join(" ").as_str())
+    }
+}
+
+/// The list of the [Rect]s separated by spaces, e.g. the fields of the record node in `rects`.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct RectList(pub Vec<Rect>);
+
+impl RectList {
+    pub fn parse(value: &str) -> Result<RectList, String> {
+        value
+            .split_whitespace()
+            .map(Rect::parse)
+            .collect::<Result<Vec<_>, _>>()
+            .map(RectList)
+    }
+}
+
+impl Display for RectList {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let rects: Vec<String> = self.0.iter().map(|r| r.to_string()).collect();
+        f.write_str(rects.join(" ").as_str())
+    }
+}
+
+impl From<Rect> for RectList {
+    fn from(value: Rect) -> Self {
+        RectList(vec![value])
+    }
+}
+
+/// The single spline of the [SplineType] with the optional positions of the arrowheads.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct EdgeSpline {
+    /// the end point of the arrowhead at the head, `e,x,y`
+    pub end: Option<Point>,
+    /// the end point of the arrowhead at the tail, `s,x,y`
+    pub start: Option<Point>,
+    /// the control points of the B-spline
+    pub points: Vec<Point>,
+}
+
+/// The [`splineType`], the list of the splines separated by `;` given in the `pos` of the edge.
+///
+/// [`splineType`]: https://graphviz.org/docs/attr-types/splineType/
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct SplineType(pub Vec<EdgeSpline>);
+
+impl SplineType {
+    pub fn parse(value: &str) -> Result<SplineType, String> {
+        let mut splines = vec![];
+        for spline in value.split(';') {
+            let mut result = EdgeSpline::default();
+            for token in spline.split_whitespace() {
+                if let Some(p) = token.strip_prefix("e,") {
+                    result.end = Some(Point::parse(p)?);
+                } else if let Some(p) = token.strip_prefix("s,") {
+                    result.start = Some(Point::parse(p)?);
+                } else {
+                    result.points.push(Point::parse(token)?);
+                }
+            }
+            if result.points.len() < 4 || result.points.len() % 3 != 1 {
+                return Err(format!(
+                    "the spline '{}' should have 3n+1 control points",
+                    spline
+                ));
+            }
+            splines.push(result);
+        }
+        Ok(SplineType(splines))
+    }
+}
+
+impl Display for SplineType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let splines: Vec<String> = self
+            .0
+            .iter()
+            .map(|s| {
+                let mut tokens = vec![];
+                if let Some(e) = &s.end {
+                    tokens.push(format!("e,{}", e));
+                }
+                if let Some(st) = &s.start {
+                    tokens.push(format!("s,{}", st));
+                }
+                tokens.extend(s.points.iter().map(|p| p.to_string()));
+                tokens.join(" ")
+            })
+            .collect();
+        f.write_str(splines.join(";").as_str())
+    }
+}
+
+/// The `pos` attribute: the [Point] of the node or the [SplineType] of the edge.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Pos {
+    Point(Point),
+    Spline(SplineType),
+}
+
+impl Pos {
+    pub fn parse(value: &str) -> Result<Pos, String> {
+        if value.trim().contains(char::is_whitespace) || value.contains(';') {
+            Ok(Pos::Spline(SplineType::parse(value)?))
+        } else {
+            Ok(Pos::Point(Point::parse(value)?))
+        }
+    }
+}
+
+impl Display for Pos {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Pos::Point(p) => write!(f, "{}", p),
+            Pos::Spline(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+impl From<Point> for Pos {
+    fn from(value: Point) -> Self {
+        Pos::Point(value)
+    }
+}
+
+impl From<SplineType> for Pos {
+    fn from(value: SplineType) -> Self {
+        Pos::Spline(value)
+    }
+}
+
+/// Defines the values given by the keywords.
+macro_rules! keywords {
+    ($name:tt; $($variant:tt => $($keyword:literal)|+),+) => {
+        impl $name {
+            pub fn parse(value: &str) -> Result<$name, String> {
+                match value.trim().to_lowercase().as_str() {
+                    $($($keyword)|+ => Ok($name::$variant),)+
+                    _ => Err(format!("the value '{}' is unknown for {}", value, stringify!($name))),
+                }
+            }
+        }
+
+        impl Display for $name {
+            fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+                match self {
+                    $($name::$variant => f.write_str([$($keyword),+][0]),)+
+                }
+            }
+        }
+    };
+}
+
+/// The [`splines`] attribute controlling how the edges are represented.
+///
+/// [`splines`]: https://graphviz.org/docs/attrs/splines/
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Splines {
+    None,
+    Line,
+    Polyline,
+    Curved,
+    Ortho,
+    Spline,
+    Compound,
+}
+
+keywords!(Splines;
+    None => "none" | "",
+    Line => "line" | "false",
+    Polyline => "polyline",
+    Curved => "curved",
+    Ortho => "ortho",
+    Spline => "spline" | "true",
+    Compound => "compound"
+);
+
+impl From<bool> for Splines {
+    fn from(value: bool) -> Self {
+        if value {
+            Splines::Spline
+        } else {
+            Splines::Line
+        }
+    }
+}
+
+/// The [`ratio`] attribute given by the number or the keyword.
+///
+/// [`ratio`]: https://graphviz.org/docs/attrs/ratio/
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Ratio {
+    Value(f32),
+    Fill,
+    Compress,
+    Expand,
+    Auto,
+}
+
+impl Ratio {
+    pub fn parse(value: &str) -> Result<Ratio, String> {
+        match value.trim().to_lowercase().as_str() {
+            "fill" => Ok(Ratio::Fill),
+            "compress" => Ok(Ratio::Compress),
+            "expand" => Ok(Ratio::Expand),
+            "auto" => Ok(Ratio::Auto),
+            v => Ok(Ratio::Value(number(v)?)),
+        }
+    }
+}
+
+impl Display for Ratio {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Ratio::Value(v) => write!(f, "{}", v),
+            Ratio::Fill => f.write_str("fill"),
+            Ratio::Compress => f.write_str("compress"),
+            Ratio::Expand => f.write_str("expand"),
+            Ratio::Auto => f.write_str("auto"),
+        }
+    }
+}
+
+impl From<f32> for Ratio {
+    fn from(value: f32) -> Self {
+        Ratio::Value(value)
+    }
+}
+
+/// The [`overlap`] attribute defining how the overlaps of the nodes are removed.
+///
+/// [`overlap`]: https://graphviz.org/docs/attrs/overlap/
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Overlap {
+    True,
+    False,
+    Scale,
+    ScaleXY,
+    /// the prism algorithm with the optional number of the attempts
+    Prism(Option<u32>),
+    Voronoi,
+    Compress,
+    Vpsc,
+    Ipsep,
+    Ortho,
+    OrthoXY,
+    OrthoYX,
+    POrtho,
+    POrthoXY,
+    POrthoYX,
+}
+
+impl Overlap {
+    pub fn parse(value: &str) -> Result<Overlap, String> {
+        let value = value.trim().to_lowercase();
+        match value.as_str() {
+            "scale" => Ok(Overlap::Scale),
+            "scalexy" => Ok(Overlap::ScaleXY),
+            "voronoi" => Ok(Overlap::Voronoi),
+            "compress" => Ok(Overlap::Compress),
+            "vpsc" => Ok(Overlap::Vpsc),
+            "ipsep" => Ok(Overlap::Ipsep),
+            "ortho" => Ok(Overlap::Ortho),
+            "orthoxy" => Ok(Overlap::OrthoXY),
+            "orthoyx" => Ok(Overlap::OrthoYX),
+            "portho" => Ok(Overlap::POrtho),
+            "porthoxy" => Ok(Overlap::POrthoXY),
+            "porthoyx" => Ok(Overlap::POrthoYX),
+            "prism" => Ok(Overlap::Prism(None)),
+            v => match v.strip_prefix("prism").map(|n| n.parse::<u32>()) {
+                Some(Ok(n)) => Ok(Overlap::Prism(Some(n))),
+                _ => boolean(v, "Overlap").map(Overlap::from),
+            },
+        }
+    }
+}
+
+impl Display for Overlap {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Overlap::True => f.write_str("true"),
+            Overlap::False => f.write_str("false"),
+            Overlap::Scale => f.write_str("scale"),
+            Overlap::ScaleXY => f.write_str("scalexy"),
+            Overlap::Prism(None) => f.write_str("prism"),
+            Overlap::Prism(Some(n)) => write!(f, "prism{}", n),
+            Overlap::Voronoi => f.write_str("voronoi"),
+            Overlap::Compress => f.write_str("compress"),
+            Overlap::Vpsc => f.write_str("vpsc"),
+            Overlap::Ipsep => f.write_str("ipsep"),
+            Overlap::Ortho => f.write_str("ortho"),
+            Overlap::OrthoXY => f.write_str("orthoxy"),
+            Overlap::OrthoYX => f.write_str("orthoyx"),
+            Overlap::POrtho => f.write_str("portho"),
+            Overlap::POrthoXY => f.write_str("porthoxy"),
+            Overlap::POrthoYX => f.write_str("porthoyx"),
+        }
+    }
+}
+
+impl From<bool> for Overlap {
+    fn from(value: bool) -> Self {
+        if value {
+            Overlap::True
+        } else {
+            Overlap::False
+        }
+    }
+}
+
+/// The layer or the range of the layers in the [LayerRange].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Layer {
+    /// the name, the number or `all`
+    Id(String),
+    Range(String, String),
+}
+
+/// The [`layerRange`] with the default separators: `,` between the items and `:` in the ranges.
+///
+/// [`layerRange`]: https://graphviz.org/docs/attr-types/layerRange/
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct LayerRange(pub Vec<Layer>);
+
+impl LayerRange {
+    pub fn parse(value: &str) -> Result<LayerRange, String> {
+        let mut layers = vec![];
+        for item in value.split(',').map(str::trim) {
+            let layer = match item.split_once(':') {
+                Some((from, to)) if !from.is_empty() && !to.is_empty() => {
+                    Layer::Range(from.to_string(), to.to_string())
+                }
+                None if !item.is_empty() => Layer::Id(item.to_string()),
+                _ => return Err(format!("the layer range '{}' is invalid", value)),
+            };
+            layers.push(layer);
+        }
+        Ok(LayerRange(layers))
+    }
+}
+
+impl Display for LayerRange {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let layers: Vec<String> = self
+            .0
+            .iter()
+            .map(|l| match l {
+                Layer::Id(id) => id.clone(),
+                Layer::Range(from, to) => format!("{}:{}", from, to),
+            })
+            .collect();
+        f.write_str(layers.join(",").as_str())
+    }
+}
+
+impl From<&str> for LayerRange {
+    fn from(value: &str) -> Self {
+        LayerRange(vec![Layer::Id(value.to_string())])
+    }
+}
+
+/// The [`layerList`], the names of the layers separated by `:`.
+///
+/// [`layerList`]: https://graphviz.org/docs/attr-types/layerList/
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct LayerList(pub Vec<String>);
+
+impl LayerList {
+    pub fn parse(value: &str) -> Result<LayerList, String> {
+        let layers: Vec<String> = value
+            .split(|c: char| c == ':' || c.is_whitespace())
+            .filter(|l| !l.is_empty())
+            .map(str::to_string)
+            .collect();
+        if layers.is_empty() {
+            Err(format!("the layer list '{}' is empty", value))
+        } else {
+            Ok(LayerList(layers))
+        }
+    }
+}
+
+impl Display for LayerList {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.0.join(":").as_str())
+    }
+}
+
+/// The center of the [ViewPort].
+#[derive(Debug, Clone, PartialEq)]
+pub enum ViewPortCenter {
+    Point(f32, f32),
+    /// the name of the node
+    Node(String),
+}
+
+/// The [`viewPort`] `W,H[,Z[,x,y | ,'N']]` clipping the final drawing.
+///
+/// [`viewPort`]: https://graphviz.org/docs/attr-types/viewPort/
+#[derive(Debug, Clone, PartialEq)]
+pub struct ViewPort {
+    pub width: f32,
+    pub height: f32,
+    pub zoom: Option<f32>,
+    pub center: Option<ViewPortCenter>,
+}
+
+impl ViewPort {
+    pub fn parse(value: &str) -> Result<ViewPort, String> {
+        let err = || format!("the viewport '{}' is invalid", value);
+        let parts: Vec<&str> = value.splitn(4, ',').map(str::trim).collect();
+        if parts.len() < 2 {
+            return Err(err());
+        }
+        let zoom = parts.get(2).map(|z| number(z)).transpose()?;
+        let center = match parts.get(3) {
+            None => None,
+            Some(n) if n.starts_with('\'') => Some(ViewPortCenter::Node(
+                n.strip_prefix('\'')
+                    .and_then(|n| n.strip_suffix('\''))
+                    .ok_or_else(err)?
+                    .to_string(),
+            )),
+            Some(p) => match numbers(p)?.as_slice() {
+                [x, y] => Some(ViewPortCenter::Point(*x, *y)),
+                _ => return Err(err()),
+            },
+        };
+        Ok(ViewPort {
+            width: number(parts[0])?,
+            height: number(parts[1])?,
+            zoom,
+            center,
+        })
+    }
+}
+
+impl Display for ViewPort {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{},{}", self.width, self.height)?;
+        if self.zoom.is_some() || self.center.is_some() {
+            write!(f, ",{}", self.zoom.unwrap_or(1.))?;
+        }
+        match &self.center {
+            Some(ViewPortCenter::Point(x, y)) => write!(f, ",{},{}", x, y),
+            Some(ViewPortCenter::Node(n)) => write!(f, ",'{}'", n),
+            None => Ok(()),
+        }
+    }
+}
+
+/// The [`ranksep`] given by the number or the list of the numbers separated by `:`
+/// with the optional `equally` keyword.
+///
+/// [`ranksep`]: https://graphviz.org/docs/attrs/ranksep/
+#[derive(Debug, Clone, PartialEq)]
+pub struct RankSep {
+    pub values: Vec<f32>,
+    pub equally: bool,
+}
+
+impl RankSep {
+    pub fn parse(value: &str) -> Result<RankSep, String> {
+        let trimmed = value.trim();
+        let (values, equally) = match trimmed.strip_suffix("equally") {
+            Some(values) => (values.trim(), true),
+            None => (trimmed, false),
+        };
+        let values = if values.is_empty() {
+            vec![]
+        } else {
+            values.split(':').map(number).collect::<Result<_, _>>()?
+        };
+        Ok(RankSep { values, equally })
+    }
+}
+
+impl Display for RankSep {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let values: Vec<String> = self.values.iter().map(|v| v.to_string()).collect();
+        f.write_str(values.join(":").as_str())?;
+        match (self.equally, values.is_empty()) {
+            (true, true) => f.write_str("equally"),
+            (true, false) => f.write_str(" equally"),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl From<f32> for RankSep {
+    fn from(value: f32) -> Self {
+        RankSep {
+            values: vec![value],
+            equally: false,
+        }
+    }
+}
+
+/// The [`pack`] attribute given by the flag or the margin around the packed components.
+///
+/// [`pack`]: https://graphviz.org/docs/attrs/pack/
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Pack {
+    Enabled(bool),
+    Margin(i32),
+}
+
+impl Pack {
+    pub fn parse(value: &str) -> Result<Pack, String> {
+        match value.trim().parse::<i32>() {
+            Ok(margin) => Ok(Pack::Margin(margin)),
+            Err(_) => boolean(value, "Pack").map(Pack::Enabled),
+        }
+    }
+}
+
+impl Display for Pack {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Pack::Enabled(v) => write!(f, "{}", v),
+            Pack::Margin(v) => write!(f, "{}", v),
+        }
+    }
+}
+
+impl From<bool> for Pack {
+    fn from(value: bool) -> Self {
+        Pack::Enabled(value)
+    }
+}
+
+impl From<i32> for Pack {
+    fn from(value: i32) -> Self {
+        Pack::Margin(value)
+    }
+}
+
+/// The [`fixedsize`] attribute of the node.
+///
+/// [`fixedsize`]: https://graphviz.org/docs/attrs/fixedsize/
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FixedSize {
+    True,
+    False,
+    /// the size is fixed for the shape only and the label can go beyond it
+    Shape,
+}
+
+impl FixedSize {
+    pub fn parse(value: &str) -> Result<FixedSize, String> {
+        match value.trim().to_lowercase().as_str() {
+            "shape" => Ok(FixedSize::Shape),
+            v => boolean(v, "FixedSize").map(FixedSize::from),
+        }
+    }
+}
+
+impl Display for FixedSize {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FixedSize::True => f.write_str("true"),
+            FixedSize::False => f.write_str("false"),
+            FixedSize::Shape => f.write_str("shape"),
+        }
+    }
+}
+
+impl From<bool> for FixedSize {
+    fn from(value: bool) -> Self {
+        if value {
+            FixedSize::True
+        } else {
+            FixedSize::False
+        }
+    }
+}
+
+/// The [`imagescale`] attribute of the node.
+///
+/// [`imagescale`]: https://graphviz.org/docs/attrs/imagescale/
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageScale {
+    True,
+    False,
+    Width,
+    Height,
+    Both,
+}
+
+impl ImageScale {
+    pub fn parse(value: &str) -> Result<ImageScale, String> {
+        match value.trim().to_lowercase().as_str() {
+            "width" => Ok(ImageScale::Width),
+            "height" => Ok(ImageScale::Height),
+            "both" => Ok(ImageScale::Both),
+            v => boolean(v, "ImageScale").map(ImageScale::from),
+        }
+    }
+}
+
+impl Display for ImageScale {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ImageScale::True => f.write_str("true"),
+            ImageScale::False => f.write_str("false"),
+            ImageScale::Width => f.write_str("width"),
+            ImageScale::Height => f.write_str("height"),
+            ImageScale::Both => f.write_str("both"),
+        }
+    }
+}
+
+impl From<bool> for ImageScale {
+    fn from(value: bool) -> Self {
+        if value {
+            ImageScale::True
+        } else {
+            ImageScale::False
+        }
+    }
+}
+
+/// The [`normalize`] attribute given by the flag or the angle of the first edge.
+///
+/// [`normalize`]: https://graphviz.org/docs/attrs/normalize/
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Normalize {
+    Enabled(bool),
+    Angle(f32),
+}
+
+impl Normalize {
+    pub fn parse(value: &str) -> Result<Normalize, String> {
+        match number(value) {
+            Ok(angle) => Ok(Normalize::Angle(angle)),
+            Err(_) => boolean(value, "Normalize").map(Normalize::Enabled),
+        }
+    }
+}
+
+impl Display for Normalize {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Normalize::Enabled(v) => write!(f, "{}", v),
+            Normalize::Angle(v) => write!(f, "{}", v),
+        }
+    }
+}
+
+impl From<bool> for Normalize {
+    fn from(value: bool) -> Self {
+        Normalize::Enabled(value)
+    }
+}
+
+impl From<f32> for Normalize {
+    fn from(value: f32) -> Self {
+        Normalize::Angle(value)
+    }
+}
+
+/// The [`orientation`] given by the rotation angle for the nodes
+/// and by the landscape or portrait mode for the graphs.
+///
+/// [`orientation`]: https://graphviz.org/docs/attrs/orientation/
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Orientation {
+    /// the angle of the node rotation in degrees
+    Angle(f32),
+    /// the graph drawing is rotated by 90 degrees
+    Landscape,
+    Portrait,
+}
+
+impl Orientation {
+    /// Parses the angle or the graph mode: the value starting with `l` or `L` is the landscape one.
+    pub fn parse(value: &str) -> Result<Orientation, String> {
+        let value = value.trim();
+        match number(value) {
+            Ok(angle) => Ok(Orientation::Angle(angle)),
+            Err(_) if value.starts_with(['l', 'L']) => Ok(Orientation::Landscape),
+            Err(_) => Ok(Orientation::Portrait),
+        }
+    }
+}
+
+impl Display for Orientation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Orientation::Angle(v) => write!(f, "{}", v),
+            Orientation::Landscape => f.write_str("landscape"),
+            Orientation::Portrait => f.write_str("portrait"),
+        }
+    }
+}
+
+impl From<f32> for Orientation {
+    fn from(value: f32) -> Self {
+        Orientation::Angle(value)
+    }
+}
+
+/// The [`compass point`] of the node the edge is aimed at.
+///
+/// [`compass point`]: https://graphviz.org/docs/attr-types/portPos/
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Compass {
+    N,
+    NE,
+    E,
+    SE,
+    S,
+    SW,
+    W,
+    NW,
+    /// the center of the node or the port
+    Center,
+    /// the side of the node or the port closest to the other end of the edge
+    Any,
+}
+
+keywords!(Compass;
+    N => "n",
+    NE => "ne",
+    E => "e",
+    SE => "se",
+    S => "s",
+    SW => "sw",
+    W => "w",
+    NW => "nw",
+    Center => "c",
+    Any => "_"
+);
+
+/// The [`portPos`] `port[:compass]` or `compass` given in `headport` and `tailport`.
+/// The value which is not a compass point is treated as the name of the port.
+///
+/// [`portPos`]: https://graphviz.org/docs/attr-types/portPos/
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PortPos {
+    pub port: Option<String>,
+    pub compass: Option<Compass>,
+}
+
+impl PortPos {
+    pub fn port(name: &str) -> PortPos {
+        PortPos {
+            port: Some(name.to_string()),
+            compass: None,
+        }
+    }
+
+    pub fn with_compass(self, compass: Compass) -> PortPos {
+        PortPos {
+            compass: Some(compass),
+            ..self
+        }
+    }
+
+    pub fn parse(value: &str) -> Result<PortPos, String> {
+        let value = value.trim();
+        match value.rsplit_once(':') {
+            Some((port, compass)) if !port.is_empty() => {
+                Ok(PortPos::port(port).with_compass(Compass::parse(compass)?))
+            }
+            None if !value.is_empty() => Ok(Compass::parse(value)
+                .map(PortPos::from)
+                .unwrap_or_else(|_| PortPos::port(value))),
+            _ => Err(format!("the port position '{}' is invalid", value)),
+        }
+    }
+}
+
+impl Display for PortPos {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match (&self.port, &self.compass) {
+            (Some(port), Some(compass)) => write!(f, "{}:{}", port, compass),
+            (Some(port), None) => f.write_str(port),
+            (None, Some(compass)) => write!(f, "{}", compass),
+            (None, None) => Ok(()),
+        }
+    }
+}
+
+impl From<Compass> for PortPos {
+    fn from(value: Compass) -> Self {
+        PortPos {
+            port: None,
+            compass: Some(value),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::attributes::values::*;
+
+    #[test]
+    fn point_test() {
+        assert_eq!(Point::parse("1,2.5").unwrap(), Point::new(1., 2.5));
+        assert_eq!(
+            Point::parse("1,2,3!").unwrap(),
+            Point::new_3d(1., 2., 3.).fixed()
+        );
+        assert_eq!(Point::new(7.5, 10.).fixed().to_string(), "7.5,10!");
+        assert!(Point::parse("1").is_err());
+        assert!(Point::parse("1,a").is_err());
+
+        assert_eq!(
+            PointOrDouble::parse("0.5").unwrap(),
+            PointOrDouble::Double(0.5)
+        );
+        assert_eq!(
+            PointOrDouble::parse("7!").unwrap(),
+            PointOrDouble::Point(Point::new(7., 7.).fixed())
+        );
+        assert_eq!(
+            AddPoint::parse("+4,2").unwrap(),
+            AddPoint::add(Point::new(4., 2.))
+        );
+        assert_eq!(AddPoint::from(0.5).to_string(), "0.5");
+
+        assert_eq!(
+            Rect::parse("0,0,54,108").unwrap(),
+            Rect::new(0., 0., 54., 108.)
+        );
+        assert!(Rect::parse("0,0,54").is_err());
+        assert_eq!(PointList::parse("0,0 1,1").unwrap().to_string(), "0,0 1,1");
+        let rects = RectList::parse("0,0,10,20 10,0,20,20").unwrap();
+        assert_eq!(rects.0[1], Rect::new(10., 0., 20., 20.));
+        assert_eq!(rects.to_string(), "0,0,10,20 10,0,20,20");
+    }
+
+    #[test]
+    fn spline_type_test() {
+        let value = "e,27,36.1 27,71.7 27,63.9 27,54.7 27,46.1;s,1,1 1,2 1,3 1,4 1,5";
+        let splines = SplineType::parse(value).unwrap();
+        assert_eq!(splines.0.len(), 2);
+        assert_eq!(splines.0[0].end, Some(Point::new(27., 36.1)));
+        assert_eq!(splines.0[1].start, Some(Point::new(1., 1.)));
+        assert_eq!(splines.0[1].points.len(), 4);
+        assert_eq!(splines.to_string(), value);
+        assert!(SplineType::parse("1,2 3,4").is_err());
+
+        assert_eq!(
+            Pos::parse("1,2!").unwrap(),
+            Pos::Point(Point::new(1., 2.).fixed())
+        );
+        assert!(matches!(Pos::parse(value).unwrap(), Pos::Spline(_)));
+    }
+
+    #[test]
+    fn keywords_test() {
+        assert_eq!(Splines::parse("ortho").unwrap(), Splines::Ortho);
+        assert_eq!(Splines::parse("true").unwrap(), Splines::Spline);
+        assert_eq!(Splines::from(false).to_string(), "line");
+        assert_eq!(Splines::None.to_string(), "none");
+
+        assert_eq!(Ratio::parse("fill").unwrap(), Ratio::Fill);
+        assert_eq!(Ratio::parse("0.7").unwrap(), Ratio::Value(0.7));
+        assert!(Ratio::parse("x").is_err());
+
+        assert_eq!(
+            Overlap::parse("prism1000").unwrap(),
+            Overlap::Prism(Some(1000))
+        );
+        assert_eq!(Overlap::parse("scalexy").unwrap(), Overlap::ScaleXY);
+        assert_eq!(Overlap::Prism(Some(5)).to_string(), "prism5");
+        assert!(Overlap::parse("prismx").is_err());
+
+        assert_eq!(Overlap::parse("no").unwrap(), Overlap::False);
+
+        assert_eq!(Pack::parse("8").unwrap(), Pack::Margin(8));
+        assert_eq!(Pack::parse("yes").unwrap(), Pack::Enabled(true));
+        assert_eq!(FixedSize::parse("shape").unwrap(), FixedSize::Shape);
+        assert_eq!(FixedSize::parse("Yes").unwrap(), FixedSize::True);
+        assert!(FixedSize::parse("label").is_err());
+        assert_eq!(ImageScale::from(true).to_string(), "true");
+        assert_eq!(ImageScale::parse("no").unwrap(), ImageScale::False);
+        assert_eq!(Normalize::parse("30").unwrap(), Normalize::Angle(30.));
+        assert_eq!(Normalize::parse("yes").unwrap(), Normalize::Enabled(true));
+
+        assert_eq!(Orientation::parse("45").unwrap(), Orientation::Angle(45.));
+        assert_eq!(Orientation::parse("Land").unwrap(), Orientation::Landscape);
+        assert_eq!(Orientation::parse("").unwrap(), Orientation::Portrait);
+
+        assert_eq!(PortPos::parse("ne").unwrap(), PortPos::from(Compass::NE));
+        assert_eq!(PortPos::parse("center").unwrap(), PortPos::port("center"));
+        assert_eq!(
+            PortPos::parse("f0:_").unwrap(),
+            PortPos::port("f0").with_compass(Compass::Any)
+        );
+        assert_eq!(
+            PortPos::port("f0").with_compass(Compass::S).to_string(),
+            "f0:s"
+        );
+        assert!(PortPos::parse("f0:up").is_err());
+        assert!(PortPos::parse(":n").is_err());
+    }
+
+    #[test]
+    fn lists_test() {
+        let range = LayerRange::parse("a:c, all").unwrap();
+        assert_eq!(
+            range,
+            LayerRange(vec![
+                Layer::Range("a".to_string(), "c".to_string()),
+                Layer::Id("all".to_string())
+            ])
+        );
+        assert_eq!(range.to_string(), "a:c,all");
+        assert!(LayerRange::parse("a,").is_err());
+
+        assert_eq!(LayerList::parse("a:b c").unwrap().to_string(), "a:b:c");
+
+        let vp = ViewPort::parse("100,200,2,'node 1'").unwrap();
+        assert_eq!(vp.center, Some(ViewPortCenter::Node("node 1".to_string())));
+        assert_eq!(vp.to_string(), "100,200,2,'node 1'");
+        let vp = ViewPort::parse("100,200,1,50,60").unwrap();
+        assert_eq!(vp.center, Some(ViewPortCenter::Point(50., 60.)));
+        assert_eq!(ViewPort::parse("100,200").unwrap().zoom, None);
+
+        let sep = RankSep::parse("0.5:1 equally").unwrap();
+        assert_eq!(sep.values, vec![0.5, 1.]);
+        assert!(sep.equally);
+        assert_eq!(sep.to_string(), "0.5:1 equally");
+        assert_eq!(RankSep::parse("equally").unwrap().to_string(), "equally");
+    }
+}
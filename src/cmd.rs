@@ -14,7 +14,7 @@
 //! use dot_generator::*;
 //! use graphviz_rust::attributes::*;
 //! use graphviz_rust::cmd::{CommandArg, Format};
-//! use graphviz_rust::exec;
+//! use graphviz_rust::{exec, exec_formats};
 //! use graphviz_rust::printer::{PrinterContext,DotPrinter};
 //!
 //! fn graph_to_output(){
@@ -42,25 +42,48 @@
 //!           CommandArg::Output("1.svg".to_string())
 //!       ]);
 //! }
+//!
+//! fn graph_to_several_formats(){
+//!        let mut g = graph!(id!("id"));
+//!        let outputs = exec_formats(g, &mut PrinterContext::default(), vec![
+//!           Format::Svg,
+//!           Format::Png,
+//!       ], vec![]).unwrap();
+//!        let png = &outputs[&Format::Png];
+//! }
 //! ```
 //!
 //! [`dot` command line executable]: https://graphviz.org/doc/info/command.html
 use std::{
+    cell::RefCell,
+    cmp::Ordering,
+    collections::HashMap,
+    fmt::{Display, Formatter},
+    fs,
     io::{self, ErrorKind, Write},
-    process::{Command, Output},
+    path::{Path, PathBuf},
+    process::{Command, Output, Stdio},
+    sync::OnceLock,
 };
 
+use dot_structures::{Attribute, Id};
+
+use crate::attributes::{EdgeAttrs, GraphAttrs, NodeAttrs};
 use tempfile::NamedTempFile;
 
 pub(crate) fn exec(graph: String, args: Vec<CommandArg>) -> io::Result<Vec<u8>> {
-    let args = args.into_iter().map(|a| a.prepare()).collect();
+    for arg in args.iter() {
+        arg.validate()
+            .map_err(|e| io::Error::new(ErrorKind::InvalidInput, e))?;
+    }
+    let args = args.iter().flat_map(CommandArg::prepare).collect();
     temp_file(graph).and_then(|f| {
         let path = f.into_temp_path();
         do_exec(path.to_string_lossy().to_string(), args).and_then(|o| {
             if o.status.code().map(|c| c != 0).unwrap_or(true) {
                 let mes = String::from_utf8_lossy(&o.stderr).to_string();
                 path.close()?;
-                Err(std::io::Error::new(ErrorKind::Other, mes))
+                Err(io::Error::other(mes))
             } else {
                 path.close()?;
                 Ok(o.stdout)
@@ -69,6 +92,197 @@ pub(crate) fn exec(graph: String, args: Vec<CommandArg>) -> io::Result<Vec<u8>>
     })
 }
 
+/// Runs the executable once with a pair of `-T` and `-o` arguments for every format
+/// so that the layout is computed only one time for all outputs.
+pub(crate) fn exec_formats<E: Executor + ?Sized>(
+    executor: &E,
+    graph: String,
+    formats: Vec<Format>,
+    args: Vec<CommandArg>,
+) -> io::Result<HashMap<Format, Vec<u8>>> {
+    if formats.is_empty() {
+        return Err(io::Error::new(
+            ErrorKind::InvalidInput,
+            "at least one format is expected",
+        ));
+    }
+    check_no_outputs(&args)?;
+    let dir = tempfile::tempdir()?;
+    let outputs = format_outputs(dir.path(), formats);
+    let mut args = args;
+    for (f, p) in outputs.iter() {
+        args.push(CommandArg::Format(*f));
+        args.push(CommandArg::Output(p.to_string_lossy().to_string()));
+    }
+    executor.exec(graph, args)?;
+    let res = outputs
+        .into_iter()
+        .map(|(f, p)| fs::read(p).map(|bytes| (f, bytes)))
+        .collect();
+    dir.close()?;
+    res
+}
+
+/// Rejects the arguments choosing the output format or the output file
+/// for the calls generating them on their own.
+pub(crate) fn check_no_outputs(args: &[CommandArg]) -> io::Result<()> {
+    match args.iter().find(|a| {
+        matches!(
+            a,
+            CommandArg::Format(_)
+                | CommandArg::OutputFormat(_)
+                | CommandArg::Output(_)
+                | CommandArg::AutoOutput
+        )
+    }) {
+        Some(a) => Err(io::Error::new(
+            ErrorKind::InvalidInput,
+            format!(
+                "the argument '{}' conflicts with the generated output arguments",
+                a.prepare().join(" ")
+            ),
+        )),
+        None => Ok(()),
+    }
+}
+
+/// The backend running the graphs.
+///
+/// [DotExecutor] spawns the `dot` process and [MockExecutor] records the calls
+/// and returns the predefined output so that the code rendering the graphs
+/// can be tested without the installed graphviz.
+///
+/// # Example:
+/// ```rust
+/// use dot_generator::*;
+/// use dot_structures::*;
+/// use graphviz_rust::{
+///     cmd::{CommandArg, Format, MockExecutor},
+///     exec_with,
+///     printer::PrinterContext,
+/// };
+///
+/// let mock = MockExecutor::new(b"<svg/>".to_vec());
+/// let g = graph!(di id!("id"); edge!(node_id!("a") => node_id!("b")));
+/// let out = exec_with(&mock, g, &mut PrinterContext::default(), vec![Format::Svg.into()]).unwrap();
+///
+/// assert_eq!(out, b"<svg/>".to_vec());
+/// let calls = mock.calls();
+/// assert_eq!(calls[0].graph, "digraph id {\n  a -> b\n}");
+/// assert_eq!(calls[0].args, vec![CommandArg::Format(Format::Svg)]);
+/// ```
+pub trait Executor {
+    /// Runs the given graph in the dot notation and returns the standard output.
+    fn exec(&self, graph: String, args: Vec<CommandArg>) -> io::Result<Vec<u8>>;
+}
+
+/// Spawns the [`dot` command line executable](https://graphviz.org/doc/info/command.html).
+#[derive(Debug, Default, Clone, Copy)]
+pub struct DotExecutor;
+
+impl Executor for DotExecutor {
+    fn exec(&self, graph: String, args: Vec<CommandArg>) -> io::Result<Vec<u8>> {
+        exec(graph, args)
+    }
+}
+
+/// A call recorded by [MockExecutor].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExecCall {
+    pub graph: String,
+    pub args: Vec<CommandArg>,
+}
+
+/// The executor recording the calls and returning the predefined output instead of running `dot`.
+///
+/// The output for the specific formats can be set with [MockExecutor::with_output].
+/// If the arguments contain [CommandArg::Output], the output of the preceding format
+/// is written to the given path like the real executable does.
+#[derive(Debug, Default)]
+pub struct MockExecutor {
+    output: Vec<u8>,
+    outputs: HashMap<Format, Vec<u8>>,
+    error: Option<String>,
+    calls: RefCell<Vec<ExecCall>>,
+}
+
+impl MockExecutor {
+    /// Creates the executor returning the given output for any format.
+    pub fn new(output: Vec<u8>) -> Self {
+        MockExecutor {
+            output,
+            ..Default::default()
+        }
+    }
+    /// Sets the output returning for the given format.
+    pub fn with_output(mut self, format: Format, output: Vec<u8>) -> Self {
+        self.outputs.insert(format, output);
+        self
+    }
+    /// Makes every call fail with the given message as if `dot` reported an error.
+    pub fn with_error(mut self, message: &str) -> Self {
+        self.error = Some(message.to_string());
+        self
+    }
+    /// Returns the recorded calls.
+    pub fn calls(&self) -> Vec<ExecCall> {
+        self.calls.borrow().clone()
+    }
+
+    fn output(&self, format: Option<Format>) -> Vec<u8> {
+        format
+            .and_then(|f| self.outputs.get(&f))
+            .unwrap_or(&self.output)
+            .clone()
+    }
+}
+
+impl Executor for MockExecutor {
+    fn exec(&self, graph: String, args: Vec<CommandArg>) -> io::Result<Vec<u8>> {
+        for arg in args.iter() {
+            arg.validate()
+                .map_err(|e| io::Error::new(ErrorKind::InvalidInput, e))?;
+        }
+        self.calls.borrow_mut().push(ExecCall {
+            graph,
+            args: args.clone(),
+        });
+        if let Some(e) = &self.error {
+            return Err(io::Error::other(e.clone()));
+        }
+
+        let mut format = None;
+        let mut to_stdout = true;
+        for arg in args {
+            match arg {
+                CommandArg::Format(f) => format = Some(f),
+                CommandArg::OutputFormat(f) => format = Some(f.format),
+                CommandArg::Output(p) => {
+                    fs::write(p, self.output(format))?;
+                    to_stdout = false;
+                }
+                _ => (),
+            }
+        }
+        if to_stdout {
+            Ok(self.output(format))
+        } else {
+            Ok(vec![])
+        }
+    }
+}
+
+fn format_outputs(dir: &Path, formats: Vec<Format>) -> Vec<(Format, PathBuf)> {
+    let mut outputs: Vec<(Format, PathBuf)> = vec![];
+    for f in formats {
+        if outputs.iter().all(|(e, _)| *e != f) {
+            let p = dir.join(format!("output_{}", outputs.len()));
+            outputs.push((f, p));
+        }
+    }
+    outputs
+}
+
 fn do_exec(input: String, args: Vec<String>) -> io::Result<Output> {
     let mut command = Command::new("dot");
 
@@ -87,6 +301,7 @@ fn temp_file(ctx: String) -> io::Result<NamedTempFile> {
 ///
 /// The list of possible commands can be found here:
 /// <https://graphviz.org/doc/info/command.html>.
+#[derive(Debug, Clone, PartialEq)]
 pub enum CommandArg {
     /// any custom argument.
     ///
@@ -103,6 +318,43 @@ pub enum CommandArg {
     ///
     /// [`Output`]:https://graphviz.org/docs/outputs/
     Format(Format),
+    /// [`Output`] format with the explicit renderer and formatter, e.g. `-Tpng:cairo:gd`.
+    ///
+    /// [`Output`]:https://graphviz.org/docs/outputs/
+    OutputFormat(OutputFormat),
+    /// Sets the default graph attributes with -G prefix, e.g. `-Gdpi=300`, one per attribute.
+    ///
+    /// The set accepts only the graph attributes, the unknown ones can be added
+    /// with [`Attrs::with_custom`](crate::attributes::Attrs::with_custom).
+    GraphAttr(GraphAttrs),
+    /// Sets the default node attributes with -N prefix, e.g. `-Nshape=box`, one per attribute.
+    ///
+    /// The set accepts only the node attributes, the unknown ones can be added
+    /// with [`Attrs::with_custom`](crate::attributes::Attrs::with_custom).
+    NodeAttr(NodeAttrs),
+    /// Sets the default edge attributes with -E prefix, e.g. `-Earrowhead=dot`, one per attribute.
+    ///
+    /// The set accepts only the edge attributes, the unknown ones can be added
+    /// with [`Attrs::with_custom`](crate::attributes::Attrs::with_custom).
+    EdgeAttr(EdgeAttrs),
+    /// Scales the input by the given factor with -s prefix. The value should be positive.
+    Scale(f32),
+    /// Inverts the y coordinate in the output with -y
+    InvertY,
+    /// Uses the given file as a device-dependent library with -l prefix
+    Library(String),
+    /// Suppresses the warning messages with -q
+    Quiet,
+    /// Sets the verbose mode with -v
+    Verbose,
+    /// Uses the input positions without layout (neato only) with -n prefix.
+    ///
+    /// The optional level can be 1 or 2. See <https://graphviz.org/doc/info/command.html#-n>.
+    NoLayout(Option<u8>),
+    /// Prunes isolated nodes and peninsulas (neato only) with -x
+    Reduce,
+    /// Generates the output file names automatically with -O
+    AutoOutput,
 }
 
 impl From<Layout> for CommandArg {
@@ -117,34 +369,95 @@ impl From<Format> for CommandArg {
     }
 }
 
+impl From<OutputFormat> for CommandArg {
+    fn from(value: OutputFormat) -> Self {
+        CommandArg::OutputFormat(value)
+    }
+}
+
 impl CommandArg {
-    fn prepare(&self) -> String {
+    /// Checks the argument before passing it to the executable.
+    pub fn validate(&self) -> Result<(), String> {
         match self {
+            CommandArg::Custom(s) if s.is_empty() => {
+                Err("the custom argument is empty".to_string())
+            }
+            CommandArg::Output(p) if p.is_empty() => Err("the output path is empty".to_string()),
+            CommandArg::Library(p) if p.is_empty() => Err("the library path is empty".to_string()),
+            CommandArg::GraphAttr(a) => check_attr_names(a.attributes()),
+            CommandArg::NodeAttr(a) => check_attr_names(a.attributes()),
+            CommandArg::EdgeAttr(a) => check_attr_names(a.attributes()),
+            CommandArg::Scale(s) if !s.is_finite() || *s <= 0.0 => {
+                Err(format!("the scale should be a positive number, got {}", s))
+            }
+            CommandArg::NoLayout(Some(l)) if *l != 1 && *l != 2 => {
+                Err(format!("the level of -n should be 1 or 2, got {}", l))
+            }
+            CommandArg::OutputFormat(f) => f.validate(),
+            _ => Ok(()),
+        }
+    }
+
+    /// Returns the command line arguments, the attribute sets give one argument per attribute.
+    fn prepare(&self) -> Vec<String> {
+        let arg = match self {
+            CommandArg::GraphAttr(a) => return attr_args("-G", a.attributes()),
+            CommandArg::NodeAttr(a) => return attr_args("-N", a.attributes()),
+            CommandArg::EdgeAttr(a) => return attr_args("-E", a.attributes()),
             CommandArg::Custom(s) => s.clone(),
+            CommandArg::Scale(s) => format!("-s{}", s),
+            CommandArg::InvertY => "-y".to_string(),
+            CommandArg::Library(p) => format!("-l{}", p),
+            CommandArg::Quiet => "-q".to_string(),
+            CommandArg::Verbose => "-v".to_string(),
+            CommandArg::NoLayout(None) => "-n".to_string(),
+            CommandArg::NoLayout(Some(l)) => format!("-n{}", l),
+            CommandArg::Reduce => "-x".to_string(),
+            CommandArg::AutoOutput => "-O".to_string(),
             CommandArg::Output(p) => format!("-o{}", p),
-            CommandArg::Layout(l) => format!("-K{}", format!("{:?}", l).to_lowercase()),
-            CommandArg::Format(f) => {
-                let str = match f {
-                    Format::Xdot12 => "xdot1.2".to_string(),
-                    Format::Xdot14 => "xdot1.4".to_string(),
-                    Format::ImapNp => "imap_np".to_string(),
-                    Format::CmapxNp => "cmapx_np".to_string(),
-                    Format::DotJson => "dot_json".to_string(),
-                    Format::XdotJson => "xdot_json".to_string(),
-                    Format::PlainExt => "plain-ext".to_string(),
-                    _ => format!("{:?}", f).to_lowercase(),
-                };
-                format!("-T{}", str)
-            }
+            CommandArg::Layout(l) => format!("-K{}", l),
+            CommandArg::Format(f) => format!("-T{}", f),
+            CommandArg::OutputFormat(f) => format!("-T{}", f),
+        };
+        vec![arg]
+    }
+}
+
+/// The command line passes the values as they are, thus the quotes of the escaped ids are removed.
+fn attr_part(id: &Id) -> String {
+    match id {
+        Id::Escaped(v) => v
+            .strip_prefix('"')
+            .and_then(|v| v.strip_suffix('"'))
+            .unwrap_or(v)
+            .to_string(),
+        Id::Html(v) | Id::Plain(v) => v.clone(),
+        Id::Anonymous(_) => "".to_string(),
+    }
+}
+
+fn attr_args(prefix: &str, attrs: &[Attribute]) -> Vec<String> {
+    attrs
+        .iter()
+        .map(|a| format!("{}{}={}", prefix, attr_part(&a.0), attr_part(&a.1)))
+        .collect()
+}
+
+fn check_attr_names(attrs: &[Attribute]) -> Result<(), String> {
+    for a in attrs.iter() {
+        let key = attr_part(&a.0);
+        if key.is_empty() || key.contains('=') || key.contains(char::is_whitespace) {
+            return Err(format!("the attribute name '{}' is invalid", key));
         }
     }
+    Ok(())
 }
 
 /// Various algorithms for projecting abstract graphs into a space for
 /// visualization
 ///
 /// <https://graphviz.org/docs/layouts/>
-#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 pub enum Layout {
     Dot,
     Neato,
@@ -160,7 +473,7 @@ pub enum Layout {
 /// applications.
 ///
 /// <https://graphviz.org/docs/outputs/>
-#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 pub enum Format {
     Bmp,
     Cgimage,
@@ -206,6 +519,7 @@ pub enum Format {
     Sgi,
     Svg,
     Svgz,
+    SvgInline,
     Tga,
     Tif,
     Tiff,
@@ -219,6 +533,385 @@ pub enum Format {
     X11,
 }
 
+impl Display for Layout {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Layout::Asage => f.write_str("osage"),
+            _ => f.write_str(format!("{:?}", self).to_lowercase().as_str()),
+        }
+    }
+}
+
+impl Display for Format {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Format::Xdot12 => f.write_str("xdot1.2"),
+            Format::Xdot14 => f.write_str("xdot1.4"),
+            Format::ImapNp => f.write_str("imap_np"),
+            Format::CmapxNp => f.write_str("cmapx_np"),
+            Format::DotJson => f.write_str("dot_json"),
+            Format::XdotJson => f.write_str("xdot_json"),
+            Format::PlainExt => f.write_str("plain-ext"),
+            Format::SvgInline => f.write_str("svg_inline"),
+            _ => f.write_str(format!("{:?}", self).to_lowercase().as_str()),
+        }
+    }
+}
+
+impl Format {
+    /// Selects the renderer for the format, e.g. `cairo` in `-Tpng:cairo`.
+    pub fn renderer(self, renderer: &str) -> OutputFormat {
+        OutputFormat {
+            format: self,
+            renderer: Some(renderer.to_string()),
+            formatter: None,
+        }
+    }
+}
+
+/// The [Format] with the optional renderer and formatter: `-Tformat[:renderer[:formatter]]`.
+///
+/// The available combinations can be received with [renderers].
+///
+/// # Example:
+/// ```rust
+/// use graphviz_rust::cmd::{Format, OutputFormat};
+///
+/// let f: OutputFormat = Format::Png.renderer("cairo").formatter("gd");
+/// assert_eq!(f.to_string(), "png:cairo:gd");
+/// assert_eq!(OutputFormat::from(Format::Svg).to_string(), "svg");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct OutputFormat {
+    pub format: Format,
+    pub renderer: Option<String>,
+    pub formatter: Option<String>,
+}
+
+impl OutputFormat {
+    /// Selects the formatter for the renderer, e.g. `gd` in `-Tpng:cairo:gd`.
+    pub fn formatter(mut self, formatter: &str) -> Self {
+        self.formatter = Some(formatter.to_string());
+        self
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        let invalid = |v: &Option<String>| {
+            v.as_ref()
+                .map(|v| v.is_empty() || v.contains(':'))
+                .unwrap_or(false)
+        };
+        if self.renderer.is_none() && self.formatter.is_some() {
+            Err(format!(
+                "the formatter of '{}' requires a renderer",
+                self.format
+            ))
+        } else if invalid(&self.renderer) || invalid(&self.formatter) {
+            Err(format!("the output format '{}' is invalid", self))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl From<Format> for OutputFormat {
+    fn from(format: Format) -> Self {
+        OutputFormat {
+            format,
+            renderer: None,
+            formatter: None,
+        }
+    }
+}
+
+impl Display for OutputFormat {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.format)?;
+        if let Some(r) = &self.renderer {
+            write!(f, ":{}", r)?;
+        }
+        if let Some(fm) = &self.formatter {
+            write!(f, ":{}", fm)?;
+        }
+        Ok(())
+    }
+}
+
+/// The formats, renderers and layouts supported by the installed executable.
+///
+/// It is received by querying `dot -T?`, `dot -Tformat:?` for every format and `dot -K?`
+/// once per process, see [capabilities].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Capabilities {
+    pub formats: Vec<String>,
+    pub layouts: Vec<String>,
+    /// the renderers and formatters of the formats, e.g. `png:cairo:gd`
+    pub renderers: Vec<String>,
+}
+
+impl Capabilities {
+    pub fn supports_format(&self, format: &Format) -> bool {
+        self.formats.contains(&format.to_string())
+    }
+    pub fn supports_layout(&self, layout: &Layout) -> bool {
+        self.layouts.contains(&layout.to_string())
+    }
+    /// Checks the format and, if given, the renderer and the formatter.
+    pub fn supports_output_format(&self, format: &OutputFormat) -> bool {
+        if !self.supports_format(&format.format) {
+            return false;
+        }
+        if format.renderer.is_none() {
+            return true;
+        }
+        let name = format.to_string();
+        let prefix = format!("{}:", name);
+        self.renderers
+            .iter()
+            .any(|r| *r == name || r.starts_with(&prefix))
+    }
+
+    /// Checks that all formats and layouts in the arguments are supported.
+    pub fn check(&self, args: &[CommandArg]) -> Result<(), String> {
+        for arg in args {
+            match arg {
+                CommandArg::Format(f) if !self.supports_format(f) => {
+                    return Err(format!(
+                        "the format '{}' is not supported by the installed graphviz. Supported formats: {}",
+                        f,
+                        self.formats.join(" ")
+                    ))
+                }
+                CommandArg::OutputFormat(f) if !self.supports_format(&f.format) => {
+                    return Err(format!(
+                        "the format '{}' is not supported by the installed graphviz. Supported formats: {}",
+                        f.format,
+                        self.formats.join(" ")
+                    ))
+                }
+                CommandArg::OutputFormat(f) if !self.supports_output_format(f) => {
+                    let prefix = format!("{}:", f.format);
+                    let available: Vec<&str> = self
+                        .renderers
+                        .iter()
+                        .filter(|r| r.starts_with(&prefix))
+                        .map(|r| r.as_str())
+                        .collect();
+                    return Err(format!(
+                        "the output format '{}' is not supported by the installed graphviz. Supported renderers: {}",
+                        f,
+                        available.join(" ")
+                    ));
+                }
+                CommandArg::Layout(l) if !self.supports_layout(l) => {
+                    return Err(format!(
+                        "the layout '{}' is not supported by the installed graphviz. Supported layouts: {}",
+                        l,
+                        self.layouts.join(" ")
+                    ))
+                }
+                _ => (),
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The version of the installed executable, e.g. `2.43.0 (0)` or `9.0.0 (20230911.1827)`.
+///
+/// The versions are compared and ordered by the major, minor and patch numbers only,
+/// the pre-release and the build info are ignored, e.g. `2.50.0~dev (0)` equals `2.50.0`.
+///
+/// # Example:
+/// ```rust
+/// use graphviz_rust::cmd::{Format, Version};
+///
+/// let v = Version::parse("dot - graphviz version 2.43.0 (0)").unwrap();
+/// assert_eq!(v, Version::new(2, 43, 0));
+/// assert!(v.supports_format(&Format::XdotJson));
+/// assert!(!v.supports_format(&Format::SvgInline));
+/// assert!(v < Version::new(10, 0, 1));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Version {
+    pub major: u32,
+    pub minor: u32,
+    pub patch: u32,
+    /// the pre-release part following `~`, e.g. `dev.20211204.0838`
+    pub pre: Option<String>,
+    /// the build info in the brackets, e.g. `20230911.1827`
+    pub build: Option<String>,
+}
+
+impl Version {
+    pub fn new(major: u32, minor: u32, patch: u32) -> Self {
+        Version {
+            major,
+            minor,
+            patch,
+            pre: None,
+            build: None,
+        }
+    }
+
+    /// Parses the output of `dot -V`.
+    /// The prefix `dot - graphviz version` is optional.
+    pub fn parse(output: &str) -> Result<Version, String> {
+        let output = output.trim();
+        let version = output
+            .split_once("version")
+            .map(|(_, v)| v)
+            .unwrap_or(output)
+            .trim();
+        let (number, build) = match version.split_once(char::is_whitespace) {
+            Some((n, b)) => (n, Some(b.trim())),
+            None => (version, None),
+        };
+        let (number, pre) = match number.split_once('~') {
+            Some((n, p)) => (n, Some(p.to_string())),
+            None => (number, None),
+        };
+        let mut parts = number.split('.').map(|p| p.parse::<u32>());
+        let mut next = || match parts.next() {
+            Some(Ok(n)) => Ok(n),
+            Some(Err(_)) => Err(format!("the version '{}' is unrecognizable", output)),
+            None => Ok(0),
+        };
+        let (major, minor, patch) = (next()?, next()?, next()?);
+        let build = build
+            .map(|b| b.trim_start_matches('(').trim_end_matches(')').to_string())
+            .filter(|b| !b.is_empty());
+        Ok(Version {
+            major,
+            minor,
+            patch,
+            pre,
+            build,
+        })
+    }
+
+    /// Checks whether the version is equal or greater than the given one.
+    pub fn at_least(&self, major: u32, minor: u32, patch: u32) -> bool {
+        *self >= Version::new(major, minor, patch)
+    }
+
+    /// Checks whether the format is available since this version.
+    ///
+    /// _Note_: the formats depending on the plugins (e.g. `webp` or `gtk`) still can be missing.
+    /// Use [capabilities] to receive the formats of the installed executable.
+    pub fn supports_format(&self, format: &Format) -> bool {
+        match format {
+            Format::Json | Format::Json0 | Format::DotJson | Format::XdotJson => {
+                self.at_least(2, 40, 0)
+            }
+            Format::Xdot14 => self.at_least(2, 32, 0),
+            Format::SvgInline => self.at_least(10, 0, 1),
+            _ => true,
+        }
+    }
+}
+
+impl PartialEq for Version {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Version {}
+
+impl PartialOrd for Version {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Version {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.major, self.minor, self.patch).cmp(&(other.major, other.minor, other.patch))
+    }
+}
+
+impl Display for Version {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)?;
+        if let Some(p) = &self.pre {
+            write!(f, "~{}", p)?;
+        }
+        if let Some(b) = &self.build {
+            write!(f, " ({})", b)?;
+        }
+        Ok(())
+    }
+}
+
+/// Receives the version of the installed executable with `dot -V`.
+pub fn version() -> io::Result<Version> {
+    let output = Command::new("dot")
+        .arg("-V")
+        .stdin(Stdio::null())
+        .output()?;
+    let version = String::from_utf8_lossy(&output.stderr);
+    Version::parse(&version).map_err(io::Error::other)
+}
+
+/// Queries the formats, renderers and layouts supported by the installed executable.
+///
+/// Since that takes a process per format, the result is cached
+/// and the executable is queried only until the first successful call.
+pub fn capabilities() -> io::Result<Capabilities> {
+    static CAPABILITIES: OnceLock<Capabilities> = OnceLock::new();
+    if let Some(capabilities) = CAPABILITIES.get() {
+        return Ok(capabilities.clone());
+    }
+    let formats = query_options("-T?")?;
+    let renderers = formats
+        .iter()
+        .flat_map(|f| query_options(format!("-T{}:?", f).as_str()).unwrap_or_default())
+        .collect();
+    let capabilities = Capabilities {
+        formats,
+        layouts: query_options("-K?")?,
+        renderers,
+    };
+    Ok(CAPABILITIES.get_or_init(|| capabilities).clone())
+}
+
+/// Queries the renderers and formatters available for the given format,
+/// e.g. `png:cairo:cairo png:cairo:gd png:gd:gd` for [Format::Png].
+pub fn renderers(format: Format) -> io::Result<Vec<OutputFormat>> {
+    Ok(query_options(format!("-T{}:?", format).as_str())?
+        .into_iter()
+        .filter_map(|o| parse_output_format(format, o.as_str()))
+        .collect())
+}
+
+fn parse_output_format(format: Format, value: &str) -> Option<OutputFormat> {
+    let mut parts = value.split(':');
+    if parts.next()? != format.to_string() {
+        return None;
+    }
+    Some(OutputFormat {
+        format,
+        renderer: parts.next().map(|r| r.to_string()),
+        formatter: parts.next().map(|f| f.to_string()),
+    })
+}
+
+fn query_options(arg: &str) -> io::Result<Vec<String>> {
+    let output = Command::new("dot").arg(arg).stdin(Stdio::null()).output()?;
+    let err = String::from_utf8_lossy(&output.stderr);
+    parse_options(&err)
+        .ok_or_else(|| io::Error::other(format!("unexpected output for dot {}: {}", arg, err)))
+}
+
+/// Takes the list after `Use one of:` from the error message, e.g.
+/// `Format: "?" not recognized. Use one of: bmp canon cmap ...`
+fn parse_options(output: &str) -> Option<Vec<String>> {
+    output
+        .split_once("Use one of:")
+        .map(|(_, list)| list.split_whitespace().map(|o| o.to_string()).collect())
+}
+
 #[cfg(test)]
 mod tests {
     use dot_generator::*;
@@ -226,7 +919,13 @@ mod tests {
 
     use crate::printer::{DotPrinter, PrinterContext};
 
-    use super::{exec, CommandArg, Format};
+    use super::{
+        exec, exec_formats, format_outputs, parse_options, parse_output_format, Capabilities,
+        CommandArg, ExecCall, Format, Layout, MockExecutor, OutputFormat, Version,
+    };
+    use crate::attributes::{
+        color, color_name, dpi, fontname, shape, EdgeAttrs, GraphAttrs, NodeAttrs,
+    };
 
     #[test]
     fn error_test() {
@@ -242,4 +941,249 @@ mod tests {
         );
         assert!(empty.is_err())
     }
+
+    #[test]
+    fn format_outputs_test() {
+        let dir = std::env::temp_dir();
+        let outputs = format_outputs(&dir, vec![Format::Svg, Format::Png, Format::Svg]);
+        assert_eq!(
+            outputs,
+            vec![
+                (Format::Svg, dir.join("output_0")),
+                (Format::Png, dir.join("output_1")),
+            ]
+        );
+    }
+
+    #[test]
+    fn empty_formats_test() {
+        let res = exec_formats(
+            &MockExecutor::default(),
+            "graph id {}".to_string(),
+            vec![],
+            vec![],
+        );
+        assert!(res.is_err())
+    }
+
+    #[test]
+    fn prepare_test() {
+        assert_eq!(
+            CommandArg::GraphAttr(GraphAttrs::new().with(dpi(300.))).prepare(),
+            vec!["-Gdpi=300"]
+        );
+        assert_eq!(
+            CommandArg::NodeAttr(NodeAttrs::new().with(shape::box_)).prepare(),
+            vec!["-Nshape=box"]
+        );
+        let attrs = EdgeAttrs::new()
+            .with(fontname("Helvetica Neue".to_string()))
+            .with_custom(attr!("label", esc "a b"));
+        assert_eq!(
+            CommandArg::EdgeAttr(attrs).prepare(),
+            vec!["-Efontname=Helvetica Neue", "-Elabel=a b"]
+        );
+        assert_eq!(CommandArg::Scale(72.).prepare(), vec!["-s72"]);
+        assert_eq!(CommandArg::InvertY.prepare(), vec!["-y"]);
+        assert_eq!(
+            CommandArg::Library("lib.ps".to_string()).prepare(),
+            vec!["-llib.ps"]
+        );
+        assert_eq!(CommandArg::Quiet.prepare(), vec!["-q"]);
+        assert_eq!(CommandArg::Verbose.prepare(), vec!["-v"]);
+        assert_eq!(CommandArg::NoLayout(None).prepare(), vec!["-n"]);
+        assert_eq!(CommandArg::NoLayout(Some(2)).prepare(), vec!["-n2"]);
+        assert_eq!(CommandArg::Reduce.prepare(), vec!["-x"]);
+        assert_eq!(CommandArg::AutoOutput.prepare(), vec!["-O"]);
+    }
+
+    #[test]
+    fn validate_test() {
+        assert!(
+            CommandArg::NodeAttr(NodeAttrs::new().with(color(color_name::red.into())))
+                .validate()
+                .is_ok()
+        );
+        assert!(
+            CommandArg::NodeAttr(NodeAttrs::new().with_custom(attr!("", "red")))
+                .validate()
+                .is_err()
+        );
+        assert!(
+            CommandArg::GraphAttr(GraphAttrs::new().with_custom(attr!("a=b", "red")))
+                .validate()
+                .is_err()
+        );
+        assert!(CommandArg::Scale(0.).validate().is_err());
+        assert!(CommandArg::Scale(f32::NAN).validate().is_err());
+        assert!(CommandArg::NoLayout(Some(3)).validate().is_err());
+        assert!(CommandArg::Library("".to_string()).validate().is_err());
+
+        let res = exec("graph id {}".to_string(), vec![CommandArg::Scale(-1.)]);
+        assert_eq!(res.unwrap_err().kind(), std::io::ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn output_format_test() {
+        assert_eq!(
+            CommandArg::Format(Format::PlainExt).prepare(),
+            vec!["-Tplain-ext"]
+        );
+        assert_eq!(CommandArg::Layout(Layout::Asage).prepare(), vec!["-Kosage"]);
+        assert_eq!(
+            CommandArg::from(Format::Png.renderer("cairo").formatter("gd")).prepare(),
+            vec!["-Tpng:cairo:gd"]
+        );
+        assert_eq!(
+            CommandArg::from(Format::Svg.renderer("core")).prepare(),
+            vec!["-Tsvg:core"]
+        );
+        assert!(
+            CommandArg::from(OutputFormat::from(Format::Png).formatter("gd"))
+                .validate()
+                .is_err()
+        );
+        assert!(CommandArg::from(Format::Png.renderer("cairo:gd"))
+            .validate()
+            .is_err());
+    }
+
+    #[test]
+    fn capabilities_test() {
+        let formats = parse_options(
+            "Format: \"?\" not recognized. Use one of: bmp canon cmap dot json png svg xdot1.2\n",
+        )
+        .unwrap();
+        let layouts =
+            parse_options("Layout: \"?\" not recognized. Use one of: circo dot fdp neato osage\n")
+                .unwrap();
+        assert_eq!(parse_options("dot - graphviz version 2.43.0"), None);
+
+        let renderers = parse_options(
+            "Format: \"png:?\" not recognized. Use one of: png:cairo:cairo png:gd:gd\n",
+        )
+        .unwrap();
+        let caps = Capabilities {
+            formats,
+            layouts,
+            renderers,
+        };
+        assert!(caps.supports_format(&Format::Xdot12));
+        assert!(!caps.supports_format(&Format::Webp));
+        assert!(caps.supports_layout(&Layout::Asage));
+        assert!(caps
+            .check(&[Format::Svg.into(), Layout::Neato.into()])
+            .is_ok());
+        assert!(caps.check(&[Format::Webp.into()]).is_err());
+        assert!(caps.check(&[Layout::Sfdp.into()]).is_err());
+        assert!(caps
+            .check(&[CommandArg::OutputFormat(Format::Png.renderer("cairo"))])
+            .is_ok());
+        assert!(caps
+            .check(&[CommandArg::OutputFormat(
+                Format::Png.renderer("gd").formatter("gd")
+            )])
+            .is_ok());
+        assert!(caps
+            .check(&[CommandArg::OutputFormat(Format::Png.into())])
+            .is_ok());
+        assert_eq!(
+            caps.check(&[CommandArg::OutputFormat(
+                Format::Png.renderer("cairo").formatter("gd")
+            )])
+            .unwrap_err(),
+            "the output format 'png:cairo:gd' is not supported by the installed graphviz. Supported renderers: png:cairo:cairo png:gd:gd"
+        );
+        assert!(!caps.supports_output_format(&Format::Svg.renderer("cairo")));
+
+        assert_eq!(
+            parse_output_format(Format::Png, "png:cairo:gd"),
+            Some(Format::Png.renderer("cairo").formatter("gd"))
+        );
+        assert_eq!(parse_output_format(Format::Png, "svg:core"), None);
+    }
+
+    #[test]
+    fn version_test() {
+        let v = Version::parse("dot - graphviz version 2.43.0 (0)\n").unwrap();
+        assert_eq!((v.major, v.minor, v.patch), (2, 43, 0));
+        assert_eq!(v.build, Some("0".to_string()));
+        assert_eq!(v.to_string(), "2.43.0 (0)");
+
+        let v = Version::parse("dot - graphviz version 9.0.0 (20230911.1827)").unwrap();
+        assert_eq!(v, Version::new(9, 0, 0));
+        assert_eq!(v.build, Some("20230911.1827".to_string()));
+        assert!(v.at_least(2, 40, 0));
+        assert!(!v.at_least(9, 0, 1));
+        assert!(v.supports_format(&Format::XdotJson));
+        assert!(!v.supports_format(&Format::SvgInline));
+
+        let v = Version::parse("dot - graphviz version 2.50.0~dev.20211204.0838 (20211204.0838)")
+            .unwrap();
+        assert_eq!(v, Version::new(2, 50, 0));
+        assert_eq!(v.pre, Some("dev.20211204.0838".to_string()));
+
+        let v = Version::parse("12.1").unwrap();
+        assert_eq!(v, Version::new(12, 1, 0));
+        assert!(v.supports_format(&Format::SvgInline));
+
+        assert!(Version::parse("dot - graphviz version unknown").is_err());
+        assert!(!Version::new(2, 38, 0).supports_format(&Format::Json));
+
+        let mut versions = vec![
+            Version::new(10, 0, 1),
+            Version::new(2, 43, 0),
+            Version::new(2, 9, 5),
+        ];
+        versions.sort();
+        assert_eq!(
+            versions,
+            vec![
+                Version::new(2, 9, 5),
+                Version::new(2, 43, 0),
+                Version::new(10, 0, 1)
+            ]
+        );
+        assert!(Version::parse("2.50.0~dev").unwrap() <= Version::new(2, 50, 0));
+    }
+
+    #[test]
+    fn mock_executor_test() {
+        let mock = MockExecutor::new(b"digraph".to_vec())
+            .with_output(Format::Svg, b"<svg/>".to_vec())
+            .with_output(Format::Png, b"png".to_vec());
+
+        let out = exec_formats(
+            &mock,
+            "digraph id {}".to_string(),
+            vec![Format::Svg, Format::Png, Format::Json],
+            vec![CommandArg::Layout(Layout::Neato)],
+        )
+        .unwrap();
+        assert_eq!(out[&Format::Svg], b"<svg/>".to_vec());
+        assert_eq!(out[&Format::Png], b"png".to_vec());
+        assert_eq!(out[&Format::Json], b"digraph".to_vec());
+
+        let calls = mock.calls();
+        assert_eq!(calls.len(), 1);
+        let ExecCall { graph, args } = &calls[0];
+        assert_eq!(graph, "digraph id {}");
+        assert_eq!(args.len(), 7);
+        assert_eq!(args[0], CommandArg::Layout(Layout::Neato));
+        assert_eq!(args[1], CommandArg::Format(Format::Svg));
+
+        for arg in [
+            CommandArg::Format(Format::Pdf),
+            CommandArg::Output("a.svg".to_string()),
+            CommandArg::AutoOutput,
+        ] {
+            let mock = MockExecutor::default();
+            assert!(exec_formats(&mock, "".to_string(), vec![Format::Svg], vec![arg]).is_err());
+            assert!(mock.calls().is_empty());
+        }
+
+        let err = MockExecutor::default().with_error("syntax error");
+        assert!(exec_formats(&err, "".to_string(), vec![Format::Svg], vec![]).is_err());
+        assert_eq!(err.calls().len(), 1);
+    }
 }
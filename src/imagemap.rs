@@ -0,0 +1,303 @@
+//! Client-side image maps from the [`cmapx`] output.
+//!
+//! The `-Tcmapx` and `-Tcmapx_np` formats produce the html `<map>` element with the `<area>`
+//! for every node, edge and cluster having the `href` or `URL` attribute.
+//! The map is parsed with [parse] and combined with the png image with [html]
+//! producing the html fragment of the clickable image.
+//! The image and the map can be rendered at once with [exec_image_map](crate::exec_image_map).
+//!
+//! # Example:
+//! ```rust
+//! use graphviz_rust::imagemap::{parse, AreaShape};
+//!
+//! let cmapx = r#"<map id="G" name="G">
+//! <area shape="poly" id="node1" href="https://a.org" title="a" alt="" coords="54,18 52,25 27,36"/>
+//! <area shape="rect" id="node2" href="https://b.org" title="b&amp;c" alt="" coords="0,0,54,36"/>
+//! </map>"#;
+//! let map = parse(cmapx.as_bytes()).unwrap();
+//! assert_eq!(map.name, "G");
+//! assert_eq!(map.areas[1].shape, AreaShape::Rect);
+//! assert_eq!(map.areas[1].title.as_deref(), Some("b&c"));
+//! assert_eq!(map.areas[1].coords, vec![0, 0, 54, 36]);
+//! ```
+//!
+//! [`cmapx`]: https://graphviz.org/docs/outputs/imap/
+use std::fmt::{Display, Formatter};
+
+use crate::svg::{attr_value, escape, unescape};
+
+/// The client-side image map.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ImageMap {
+    pub id: Option<String>,
+    pub name: String,
+    pub areas: Vec<Area>,
+}
+
+/// The clickable area of the image map.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Area {
+    pub shape: AreaShape,
+    /// the coordinates in pixels, their meaning depends on the shape
+    pub coords: Vec<i32>,
+    pub href: Option<String>,
+    pub title: Option<String>,
+    pub alt: Option<String>,
+    pub id: Option<String>,
+    pub target: Option<String>,
+}
+
+/// The shape of the area.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AreaShape {
+    /// `x1,y1,x2,y2` of the corners
+    Rect,
+    /// `x,y,r` of the center and the radius
+    Circle,
+    /// `x1,y1,x2,y2,...` of the vertices
+    Poly,
+    /// the whole image
+    Default,
+}
+
+impl AreaShape {
+    pub fn parse(value: &str) -> Result<AreaShape, String> {
+        match value.to_lowercase().as_str() {
+            "rect" | "rectangle" => Ok(AreaShape::Rect),
+            "circle" | "circ" => Ok(AreaShape::Circle),
+            "poly" | "polygon" => Ok(AreaShape::Poly),
+            "default" => Ok(AreaShape::Default),
+            _ => Err(format!("the area shape '{}' is unknown", value)),
+        }
+    }
+}
+
+impl Display for AreaShape {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AreaShape::Rect => f.write_str("rect"),
+            AreaShape::Circle => f.write_str("circle"),
+            AreaShape::Poly => f.write_str("poly"),
+            AreaShape::Default => f.write_str("default"),
+        }
+    }
+}
+
+impl Display for Area {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "<area shape=\"{}\"", self.shape)?;
+        let attrs = [
+            ("id", &self.id),
+            ("href", &self.href),
+            ("target", &self.target),
+            ("title", &self.title),
+            ("alt", &self.alt),
+        ];
+        for (name, value) in attrs {
+            if let Some(v) = value {
+                write!(f, " {}=\"{}\"", name, escape(v))?;
+            }
+        }
+        if !self.coords.is_empty() {
+            let coords: Vec<String> = self.coords.iter().map(|c| c.to_string()).collect();
+            write!(f, " coords=\"{}\"", coords.join(","))?;
+        }
+        f.write_str("/>")
+    }
+}
+
+impl Display for ImageMap {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str("<map")?;
+        if let Some(id) = &self.id {
+            write!(f, " id=\"{}\"", escape(id))?;
+        }
+        writeln!(f, " name=\"{}\">", escape(&self.name))?;
+        for area in self.areas.iter() {
+            writeln!(f, "{}", area)?;
+        }
+        f.write_str("</map>")
+    }
+}
+
+/// Parses the output of `-Tcmapx` or `-Tcmapx_np` into the [ImageMap].
+pub fn parse(output: &[u8]) -> Result<ImageMap, String> {
+    let output = std::str::from_utf8(output).map_err(|e| e.to_string())?;
+    let map_tag = tag(output, "<map").ok_or("the map element is not found")?;
+    let name = attr(map_tag, "name")
+        .or_else(|| attr(map_tag, "id"))
+        .ok_or("the map element has no name")?;
+    let mut map = ImageMap {
+        id: attr(map_tag, "id"),
+        name,
+        areas: vec![],
+    };
+    let mut rest = output;
+    while let Some(start) = rest.find("<area") {
+        rest = &rest[start..];
+        let area_tag = tag(rest, "<area").ok_or("the area element is not closed")?;
+        map.areas.push(parse_area(area_tag)?);
+        rest = &rest[area_tag.len()..];
+    }
+    Ok(map)
+}
+
+fn parse_area(tag: &str) -> Result<Area, String> {
+    let shape = AreaShape::parse(attr(tag, "shape").as_deref().unwrap_or("rect"))?;
+    let coords = attr(tag, "coords")
+        .map(|v| {
+            v.split(|c: char| c == ',' || c.is_whitespace())
+                .filter(|c| !c.is_empty())
+                .map(|c| {
+                    c.parse::<f64>()
+                        .map(|c| c.round() as i32)
+                        .map_err(|_| format!("the coordinate '{}' is not a number", c))
+                })
+                .collect::<Result<Vec<_>, _>>()
+        })
+        .transpose()?
+        .unwrap_or_default();
+    let expected = match shape {
+        AreaShape::Rect => coords.len() == 4,
+        AreaShape::Circle => coords.len() == 3,
+        AreaShape::Poly => coords.len() >= 6 && coords.len() % 2 == 0,
+        AreaShape::Default => true,
+    };
+    if !expected {
+        return Err(format!(
+            "the area '{}' has unexpected number of coordinates {}",
+            shape,
+            coords.len()
+        ));
+    }
+    Ok(Area {
+        shape,
+        coords,
+        href: attr(tag, "href"),
+        title: attr(tag, "title"),
+        alt: attr(tag, "alt"),
+        id: attr(tag, "id"),
+        target: attr(tag, "target"),
+    })
+}
+
+/// Returns the tag starting with the given prefix up to the closing `>`.
+fn tag<'a>(text: &'a str, prefix: &str) -> Option<&'a str> {
+    let start = text.find(prefix)?;
+    let end = text[start..].find('>')?;
+    Some(&text[start..start + end + 1])
+}
+
+fn attr(tag: &str, name: &str) -> Option<String> {
+    attr_value(tag, name).map(unescape)
+}
+
+/// Returns the html fragment with the png image embedded as the data url and the image map.
+///
+/// The image refers to the map with the `usemap` attribute.
+pub fn html(png: &[u8], map: &ImageMap) -> String {
+    format!(
+        "<img src=\"data:image/png;base64,{}\" usemap=\"#{}\" alt=\"{}\"/>\n{}\n",
+        base64(png),
+        escape(&map.name),
+        escape(&map.name),
+        map
+    )
+}
+
+fn base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut result = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let b = [
+            chunk[0],
+            chunk.get(1).copied().unwrap_or(0),
+            chunk.get(2).copied().unwrap_or(0),
+        ];
+        let n = (b[0] as u32) << 16 | (b[1] as u32) << 8 | b[2] as u32;
+        for i in 0..4 {
+            if i <= chunk.len() {
+                result.push(ALPHABET[(n >> (18 - 6 * i) & 0x3f) as usize] as char);
+            } else {
+                result.push('=');
+            }
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::imagemap::{base64, html, parse, Area, AreaShape, ImageMap};
+
+    const CMAPX: &str = r#"<map id="G" name="G">
+<area shape="poly" id="node1" href="https://a.org?x=1&amp;y=2" title="a" alt="" coords="54,-18 52,-11 47,-5 40,-1"/>
+<area shape="rect" id="edge1" href="https://b.org" target="_blank" title="a&#45;&gt;b" alt="" coords="23,43,31,71"/>
+<area shape="circle" id="node2" href="https://c.org" title="c" alt="" coords="10,10,5"/>
+</map>
+"#;
+
+    #[test]
+    fn parse_test() {
+        let map = parse(CMAPX.as_bytes()).unwrap();
+        assert_eq!(map.id.as_deref(), Some("G"));
+        assert_eq!(map.name, "G");
+        assert_eq!(map.areas.len(), 3);
+        assert_eq!(
+            map.areas[0],
+            Area {
+                shape: AreaShape::Poly,
+                coords: vec![54, -18, 52, -11, 47, -5, 40, -1],
+                href: Some("https://a.org?x=1&y=2".to_string()),
+                title: Some("a".to_string()),
+                alt: Some("".to_string()),
+                id: Some("node1".to_string()),
+                target: None,
+            }
+        );
+        assert_eq!(map.areas[1].title.as_deref(), Some("a->b"));
+        assert_eq!(map.areas[1].target.as_deref(), Some("_blank"));
+        assert_eq!(map.areas[2].shape, AreaShape::Circle);
+    }
+
+    #[test]
+    fn print_test() {
+        let map = parse(CMAPX.as_bytes()).unwrap();
+        let printed = map.to_string();
+        assert!(printed.starts_with("<map id=\"G\" name=\"G\">\n"));
+        assert!(printed.contains(
+            r#"<area shape="rect" id="edge1" href="https://b.org" target="_blank" title="a-&gt;b" alt="" coords="23,43,31,71"/>"#
+        ));
+        assert_eq!(parse(printed.as_bytes()).unwrap(), map);
+    }
+
+    #[test]
+    fn html_test() {
+        let map = ImageMap {
+            id: None,
+            name: "G".to_string(),
+            areas: vec![],
+        };
+        assert_eq!(
+            html(b"png", &map),
+            "<img src=\"data:image/png;base64,cG5n\" usemap=\"#G\" alt=\"G\"/>\n<map name=\"G\">\n</map>\n"
+        );
+    }
+
+    #[test]
+    fn base64_test() {
+        assert_eq!(base64(b""), "");
+        assert_eq!(base64(b"f"), "Zg==");
+        assert_eq!(base64(b"fo"), "Zm8=");
+        assert_eq!(base64(b"foo"), "Zm9v");
+        assert_eq!(base64(b"foobar"), "Zm9vYmFy");
+    }
+
+    #[test]
+    fn error_test() {
+        assert!(parse(b"<area shape=\"rect\" coords=\"1,2,3,4\"/>").is_err());
+        assert!(parse(b"<map name=\"G\"><area shape=\"rect\" coords=\"1,2,3\"/></map>").is_err());
+        assert!(parse(b"<map name=\"G\"><area shape=\"star\"/></map>").is_err());
+        assert!(parse(b"<map name=\"G\"><area shape=\"circle\" coords=\"a,b,c\"/></map>").is_err());
+    }
+}
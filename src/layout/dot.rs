@@ -0,0 +1,318 @@
+//! The layout received from the [`dot`] and [`xdot`] outputs.
+//!
+//! Graphviz returns the input graph in the dot notation annotated with the layout attributes
+//! like `pos`, `bb`, `width`, `height`, `lp`, and for `xdot` also with the drawing operations.
+//! The output is parsed with [crate::parse] and the attributes are decoded into [GraphLayout],
+//! the drawing operations are decoded with [xdot::parse_ops](crate::layout::xdot::parse_ops).
+//!
+//! # Example:
+//! ```rust
+//! use graphviz_rust::layout::{dot::parse, Point};
+//!
+//! let output = r#"
+//! digraph {
+//!     graph [bb="0,0,54,108"];
+//!     node [label="\N"];
+//!     a [height=0.5, pos="27,90", width=0.75,
+//!        _draw_="c 7 -#000000 e 27 90 27 18 "];
+//!     b [height=0.5, pos="27,18", width=0.75];
+//!     a -> b [pos="e,27,36.104 27,71.697 27,63.983 27,54.712 27,46.112"];
+//! }
+//! "#;
+//! let layout = parse(output.as_bytes()).unwrap();
+//! assert_eq!(layout.node("a").unwrap().pos, Some(Point::new(27.0, 90.0)));
+//! assert_eq!(layout.node("a").unwrap().draw.shape.len(), 2);
+//! assert_eq!(layout.edges_between("a", "b").count(), 1);
+//! ```
+//!
+//! [`dot`]: https://graphviz.org/docs/outputs/canon/
+//! [`xdot`]: https://graphviz.org/docs/outputs/canon/#xdot
+use std::collections::HashMap;
+
+use dot_structures::{Graph, Port};
+
+use crate::{
+    layout::{
+        xdot::parse_ops, Draw, EdgeLayout, GraphLayout, NodeLayout, Point, Rect, Spline,
+        SubgraphLayout,
+    },
+    model::{id_str, AttributeMap, EdgeModel, GraphModel, NodeModel, SourceKind, SubgraphModel},
+};
+
+/// Parses the output of `-Tdot`, `-Tgv`, `-Tcanon` or `-Txdot` into the [GraphLayout].
+pub fn parse(output: &[u8]) -> Result<GraphLayout, String> {
+    let output = std::str::from_utf8(output).map_err(|e| e.to_string())?;
+    from_graph(&crate::parse(output)?)
+}
+
+/// Builds the [GraphLayout] from the graph annotated with the layout attributes.
+///
+/// The elements are resolved with the [GraphModel] so the node and edge defaults
+/// are applied to the elements in their scope.
+pub fn from_graph(graph: &Graph) -> Result<GraphLayout, String> {
+    let model = GraphModel::new(graph);
+
+    let mut draw = Draw::default();
+    let mut attrs = Attrs::new(attrs(&model.attributes, false), &mut draw)?;
+    let mut layout = GraphLayout {
+        name: id_str(&model.id),
+        directed: model.directed,
+        strict: model.strict,
+        bb: attrs.take("bb", Rect::parse)?,
+        label_pos: attrs.take("lp", Point::parse)?,
+        draw,
+        ..Default::default()
+    };
+    layout.attributes = attrs.rest;
+    layout.nodes = model
+        .nodes
+        .iter()
+        .map(process_node)
+        .collect::<Result<_, _>>()?;
+    layout.edges = model
+        .edges
+        .iter()
+        .map(process_edge)
+        .collect::<Result<_, _>>()?;
+    layout.subgraphs = model
+        .subgraphs
+        .iter()
+        .map(process_subgraph)
+        .collect::<Result<_, _>>()?;
+    Ok(layout)
+}
+
+fn process_node(node: &NodeModel) -> Result<NodeLayout, String> {
+    let mut draw = Draw::default();
+    let mut attrs = Attrs::new(attrs(&node.attributes, false), &mut draw)?;
+    Ok(NodeLayout {
+        name: node.name(),
+        pos: attrs.take("pos", Point::parse)?,
+        width: attrs.take("width", parse_f64)?,
+        height: attrs.take("height", parse_f64)?,
+        xlabel_pos: attrs.take("xlp", Point::parse)?,
+        attributes: attrs.rest,
+        draw,
+    })
+}
+
+fn process_edge(edge: &EdgeModel) -> Result<EdgeLayout, String> {
+    let mut attributes = attrs(&edge.attributes, false);
+    // the ports of the ends override the defaults but not the explicit attributes
+    let ports = [("tailport", &edge.tail_port), ("headport", &edge.head_port)];
+    for (key, port) in ports {
+        let explicit = edge
+            .attributes
+            .source(key)
+            .is_some_and(|s| s.kind == SourceKind::Explicit);
+        if let (Some(port), false) = (port, explicit) {
+            attributes.insert(key.to_string(), port_str(port));
+        }
+    }
+    let mut draw = Draw::default();
+    let mut attrs = Attrs::new(attributes, &mut draw)?;
+    Ok(EdgeLayout {
+        tail: edge.tail,
+        head: edge.head,
+        splines: attrs.take("pos", Spline::parse_all)?.unwrap_or_default(),
+        label_pos: attrs.take("lp", Point::parse)?,
+        xlabel_pos: attrs.take("xlp", Point::parse)?,
+        head_label_pos: attrs.take("head_lp", Point::parse)?,
+        tail_label_pos: attrs.take("tail_lp", Point::parse)?,
+        attributes: attrs.rest,
+        draw,
+    })
+}
+
+/// The subgraph keeps only its own attributes since the layout attributes
+/// like `bb` and `_draw_` of the parent do not describe the subgraph.
+fn process_subgraph(sub: &SubgraphModel) -> Result<SubgraphLayout, String> {
+    let mut draw = Draw::default();
+    let mut attrs = Attrs::new(attrs(&sub.attributes, true), &mut draw)?;
+    Ok(SubgraphLayout {
+        name: sub.name(),
+        bb: attrs.take("bb", Rect::parse)?,
+        label_pos: attrs.take("lp", Point::parse)?,
+        nodes: sub.nodes.clone(),
+        edges: sub.edges.clone(),
+        subgraphs: sub.subgraphs.clone(),
+        attributes: attrs.rest,
+        draw,
+    })
+}
+
+fn port_str(Port(id, dir): &Port) -> String {
+    match (id, dir) {
+        (Some(id), Some(dir)) => format!("{}:{}", id_str(id), dir),
+        (Some(id), None) => id_str(id),
+        (None, Some(dir)) => dir.clone(),
+        (None, None) => String::new(),
+    }
+}
+
+/// The attributes of the element with the drawing operations taken out.
+struct Attrs {
+    rest: HashMap<String, String>,
+}
+
+impl Attrs {
+    fn new(attributes: HashMap<String, String>, draw: &mut Draw) -> Result<Attrs, String> {
+        let mut rest = HashMap::new();
+        for (k, v) in attributes {
+            match draw.ops_mut(k.as_str()) {
+                Some(ops) => *ops = parse_ops(v.as_str())?,
+                None => {
+                    rest.insert(k, v);
+                }
+            }
+        }
+        Ok(Attrs { rest })
+    }
+
+    fn take<T, F>(&mut self, key: &str, parse: F) -> Result<Option<T>, String>
+    where
+        F: Fn(&str) -> Result<T, String>,
+    {
+        self.rest.remove(key).map(|v| parse(v.as_str())).transpose()
+    }
+}
+
+fn parse_f64(value: &str) -> Result<f64, String> {
+    value
+        .trim()
+        .parse::<f64>()
+        .map_err(|_| format!("the value '{}' is not a number", value))
+}
+
+/// Returns the attributes without quotes skipping the inherited ones if `own` is set.
+fn attrs(attributes: &AttributeMap, own: bool) -> HashMap<String, String> {
+    attributes
+        .iter_with_sources()
+        .filter(|(_, s)| !own || !matches!(s, Some(s) if s.kind == SourceKind::Inherited))
+        .map(|(a, _)| (id_str(&a.0), id_str(&a.1)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::layout::{
+        dot::parse,
+        draw::{DrawOp, Paint},
+        Point, Rect,
+    };
+
+    const OUTPUT: &str = r#"digraph G {
+	graph [_draw_="c 9 -#fffffe00 C 7 -#ffffff P 4 0 0 0 152 134 152 134 0 ",
+		bb="0,0,134,152",
+		xdotversion=1.7
+	];
+	node [label="\N"];
+	subgraph cluster_0 {
+		graph [_draw_="c 7 -#000000 p 4 8 8 8 140 78 140 78 8 ",
+			_ldraw_="F 14 11 -Times-Roman c 7 -#000000 T 43 123.8 0 21 2 -c0 ",
+			bb="8,8,78,140",
+			label=c0,
+			lp="43,123.8"
+		];
+		a	[_draw_="c 7 -#000000 e 43 90 27 18 ",
+			_ldraw_="F 14 11 -Times-Roman c 7 -#000000 T 43 85.8 0 7 1 -a ",
+			height=0.5,
+			pos="43,90",
+			width=0.75];
+		b	[height=0.5,
+			pos="43,34",
+			width=0.75];
+		a -> b	[_draw_="c 7 -#000000 B 4 43 71.7 43 64 43 54.7 43 46.1 ",
+			_hdraw_="S 5 -solid c 7 -#000000 C 7 -#000000 P 3 46.5 46.1 43 36.1 39.5 46.1 ",
+			pos="e,43,36.104 43,71.697 43,63.983 43,54.712 43,46.112"];
+	}
+	{
+		rank=same;
+		c	[label="long \
+name",
+			pos="106,90"];
+	}
+	a:s -> c	[color=red];
+}
+"#;
+
+    #[test]
+    fn graph_test() {
+        let layout = parse(OUTPUT.as_bytes()).unwrap();
+        assert_eq!(layout.name, "G");
+        assert!(layout.directed);
+        assert_eq!(
+            layout.bb,
+            Some(Rect::new(Point::new(0., 0.), Point::new(134., 152.)))
+        );
+        assert_eq!(layout.draw.shape.len(), 3);
+        assert_eq!(layout.attributes.get("xdotversion").unwrap(), "1.7");
+        assert_eq!(layout.nodes.len(), 3);
+        assert_eq!(layout.edges.len(), 2);
+    }
+
+    #[test]
+    fn node_test() {
+        let layout = parse(OUTPUT.as_bytes()).unwrap();
+        let a = layout.node("a").unwrap();
+        assert_eq!(a.pos, Some(Point::new(43., 90.)));
+        assert_eq!(a.width, Some(0.75));
+        assert_eq!(a.attributes.get("label").unwrap(), "\\N");
+        assert_eq!(
+            a.draw.shape[0],
+            DrawOp::PenColor(Paint::Color("#000000".to_string()))
+        );
+        assert!(matches!(a.draw.label[2], DrawOp::Text { ref text, .. } if text == "a"));
+        let c = layout.node("c").unwrap();
+        assert_eq!(c.attributes.get("label").unwrap(), "long name");
+    }
+
+    #[test]
+    fn edge_test() {
+        let layout = parse(OUTPUT.as_bytes()).unwrap();
+        let ab = layout.edges_between("a", "b").next().unwrap();
+        assert_eq!(ab.splines[0].points.len(), 4);
+        assert_eq!(ab.draw.head.len(), 4);
+        let ac = layout.edges_between("a", "c").next().unwrap();
+        assert_eq!(ac.attributes.get("tailport").unwrap(), "s");
+        assert_eq!(ac.attributes.get("color").unwrap(), "red");
+    }
+
+    #[test]
+    fn subgraph_test() {
+        let layout = parse(OUTPUT.as_bytes()).unwrap();
+        assert_eq!(layout.subgraphs.len(), 2);
+        let cluster = layout.clusters().next().unwrap();
+        assert_eq!(cluster.name, "cluster_0");
+        assert_eq!(cluster.nodes, vec![0, 1]);
+        assert_eq!(cluster.edges, vec![0]);
+        assert_eq!(cluster.label_pos, Some(Point::new(43., 123.8)));
+        assert_eq!(cluster.draw.label.len(), 3);
+        let rank = &layout.subgraphs[1];
+        assert_eq!(rank.name, "");
+        assert_eq!(rank.nodes, vec![2]);
+        assert_eq!(rank.attributes.get("rank").unwrap(), "same");
+    }
+
+    #[test]
+    fn error_test() {
+        assert!(parse(b"digraph { a [pos=\"1\"] }").is_err());
+        assert!(parse(b"digraph { a [_draw_=\"X 1\"] }").is_err());
+        assert!(parse(b"digraph { a ").is_err());
+    }
+
+    #[test]
+    fn same_subgraph_test() {
+        let layout = parse(b"digraph { subgraph s { a } b -> subgraph s { c } }").unwrap();
+        assert_eq!(layout.subgraphs.len(), 1);
+        assert_eq!(layout.subgraphs[0].nodes, vec![0, 2]);
+
+        // the subgraphs with the same name are merged within the same parent only
+        let layout =
+            parse(b"digraph { subgraph p { subgraph s { a } } subgraph q { subgraph s { b } } }")
+                .unwrap();
+        assert_eq!(layout.subgraphs.len(), 4);
+        assert_eq!(layout.subgraphs[1].nodes, vec![0]);
+        assert_eq!(layout.subgraphs[3].nodes, vec![1]);
+    }
+}
@@ -0,0 +1,111 @@
+//! The drawing operations of the [`xdot`] format.
+//!
+//! The operations are provided in the `_draw_`, `_ldraw_`, `_hdraw_`, `_tdraw_`, `_hldraw_`
+//! and `_tldraw_` attributes and describe how to render the element.
+//! The color and style operations change the state for the subsequent operations.
+//!
+//! [`xdot`]: https://graphviz.org/docs/outputs/canon/#xdot
+use crate::layout::Point;
+
+/// A drawing operation.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DrawOp {
+    /// `E` (filled) or `e` (unfilled) ellipse with the given center and half-axes
+    Ellipse {
+        filled: bool,
+        center: Point,
+        width: f64,
+        height: f64,
+    },
+    /// `P` (filled) or `p` (unfilled) polygon
+    Polygon { filled: bool, points: Vec<Point> },
+    /// `L` polyline
+    Polyline { points: Vec<Point> },
+    /// `B` (unfilled) or `b` (filled) B-spline
+    BSpline { filled: bool, points: Vec<Point> },
+    /// `T` text where the `pos` is the baseline point and the `width` is the estimated width
+    Text {
+        pos: Point,
+        align: TextAlign,
+        width: f64,
+        text: String,
+    },
+    /// `c` the pen color
+    PenColor(Paint),
+    /// `C` the fill color
+    FillColor(Paint),
+    /// `F` the font
+    Font { size: f64, name: String },
+    /// `S` the style, e.g. `dashed` or `setlinewidth(2)`
+    Style(String),
+    /// `I` the image placed in the given rectangle
+    Image {
+        pos: Point,
+        width: f64,
+        height: f64,
+        name: String,
+    },
+    /// `t` the font characteristics, a bitwise or of [FontChar] flags
+    FontChar(u32),
+}
+
+/// The alignment of the text relative to the given point.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextAlign {
+    Left,
+    Center,
+    Right,
+}
+
+impl TextAlign {
+    /// Parses `-1`, `0`, `1` from xdot or `l`, `c`, `r` from json.
+    pub fn parse(value: &str) -> Result<TextAlign, String> {
+        match value {
+            "-1" | "l" => Ok(TextAlign::Left),
+            "0" | "c" => Ok(TextAlign::Center),
+            "1" | "r" => Ok(TextAlign::Right),
+            _ => Err(format!("the text alignment '{}' is unknown", value)),
+        }
+    }
+}
+
+/// The flags of the font characteristics for [DrawOp::FontChar].
+pub enum FontChar {}
+
+impl FontChar {
+    pub const BOLD: u32 = 1;
+    pub const ITALIC: u32 = 2;
+    pub const UNDERLINE: u32 = 4;
+    pub const SUPERSCRIPT: u32 = 8;
+    pub const SUBSCRIPT: u32 = 16;
+    pub const STRIKE_THROUGH: u32 = 32;
+    pub const OVERLINE: u32 = 64;
+}
+
+/// The color or the gradient used by the pen and fill operations.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Paint {
+    /// the color in the form `#rrggbb`, `#rrggbbaa` or a name
+    Color(String),
+    /// the linear gradient from `start` to `end`
+    LinearGradient {
+        start: Point,
+        end: Point,
+        stops: Vec<ColorStop>,
+    },
+    /// the radial gradient between the two circles
+    RadialGradient {
+        start: Point,
+        start_radius: f64,
+        end: Point,
+        end_radius: f64,
+        stops: Vec<ColorStop>,
+    },
+}
+
+/// A color at the given offset (from 0 to 1) of the gradient.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColorStop {
+    pub offset: f64,
+    pub color: String,
+}
@@ -0,0 +1,604 @@
+//! Parser for the [`json`] output: `-Tjson`, `-Txdot_json`, `-Tjson0` and `-Tdot_json`.
+//!
+//! The `json0` and `dot_json` formats do not contain the drawing operations
+//! and the `dot_json` format does not contain the layout at all.
+//!
+//! # Example:
+//! ```rust
+//! use graphviz_rust::layout::{json, Point};
+//!
+//! let output = r#"{
+//!   "name": "G", "directed": true, "strict": false, "bb": "0,0,54,108",
+//!   "_subgraph_cnt": 0,
+//!   "objects": [
+//!     {"_gvid": 0, "name": "a", "pos": "27,90", "width": "0.75", "height": "0.5"},
+//!     {"_gvid": 1, "name": "b", "pos": "27,18", "width": "0.75", "height": "0.5"}
+//!   ],
+//!   "edges": [
+//!     {"_gvid": 0, "tail": 0, "head": 1, "pos": "e,27,36.104 27,71.697 27,63.983 27,54.712 27,46.112"}
+//!   ]
+//! }"#;
+//! let layout = json::parse(output.as_bytes()).unwrap();
+//! assert_eq!(layout.node("b").unwrap().pos, Some(Point::new(27.0, 18.0)));
+//! assert_eq!(layout.edges[0].splines[0].points.len(), 4);
+//! ```
+//!
+//! [`json`]: https://graphviz.org/docs/outputs/json/
+use std::collections::HashMap;
+
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::layout::{
+    draw::{ColorStop, DrawOp, Paint, TextAlign},
+    Draw, EdgeLayout, GraphLayout, NodeLayout, Point, Rect, Spline, SubgraphLayout,
+};
+
+#[derive(Deserialize)]
+struct JsonGraph {
+    #[serde(default)]
+    name: String,
+    #[serde(default)]
+    directed: bool,
+    #[serde(default)]
+    strict: bool,
+    #[serde(rename = "_subgraph_cnt", default)]
+    subgraph_cnt: usize,
+    #[serde(default)]
+    objects: Vec<JsonObject>,
+    #[serde(default)]
+    edges: Vec<JsonEdge>,
+    #[serde(flatten)]
+    attributes: HashMap<String, Value>,
+}
+
+#[derive(Deserialize)]
+struct JsonObject {
+    #[serde(rename = "_gvid")]
+    gvid: usize,
+    #[serde(default)]
+    name: String,
+    #[serde(default)]
+    nodes: Vec<usize>,
+    #[serde(default)]
+    edges: Vec<usize>,
+    #[serde(default)]
+    subgraphs: Vec<usize>,
+    #[serde(flatten)]
+    attributes: HashMap<String, Value>,
+}
+
+#[derive(Deserialize)]
+struct JsonEdge {
+    #[serde(rename = "_gvid")]
+    gvid: usize,
+    tail: usize,
+    head: usize,
+    #[serde(flatten)]
+    attributes: HashMap<String, Value>,
+}
+
+#[derive(Deserialize)]
+struct JsonOp {
+    op: String,
+    grad: Option<String>,
+    color: Option<String>,
+    p0: Option<Vec<f64>>,
+    p1: Option<Vec<f64>>,
+    stops: Option<Vec<JsonStop>>,
+    rect: Option<[f64; 4]>,
+    points: Option<Vec<[f64; 2]>>,
+    pt: Option<[f64; 2]>,
+    align: Option<String>,
+    width: Option<f64>,
+    text: Option<String>,
+    size: Option<Value>,
+    face: Option<String>,
+    style: Option<String>,
+    fontchar: Option<u32>,
+    pos: Option<[f64; 2]>,
+    name: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct JsonStop {
+    frac: f64,
+    color: String,
+}
+
+/// Parses the json output into the [GraphLayout].
+pub fn parse(output: &[u8]) -> Result<GraphLayout, String> {
+    let graph: JsonGraph = serde_json::from_slice(output).map_err(|e| e.to_string())?;
+    process_graph(graph)
+}
+
+fn process_graph(graph: JsonGraph) -> Result<GraphLayout, String> {
+    let mut layout = GraphLayout {
+        name: graph.name,
+        directed: graph.directed,
+        strict: graph.strict,
+        ..Default::default()
+    };
+    let mut attrs = Attrs::new(graph.attributes, &mut layout.draw)?;
+    layout.bb = attrs.take("bb", Rect::parse)?;
+    layout.label_pos = attrs.take("lp", Point::parse)?;
+    layout.attributes = attrs.rest;
+
+    let mut objects = graph.objects;
+    let nodes = objects.split_off(graph.subgraph_cnt.min(objects.len()));
+    layout.subgraphs = objects
+        .into_iter()
+        .map(process_subgraph)
+        .collect::<Result<_, _>>()?;
+    layout.nodes = nodes
+        .into_iter()
+        .enumerate()
+        .map(|(idx, n)| {
+            if n.gvid == idx {
+                process_node(n)
+            } else {
+                Err(format!(
+                    "the node '{}' has unexpected _gvid {}",
+                    n.name, n.gvid
+                ))
+            }
+        })
+        .collect::<Result<_, _>>()?;
+
+    let mut edges = graph.edges;
+    edges.sort_by_key(|e| e.gvid);
+    layout.edges = edges
+        .into_iter()
+        .map(|e| process_edge(e, layout.nodes.len()))
+        .collect::<Result<_, _>>()?;
+    Ok(layout)
+}
+
+fn process_subgraph(obj: JsonObject) -> Result<SubgraphLayout, String> {
+    let mut draw = Draw::default();
+    let mut attrs = Attrs::new(obj.attributes, &mut draw)?;
+    let bb = attrs.take("bb", Rect::parse)?;
+    let label_pos = attrs.take("lp", Point::parse)?;
+    Ok(SubgraphLayout {
+        name: obj.name,
+        bb,
+        label_pos,
+        nodes: obj.nodes,
+        edges: obj.edges,
+        subgraphs: obj.subgraphs,
+        attributes: attrs.rest,
+        draw,
+    })
+}
+
+fn process_node(obj: JsonObject) -> Result<NodeLayout, String> {
+    let mut draw = Draw::default();
+    let mut attrs = Attrs::new(obj.attributes, &mut draw)?;
+    let pos = attrs.take("pos", Point::parse)?;
+    let width = attrs.take("width", parse_f64)?;
+    let height = attrs.take("height", parse_f64)?;
+    let xlabel_pos = attrs.take("xlp", Point::parse)?;
+    Ok(NodeLayout {
+        name: obj.name,
+        pos,
+        width,
+        height,
+        xlabel_pos,
+        attributes: attrs.rest,
+        draw,
+    })
+}
+
+fn process_edge(edge: JsonEdge, nodes: usize) -> Result<EdgeLayout, String> {
+    if edge.tail >= nodes || edge.head >= nodes {
+        return Err(format!(
+            "the edge {} refers to the missing node {} -> {}",
+            edge.gvid, edge.tail, edge.head
+        ));
+    }
+    let mut draw = Draw::default();
+    let mut attrs = Attrs::new(edge.attributes, &mut draw)?;
+    let splines = attrs.take("pos", Spline::parse_all)?.unwrap_or_default();
+    let label_pos = attrs.take("lp", Point::parse)?;
+    let xlabel_pos = attrs.take("xlp", Point::parse)?;
+    let head_label_pos = attrs.take("head_lp", Point::parse)?;
+    let tail_label_pos = attrs.take("tail_lp", Point::parse)?;
+    Ok(EdgeLayout {
+        tail: edge.tail,
+        head: edge.head,
+        splines,
+        label_pos,
+        xlabel_pos,
+        head_label_pos,
+        tail_label_pos,
+        attributes: attrs.rest,
+        draw,
+    })
+}
+
+/// The attributes of the element with the drawing operations taken out.
+struct Attrs {
+    rest: HashMap<String, String>,
+}
+
+impl Attrs {
+    fn new(attributes: HashMap<String, Value>, draw: &mut Draw) -> Result<Attrs, String> {
+        let mut rest = HashMap::new();
+        for (k, v) in attributes {
+            if let Some(ops) = draw.ops_mut(k.as_str()) {
+                *ops = process_ops(v)?;
+            } else {
+                match v {
+                    Value::String(s) => {
+                        rest.insert(k, s);
+                    }
+                    Value::Number(n) => {
+                        rest.insert(k, n.to_string());
+                    }
+                    Value::Bool(b) => {
+                        rest.insert(k, b.to_string());
+                    }
+                    _ => (),
+                }
+            }
+        }
+        Ok(Attrs { rest })
+    }
+
+    fn take<T, F>(&mut self, key: &str, parse: F) -> Result<Option<T>, String>
+    where
+        F: Fn(&str) -> Result<T, String>,
+    {
+        self.rest.remove(key).map(|v| parse(v.as_str())).transpose()
+    }
+}
+
+fn parse_f64(value: &str) -> Result<f64, String> {
+    value
+        .trim()
+        .parse::<f64>()
+        .map_err(|_| format!("the value '{}' is not a number", value))
+}
+
+fn process_ops(value: Value) -> Result<Vec<DrawOp>, String> {
+    let ops: Vec<JsonOp> = serde_json::from_value(value).map_err(|e| e.to_string())?;
+    ops.into_iter().map(process_op).collect()
+}
+
+fn point(p: [f64; 2]) -> Point {
+    Point::new(p[0], p[1])
+}
+
+fn missing(op: &str, field: &str) -> String {
+    format!("the field '{}' is missing in the operation '{}'", field, op)
+}
+
+fn process_op(op: JsonOp) -> Result<DrawOp, String> {
+    let name = op.op.as_str();
+    let points = |op: &JsonOp| {
+        op.points
+            .as_ref()
+            .map(|ps| ps.iter().map(|p| point(*p)).collect::<Vec<_>>())
+            .ok_or_else(|| missing(name, "points"))
+    };
+    match name {
+        "e" | "E" => {
+            let [x, y, w, h] = op.rect.ok_or_else(|| missing(name, "rect"))?;
+            Ok(DrawOp::Ellipse {
+                filled: name == "E",
+                center: Point::new(x, y),
+                width: w,
+                height: h,
+            })
+        }
+        "p" | "P" => Ok(DrawOp::Polygon {
+            filled: name == "P",
+            points: points(&op)?,
+        }),
+        "L" => Ok(DrawOp::Polyline {
+            points: points(&op)?,
+        }),
+        "b" | "B" => Ok(DrawOp::BSpline {
+            filled: name == "b",
+            points: points(&op)?,
+        }),
+        "T" => Ok(DrawOp::Text {
+            pos: point(op.pt.ok_or_else(|| missing(name, "pt"))?),
+            align: TextAlign::parse(op.align.as_deref().unwrap_or("c"))?,
+            width: op.width.unwrap_or_default(),
+            text: op.text.ok_or_else(|| missing(name, "text"))?,
+        }),
+        "c" => Ok(DrawOp::PenColor(process_paint(op)?)),
+        "C" => Ok(DrawOp::FillColor(process_paint(op)?)),
+        "F" => Ok(DrawOp::Font {
+            size: op
+                .size
+                .as_ref()
+                .and_then(|s| s.as_f64())
+                .ok_or_else(|| missing(name, "size"))?,
+            name: op.face.ok_or_else(|| missing(name, "face"))?,
+        }),
+        "S" => Ok(DrawOp::Style(
+            op.style.ok_or_else(|| missing(name, "style"))?,
+        )),
+        "t" => Ok(DrawOp::FontChar(
+            op.fontchar.ok_or_else(|| missing(name, "fontchar"))?,
+        )),
+        "I" => {
+            let size: [f64; 2] = op
+                .size
+                .and_then(|s| serde_json::from_value(s).ok())
+                .ok_or_else(|| missing(name, "size"))?;
+            Ok(DrawOp::Image {
+                pos: point(op.pos.ok_or_else(|| missing(name, "pos"))?),
+                width: size[0],
+                height: size[1],
+                name: op.name.ok_or_else(|| missing(name, "name"))?,
+            })
+        }
+        _ => Err(format!("the drawing operation '{}' is unknown", name)),
+    }
+}
+
+fn process_paint(op: JsonOp) -> Result<Paint, String> {
+    let stops = || {
+        op.stops
+            .as_ref()
+            .map(|ss| {
+                ss.iter()
+                    .map(|s| ColorStop {
+                        offset: s.frac,
+                        color: s.color.clone(),
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default()
+    };
+    let coords = |p: &Option<Vec<f64>>, field: &str| {
+        p.clone()
+            .filter(|p| p.len() >= 2)
+            .ok_or_else(|| missing(op.op.as_str(), field))
+    };
+    match op.grad.as_deref().unwrap_or("none") {
+        "none" => op
+            .color
+            .clone()
+            .map(Paint::Color)
+            .ok_or_else(|| missing(op.op.as_str(), "color")),
+        "linear" => {
+            let (p0, p1) = (coords(&op.p0, "p0")?, coords(&op.p1, "p1")?);
+            Ok(Paint::LinearGradient {
+                start: Point::new(p0[0], p0[1]),
+                end: Point::new(p1[0], p1[1]),
+                stops: stops(),
+            })
+        }
+        "radial" => {
+            let (p0, p1) = (coords(&op.p0, "p0")?, coords(&op.p1, "p1")?);
+            Ok(Paint::RadialGradient {
+                start: Point::new(p0[0], p0[1]),
+                start_radius: p0.get(2).copied().unwrap_or_default(),
+                end: Point::new(p1[0], p1[1]),
+                end_radius: p1.get(2).copied().unwrap_or_default(),
+                stops: stops(),
+            })
+        }
+        g => Err(format!("the gradient '{}' is unknown", g)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::layout::{
+        draw::{ColorStop, DrawOp, Paint, TextAlign},
+        json::parse,
+        Point, Rect,
+    };
+
+    const OUTPUT: &str = r##"{
+  "name": "G",
+  "directed": true,
+  "strict": false,
+  "_draw_": [
+    {"op": "c", "grad": "none", "color": "#fffffe00"},
+    {"op": "C", "grad": "none", "color": "#ffffff"},
+    {"op": "P", "points": [[0.000,0.000],[0.000,132.000],[78.000,132.000],[78.000,0.000]]}
+  ],
+  "bb": "0,0,78,132",
+  "xdotversion": "1.7",
+  "_subgraph_cnt": 1,
+  "objects": [
+    {
+      "_gvid": 0,
+      "name": "cluster_x",
+      "_draw_": [
+        {"op": "c", "grad": "none", "color": "#000000"},
+        {"op": "C", "grad": "linear", "color": "#ffffff", "p0": [8.0,8.0], "p1": [70.0,8.0],
+         "stops": [{"frac": 0.0, "color": "#ffffff"}, {"frac": 1.0, "color": "#0000ff"}]},
+        {"op": "p", "points": [[8.000,8.000],[8.000,124.000],[70.000,124.000],[70.000,8.000]]}
+      ],
+      "_ldraw_": [
+        {"op": "F", "size": 14.000, "face": "Times-Roman"},
+        {"op": "c", "grad": "none", "color": "#000000"},
+        {"op": "T", "pt": [39.000,108.800], "align": "c", "width": 7.000, "text": "x"}
+      ],
+      "bb": "8,8,70,124",
+      "label": "x",
+      "lp": "39,112.5",
+      "nodes": [0, 1],
+      "edges": [0]
+    },
+    {
+      "_gvid": 0,
+      "name": "a",
+      "_draw_": [
+        {"op": "c", "grad": "none", "color": "#000000"},
+        {"op": "e", "rect": [39.000,90.000,27.000,18.000]}
+      ],
+      "_ldraw_": [
+        {"op": "F", "size": 14.000, "face": "Times-Roman"},
+        {"op": "t", "fontchar": 1},
+        {"op": "T", "pt": [39.000,86.300], "align": "l", "width": 7.000, "text": "a"}
+      ],
+      "height": "0.5",
+      "label": "\\N",
+      "pos": "39,90",
+      "width": "0.75"
+    },
+    {
+      "_gvid": 1,
+      "name": "b",
+      "height": "0.5",
+      "pos": "39,34",
+      "width": "0.75"
+    }
+  ],
+  "edges": [
+    {
+      "_gvid": 0,
+      "tail": 0,
+      "head": 1,
+      "_draw_": [
+        {"op": "c", "grad": "none", "color": "#000000"},
+        {"op": "B", "points": [[39.000,71.700],[39.000,63.980],[39.000,54.710],[39.000,46.110]]}
+      ],
+      "_hdraw_": [
+        {"op": "S", "style": "solid"},
+        {"op": "C", "grad": "none", "color": "#000000"},
+        {"op": "P", "points": [[42.500,46.100],[39.000,36.100],[35.500,46.100]]}
+      ],
+      "lp": "42,54",
+      "pos": "e,39,52.104 39,71.697 39,63.983 39,54.712 39,46.112"
+    }
+  ]
+}"##;
+
+    #[test]
+    fn graph_test() {
+        let layout = parse(OUTPUT.as_bytes()).unwrap();
+        assert_eq!(layout.name, "G");
+        assert!(layout.directed);
+        assert_eq!(
+            layout.bb,
+            Some(Rect::new(Point::new(0., 0.), Point::new(78., 132.)))
+        );
+        assert_eq!(layout.attributes.get("xdotversion").unwrap(), "1.7");
+        assert_eq!(layout.draw.shape.len(), 3);
+        assert_eq!(
+            layout.draw.shape[1],
+            DrawOp::FillColor(Paint::Color("#ffffff".to_string()))
+        );
+    }
+
+    #[test]
+    fn node_test() {
+        let layout = parse(OUTPUT.as_bytes()).unwrap();
+        assert_eq!(layout.nodes.len(), 2);
+        let a = layout.node("a").unwrap();
+        assert_eq!(a.pos, Some(Point::new(39., 90.)));
+        assert_eq!(a.width, Some(0.75));
+        assert_eq!(a.height, Some(0.5));
+        assert_eq!(a.attributes.get("label").unwrap(), "\\N");
+        assert_eq!(
+            a.draw.shape[1],
+            DrawOp::Ellipse {
+                filled: false,
+                center: Point::new(39., 90.),
+                width: 27.,
+                height: 18.,
+            }
+        );
+        assert_eq!(
+            a.draw.label,
+            vec![
+                DrawOp::Font {
+                    size: 14.,
+                    name: "Times-Roman".to_string()
+                },
+                DrawOp::FontChar(1),
+                DrawOp::Text {
+                    pos: Point::new(39., 86.3),
+                    align: TextAlign::Left,
+                    width: 7.,
+                    text: "a".to_string(),
+                }
+            ]
+        );
+        assert!(layout.node("b").unwrap().draw.is_empty());
+    }
+
+    #[test]
+    fn edge_test() {
+        let layout = parse(OUTPUT.as_bytes()).unwrap();
+        let e = layout.edges_between("a", "b").next().unwrap();
+        assert_eq!(e.splines.len(), 1);
+        assert_eq!(e.splines[0].end, Some(Point::new(39., 52.104)));
+        assert_eq!(e.label_pos, Some(Point::new(42., 54.)));
+        assert_eq!(e.draw.head[0], DrawOp::Style("solid".to_string()));
+        assert_eq!(
+            e.draw.shape[1],
+            DrawOp::BSpline {
+                filled: false,
+                points: vec![
+                    Point::new(39., 71.7),
+                    Point::new(39., 63.98),
+                    Point::new(39., 54.71),
+                    Point::new(39., 46.11)
+                ]
+            }
+        );
+        assert_eq!(layout.edges_between("b", "a").count(), 0);
+    }
+
+    #[test]
+    fn cluster_test() {
+        let layout = parse(OUTPUT.as_bytes()).unwrap();
+        let clusters: Vec<_> = layout.clusters().collect();
+        assert_eq!(clusters.len(), 1);
+        let c = clusters[0];
+        assert_eq!(c.name, "cluster_x");
+        assert_eq!(c.nodes, vec![0, 1]);
+        assert_eq!(c.edges, vec![0]);
+        assert_eq!(c.label_pos, Some(Point::new(39., 112.5)));
+        assert_eq!(
+            c.draw.shape[1],
+            DrawOp::FillColor(Paint::LinearGradient {
+                start: Point::new(8., 8.),
+                end: Point::new(70., 8.),
+                stops: vec![
+                    ColorStop {
+                        offset: 0.,
+                        color: "#ffffff".to_string()
+                    },
+                    ColorStop {
+                        offset: 1.,
+                        color: "#0000ff".to_string()
+                    }
+                ]
+            })
+        );
+    }
+
+    #[test]
+    fn json0_test() {
+        let layout = parse(
+            br#"{"name": "G", "directed": false, "strict": false, "bb": "0,0,54,36",
+                 "objects": [{"_gvid": 0, "name": "a", "pos": "27,18"}]}"#,
+        )
+        .unwrap();
+        assert_eq!(layout.nodes[0].pos, Some(Point::new(27., 18.)));
+        assert!(layout.edges.is_empty());
+    }
+
+    #[test]
+    fn error_test() {
+        assert!(parse(b"{").is_err());
+        assert!(parse(br#"{"objects": [{"_gvid": 0, "name": "a", "pos": "x"}]}"#).is_err());
+        assert!(parse(br#"{"_draw_": [{"op": "X"}]}"#).is_err());
+        assert!(parse(br#"{"_draw_": [{"op": "e"}]}"#).is_err());
+        assert!(parse(
+            br#"{"objects": [{"_gvid": 0, "name": "a"}], "edges": [{"_gvid": 0, "tail": 0, "head": 3}]}"#
+        )
+        .is_err());
+    }
+}
@@ -0,0 +1,275 @@
+//! Attaches the layout computed by graphviz to the elements of the original graph.
+//!
+//! The `-Tdot` output is the input graph annotated with the layout attributes, but it is printed
+//! by graphviz and therefore differs from the original graph in the order of the statements
+//! and the anonymous subgraphs. The [merge] function matches the elements of the output
+//! with the elements of the original graph:
+//!  - nodes by the name
+//!  - edges by the names of the ends and the order of the edges between them
+//!  - named subgraphs by the name, anonymous subgraphs by the parent and the set of the nodes
+//!
+//! The layout can be received with [exec_layout](crate::exec_layout).
+//!
+//! # Example:
+//! ```rust
+//! use dot_generator::*;
+//! use dot_structures::*;
+//! use graphviz_rust::layout::{dot, merge::merge, Point};
+//!
+//! let g = graph!(di id!("g");
+//!     node!("a"; attr!("my_attr", "1")),
+//!     subgraph!(; stmt!(attr!("rank", "same")), node!("b")),
+//!     edge!(node_id!("a") => node_id!("b"))
+//! );
+//! let output = r#"
+//! digraph g {
+//!     graph [bb="0,0,54,108"];
+//!     node [label="\N"];
+//!     { graph [rank=same]; b [height=0.5, pos="27,18", width=0.75]; }
+//!     a [height=0.5, my_attr=1, pos="27,90", width=0.75];
+//!     a -> b [pos="e,27,36.104 27,71.697 27,63.983 27,54.712 27,46.112"];
+//! }
+//! "#;
+//! let laid_out = merge(g, &dot::parse(output.as_bytes()).unwrap()).unwrap();
+//! assert_eq!(laid_out.node("a").unwrap().pos, Some(Point::new(27.0, 90.0)));
+//! assert_eq!(laid_out.layout.subgraphs[0].nodes, vec![1]);
+//! ```
+use std::collections::{HashMap, HashSet};
+
+use dot_structures::Graph;
+
+use crate::layout::{dot::from_graph, GraphLayout, NodeLayout, SubgraphLayout};
+
+/// The original graph with the layout of its elements.
+///
+/// The elements of the [LayoutGraph::layout] follow the order of the statements in the
+/// original graph and contain the attributes of the original graph.
+/// The layout data is empty for the elements that are missing in the output.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LayoutGraph {
+    pub graph: Graph,
+    pub layout: GraphLayout,
+}
+
+impl LayoutGraph {
+    /// Finds the node by the name.
+    pub fn node(&self, name: &str) -> Option<&NodeLayout> {
+        self.layout.node(name)
+    }
+
+    /// Finds the subgraph by the name.
+    pub fn subgraph(&self, name: &str) -> Option<&SubgraphLayout> {
+        self.layout.subgraphs.iter().find(|s| s.name == name)
+    }
+}
+
+/// Attaches the layout parsed from the `-Tdot` or `-Txdot` output to the elements of the graph.
+pub fn merge(graph: Graph, output: &GraphLayout) -> Result<LayoutGraph, String> {
+    let mut layout = from_graph(&graph)?;
+
+    layout.bb = output.bb;
+    layout.label_pos = output.label_pos;
+    layout.draw = output.draw.clone();
+
+    for node in layout.nodes.iter_mut() {
+        if let Some(out) = output.node(node.name.as_str()) {
+            node.pos = out.pos;
+            node.width = out.width;
+            node.height = out.height;
+            node.xlabel_pos = out.xlabel_pos;
+            node.draw = out.draw.clone();
+        }
+    }
+
+    merge_edges(&mut layout, output);
+    merge_subgraphs(&mut layout, output);
+
+    Ok(LayoutGraph { graph, layout })
+}
+
+fn merge_edges(layout: &mut GraphLayout, output: &GraphLayout) {
+    let names = |l: &GraphLayout, tail: usize, head: usize| {
+        (l.nodes[tail].name.clone(), l.nodes[head].name.clone())
+    };
+    let mut out_edges: HashMap<(String, String), Vec<usize>> = HashMap::new();
+    for (idx, e) in output.edges.iter().enumerate() {
+        out_edges
+            .entry(names(output, e.tail, e.head))
+            .or_default()
+            .push(idx);
+    }
+    let mut seen: HashMap<(String, String), usize> = HashMap::new();
+    for idx in 0..layout.edges.len() {
+        let (tail, head) = names(layout, layout.edges[idx].tail, layout.edges[idx].head);
+        let mut key = (tail.clone(), head.clone());
+        if !layout.directed && !out_edges.contains_key(&key) {
+            key = (head, tail);
+        }
+        let nth = seen.entry(key.clone()).or_default();
+        if let Some(out) = out_edges.get(&key).and_then(|e| e.get(*nth)) {
+            let out = &output.edges[*out];
+            let edge = &mut layout.edges[idx];
+            edge.splines = out.splines.clone();
+            edge.label_pos = out.label_pos;
+            edge.xlabel_pos = out.xlabel_pos;
+            edge.head_label_pos = out.head_label_pos;
+            edge.tail_label_pos = out.tail_label_pos;
+            edge.draw = out.draw.clone();
+        }
+        *nth += 1;
+    }
+}
+
+fn merge_subgraphs(layout: &mut GraphLayout, output: &GraphLayout) {
+    let roots = |l: &GraphLayout| {
+        let nested: HashSet<usize> = l
+            .subgraphs
+            .iter()
+            .flat_map(|s| s.subgraphs.clone())
+            .collect();
+        (0..l.subgraphs.len())
+            .filter(|idx| !nested.contains(idx))
+            .collect::<Vec<_>>()
+    };
+    let mut matched = HashSet::new();
+    let mut stack = vec![(roots(layout), roots(output))];
+    while let Some((subs, out_subs)) = stack.pop() {
+        for idx in subs {
+            if let Some(out) = find_subgraph(layout, output, idx, &out_subs, &matched) {
+                matched.insert(out);
+                let out_sub = &output.subgraphs[out];
+                let sub = &mut layout.subgraphs[idx];
+                sub.bb = out_sub.bb;
+                sub.label_pos = out_sub.label_pos;
+                sub.draw = out_sub.draw.clone();
+                stack.push((sub.subgraphs.clone(), out_sub.subgraphs.clone()));
+            }
+        }
+    }
+}
+
+/// Finds the subgraph of the output among the candidates with the same parent.
+///
+/// The anonymous subgraph is matched by the set of the nodes only,
+/// the subgraph without the match gets no layout.
+fn find_subgraph(
+    layout: &GraphLayout,
+    output: &GraphLayout,
+    idx: usize,
+    candidates: &[usize],
+    matched: &HashSet<usize>,
+) -> Option<usize> {
+    let sub = &layout.subgraphs[idx];
+    let mut free = candidates.iter().copied().filter(|c| !matched.contains(c));
+    if !sub.name.is_empty() {
+        return free.find(|c| output.subgraphs[*c].name == sub.name);
+    }
+    let node_names = |l: &GraphLayout, s: &SubgraphLayout| {
+        s.nodes
+            .iter()
+            .map(|n| l.nodes[*n].name.clone())
+            .collect::<HashSet<_>>()
+    };
+    let nodes = node_names(layout, sub);
+    free.find(|c| {
+        output.subgraphs[*c].name.is_empty() && node_names(output, &output.subgraphs[*c]) == nodes
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use dot_generator::*;
+    use dot_structures::*;
+
+    use crate::layout::{dot, merge::merge, Point, Rect};
+
+    const OUTPUT: &str = r#"digraph g {
+	graph [bb="0,0,170,124"];
+	node [label="\N"];
+	{
+		graph [rank=same];
+		c	[height=0.5, pos="99,18", width=0.75];
+		d	[height=0.5, pos="27,18", width=0.75];
+	}
+	{
+		graph [rank=same];
+		e	[height=0.5, pos="143,18", width=0.75];
+	}
+	subgraph cluster_x {
+		graph [bb="8,64,80,116", label=x, lp="44,100"];
+		a	[height=0.5, my=1, pos="44,90", width=0.75];
+	}
+	b	[height=0.5, pos="120,90", width=0.75];
+	a -> c	[pos="e,1,1 1,2 1,3 1,4 1,5"];
+	a -> c	[pos="e,2,1 2,2 2,3 2,4 2,5"];
+	b -> d	[pos="e,3,1 3,2 3,3 3,4 3,5"];
+}
+"#;
+
+    fn graph() -> Graph {
+        graph!(di id!("g");
+            subgraph!("cluster_x"; stmt!(attr!("label", "x")), node!("a"; attr!("my", "1"))),
+            node!("b"),
+            subgraph!(; stmt!(attr!("rank", "same")), node!("e")),
+            subgraph!(; stmt!(attr!("rank", "same")), node!("d"), node!("c")),
+            edge!(node_id!("b") => node_id!("d")),
+            edge!(node_id!("a") => node_id!("c"); attr!("color", "red")),
+            edge!(node_id!("a") => node_id!("c"))
+        )
+    }
+
+    #[test]
+    fn nodes_test() {
+        let output = dot::parse(OUTPUT.as_bytes()).unwrap();
+        let res = merge(graph(), &output).unwrap();
+        assert_eq!(res.graph, graph());
+        let names: Vec<&str> = res.layout.nodes.iter().map(|n| n.name.as_str()).collect();
+        assert_eq!(names, vec!["a", "b", "e", "d", "c"]);
+        let a = res.node("a").unwrap();
+        assert_eq!(a.pos, Some(Point::new(44., 90.)));
+        assert_eq!(a.attributes.get("my").unwrap(), "1");
+        assert_eq!(res.node("e").unwrap().pos, Some(Point::new(143., 18.)));
+        assert_eq!(
+            res.layout.bb,
+            Some(Rect::new(Point::new(0., 0.), Point::new(170., 124.)))
+        );
+    }
+
+    #[test]
+    fn edges_test() {
+        let output = dot::parse(OUTPUT.as_bytes()).unwrap();
+        let res = merge(graph(), &output).unwrap();
+        let first_x = |idx: usize| res.layout.edges[idx].splines[0].points[0].x;
+        assert_eq!(first_x(0), 3.);
+        assert_eq!(first_x(1), 1.);
+        assert_eq!(first_x(2), 2.);
+        assert_eq!(res.layout.edges[1].attributes.get("color").unwrap(), "red");
+    }
+
+    #[test]
+    fn subgraphs_test() {
+        let output = dot::parse(OUTPUT.as_bytes()).unwrap();
+        let res = merge(graph(), &output).unwrap();
+        let cluster = res.subgraph("cluster_x").unwrap();
+        assert_eq!(cluster.label_pos, Some(Point::new(44., 100.)));
+        assert!(cluster.bb.is_some());
+        assert_eq!(res.layout.subgraphs[1].nodes, vec![2]);
+        assert_eq!(res.layout.subgraphs[2].nodes, vec![3, 4]);
+    }
+
+    #[test]
+    fn missing_test() {
+        let output = dot::parse(OUTPUT.as_bytes()).unwrap();
+        let g = graph!(di id!("g"); node!("z"), edge!(node_id!("c") => node_id!("a")));
+        let res = merge(g, &output).unwrap();
+        assert_eq!(res.node("z").unwrap().pos, None);
+        assert!(res.layout.edges[0].splines.is_empty());
+        assert_eq!(res.node("a").unwrap().pos, Some(Point::new(44., 90.)));
+
+        let output = dot::parse(br#"digraph { { graph [_draw_="c 7 -#000000 "]; a } }"#).unwrap();
+        let g = graph!(di id!("g"); subgraph!(; node!("b")), subgraph!("cluster_y"; node!("a")));
+        let res = merge(g, &output).unwrap();
+        assert!(res.layout.subgraphs[0].draw.shape.is_empty());
+        assert!(res.subgraph("cluster_y").unwrap().draw.shape.is_empty());
+    }
+}
@@ -0,0 +1,326 @@
+//! The typed representation of the layout computed by graphviz.
+//!
+//! The coordinates are in points (1/72 inch) with the origin in the lower-left corner
+//! and the sizes of the nodes are in inches as graphviz provides them.
+//!
+//! The layout can be received from the output of the following formats:
+//!  - [`json`]: `-Tjson`, `-Txdot_json`, `-Tjson0` and `-Tdot_json` (requires the `json` feature)
+//!  - [`plain`]: `-Tplain` and `-Tplain-ext` in the module [plain]
+//!  - [`dot`]: `-Tdot` and `-Txdot` in the module [dot], the drawing operations are decoded by [xdot]
+//!
+//! # Example:
+//! ```rust
+//! use graphviz_rust::layout::{Point, Rect, Spline};
+//!
+//! assert_eq!(Point::parse("27,90").unwrap(), Point::new(27.0, 90.0));
+//! assert_eq!(
+//!     Rect::parse("0,0,62,116").unwrap(),
+//!     Rect::new(Point::new(0.0, 0.0), Point::new(62.0, 116.0))
+//! );
+//! let splines = Spline::parse_all("e,27,36.1 27,71.7 27,63.9 27,54.7 27,46.1").unwrap();
+//! assert_eq!(splines[0].end, Some(Point::new(27.0, 36.1)));
+//! assert_eq!(splines[0].points.len(), 4);
+//! ```
+//!
+//! [`json`]: https://graphviz.org/docs/outputs/json/
+//! [`plain`]: https://graphviz.org/docs/outputs/plain/
+//! [`dot`]: https://graphviz.org/docs/outputs/canon/
+use std::collections::HashMap;
+
+pub mod dot;
+pub mod draw;
+#[cfg(feature = "json")]
+pub mod json;
+pub mod merge;
+pub mod plain;
+pub mod xdot;
+
+use draw::DrawOp;
+
+/// A point with the coordinates in points.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Point {
+    pub x: f64,
+    pub y: f64,
+}
+
+impl Point {
+    pub fn new(x: f64, y: f64) -> Self {
+        Point { x, y }
+    }
+
+    /// Parses the point in the form `x,y` with the optional third coordinate and `!` suffix.
+    pub fn parse(value: &str) -> Result<Point, String> {
+        let coords = parse_numbers(value.trim().trim_end_matches('!'), ',')?;
+        match coords.as_slice() {
+            [x, y] | [x, y, _] => Ok(Point::new(*x, *y)),
+            _ => Err(format!("the point '{}' should have 2 coordinates", value)),
+        }
+    }
+}
+
+/// A rectangle given by the lower-left and upper-right corners, e.g. the bounding box `bb`.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Rect {
+    pub lower_left: Point,
+    pub upper_right: Point,
+}
+
+impl Rect {
+    pub fn new(lower_left: Point, upper_right: Point) -> Self {
+        Rect {
+            lower_left,
+            upper_right,
+        }
+    }
+
+    /// Parses the rectangle in the form `llx,lly,urx,ury`.
+    pub fn parse(value: &str) -> Result<Rect, String> {
+        match parse_numbers(value.trim(), ',')?.as_slice() {
+            [llx, lly, urx, ury] => Ok(Rect::new(Point::new(*llx, *lly), Point::new(*urx, *ury))),
+            _ => Err(format!("the rect '{}' should have 4 coordinates", value)),
+        }
+    }
+
+    pub fn width(&self) -> f64 {
+        self.upper_right.x - self.lower_left.x
+    }
+
+    pub fn height(&self) -> f64 {
+        self.upper_right.y - self.lower_left.y
+    }
+}
+
+/// A B-spline of the edge with the optional arrow endpoints.
+///
+/// The `points` contain `3n + 1` control points.
+/// The `start` and `end` are the tips of the arrows at the tail and the head respectively.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Spline {
+    pub start: Option<Point>,
+    pub end: Option<Point>,
+    pub points: Vec<Point>,
+}
+
+impl Spline {
+    /// Parses one spline in the form `[e,x,y] [s,x,y] x,y x,y ...`.
+    pub fn parse(value: &str) -> Result<Spline, String> {
+        let mut spline = Spline::default();
+        for part in value.split_whitespace() {
+            if let Some(p) = part.strip_prefix("e,") {
+                spline.end = Some(Point::parse(p)?);
+            } else if let Some(p) = part.strip_prefix("s,") {
+                spline.start = Some(Point::parse(p)?);
+            } else {
+                spline.points.push(Point::parse(part)?);
+            }
+        }
+        if spline.points.is_empty() {
+            Err(format!("the spline '{}' has no control points", value))
+        } else {
+            Ok(spline)
+        }
+    }
+
+    /// Parses the value of the edge `pos` attribute that can contain several splines separated by `;`.
+    pub fn parse_all(value: &str) -> Result<Vec<Spline>, String> {
+        value
+            .split(';')
+            .filter(|s| !s.trim().is_empty())
+            .map(Spline::parse)
+            .collect()
+    }
+}
+
+/// The layout of the whole graph.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct GraphLayout {
+    pub name: String,
+    pub directed: bool,
+    pub strict: bool,
+    /// the bounding box of the graph
+    pub bb: Option<Rect>,
+    /// the position of the graph label
+    pub label_pos: Option<Point>,
+    pub nodes: Vec<NodeLayout>,
+    pub edges: Vec<EdgeLayout>,
+    /// all subgraphs including clusters
+    pub subgraphs: Vec<SubgraphLayout>,
+    pub draw: Draw,
+    /// the rest of the attributes
+    pub attributes: HashMap<String, String>,
+}
+
+impl GraphLayout {
+    /// Finds the node by the name.
+    pub fn node(&self, name: &str) -> Option<&NodeLayout> {
+        self.nodes.iter().find(|n| n.name == name)
+    }
+
+    /// Returns the subgraphs that are clusters, namely their names start with `cluster`.
+    pub fn clusters(&self) -> impl Iterator<Item = &SubgraphLayout> {
+        self.subgraphs.iter().filter(|s| s.is_cluster())
+    }
+
+    /// Returns the edges going from the node with the given name to the node with the given name.
+    pub fn edges_between<'a>(
+        &'a self,
+        tail: &'a str,
+        head: &'a str,
+    ) -> impl Iterator<Item = &'a EdgeLayout> {
+        let idx = |name: &str| self.nodes.iter().position(|n| n.name == name);
+        let (t, h) = (idx(tail), idx(head));
+        self.edges
+            .iter()
+            .filter(move |e| Some(e.tail) == t && Some(e.head) == h)
+    }
+}
+
+/// The layout of the node.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct NodeLayout {
+    pub name: String,
+    /// the center of the node
+    pub pos: Option<Point>,
+    /// the width in inches
+    pub width: Option<f64>,
+    /// the height in inches
+    pub height: Option<f64>,
+    /// the position of the external label
+    pub xlabel_pos: Option<Point>,
+    pub draw: Draw,
+    /// the rest of the attributes
+    pub attributes: HashMap<String, String>,
+}
+
+/// The layout of the edge.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct EdgeLayout {
+    /// the index of the tail node in [GraphLayout::nodes]
+    pub tail: usize,
+    /// the index of the head node in [GraphLayout::nodes]
+    pub head: usize,
+    pub splines: Vec<Spline>,
+    pub label_pos: Option<Point>,
+    pub xlabel_pos: Option<Point>,
+    pub head_label_pos: Option<Point>,
+    pub tail_label_pos: Option<Point>,
+    pub draw: Draw,
+    /// the rest of the attributes
+    pub attributes: HashMap<String, String>,
+}
+
+/// The layout of the subgraph.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct SubgraphLayout {
+    pub name: String,
+    /// the bounding box. It is defined for clusters only
+    pub bb: Option<Rect>,
+    pub label_pos: Option<Point>,
+    /// the indexes of the nodes in [GraphLayout::nodes]
+    pub nodes: Vec<usize>,
+    /// the indexes of the edges in [GraphLayout::edges]
+    pub edges: Vec<usize>,
+    /// the indexes of the nested subgraphs in [GraphLayout::subgraphs]
+    pub subgraphs: Vec<usize>,
+    pub draw: Draw,
+    /// the rest of the attributes
+    pub attributes: HashMap<String, String>,
+}
+
+impl SubgraphLayout {
+    pub fn is_cluster(&self) -> bool {
+        self.name.starts_with("cluster")
+    }
+}
+
+/// The drawing operations of the element, see [`xdot`].
+///
+/// [`xdot`]: https://graphviz.org/docs/outputs/canon/#xdot
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Draw {
+    /// `_draw_`: the shape of the element
+    pub shape: Vec<DrawOp>,
+    /// `_ldraw_`: the label
+    pub label: Vec<DrawOp>,
+    /// `_hdraw_`: the head arrow
+    pub head: Vec<DrawOp>,
+    /// `_tdraw_`: the tail arrow
+    pub tail: Vec<DrawOp>,
+    /// `_hldraw_`: the head label
+    pub head_label: Vec<DrawOp>,
+    /// `_tldraw_`: the tail label
+    pub tail_label: Vec<DrawOp>,
+}
+
+impl Draw {
+    /// Returns the operations for the given attribute name, e.g. `_ldraw_`.
+    pub fn ops_mut(&mut self, attr: &str) -> Option<&mut Vec<DrawOp>> {
+        match attr {
+            "_draw_" => Some(&mut self.shape),
+            "_ldraw_" => Some(&mut self.label),
+            "_hdraw_" => Some(&mut self.head),
+            "_tdraw_" => Some(&mut self.tail),
+            "_hldraw_" => Some(&mut self.head_label),
+            "_tldraw_" => Some(&mut self.tail_label),
+            _ => None,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.shape.is_empty()
+            && self.label.is_empty()
+            && self.head.is_empty()
+            && self.tail.is_empty()
+            && self.head_label.is_empty()
+            && self.tail_label.is_empty()
+    }
+}
+
+pub(crate) fn parse_numbers(value: &str, sep: char) -> Result<Vec<f64>, String> {
+    value
+        .split(sep)
+        .map(|v| {
+            v.trim()
+                .parse::<f64>()
+                .map_err(|_| format!("the value '{}' is not a number", value))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::layout::{Point, Rect, Spline};
+
+    #[test]
+    fn point_test() {
+        assert_eq!(Point::parse("27,90").unwrap(), Point::new(27., 90.));
+        assert_eq!(Point::parse("1.5,-2,3!").unwrap(), Point::new(1.5, -2.));
+        assert!(Point::parse("27").is_err());
+        assert!(Point::parse("a,b").is_err());
+    }
+
+    #[test]
+    fn rect_test() {
+        let r = Rect::parse("0,0,62,116").unwrap();
+        assert_eq!(r.width(), 62.);
+        assert_eq!(r.height(), 116.);
+        assert!(Rect::parse("0,0,62").is_err());
+    }
+
+    #[test]
+    fn spline_test() {
+        let splines = Spline::parse_all(
+            "s,1,2 e,27,36.104 27,71.697 27,63.983 27,54.712 27,46.112;27,1 27,2 27,3 27,4",
+        )
+        .unwrap();
+        assert_eq!(splines.len(), 2);
+        assert_eq!(splines[0].start, Some(Point::new(1., 2.)));
+        assert_eq!(splines[0].end, Some(Point::new(27., 36.104)));
+        assert_eq!(splines[0].points.len(), 4);
+        assert_eq!(splines[1].start, None);
+        assert_eq!(splines[1].points[3], Point::new(27., 4.));
+
+        assert!(Spline::parse("e,1,2").is_err());
+    }
+}
@@ -0,0 +1,379 @@
+//! Parser for the [`plain`] output: `-Tplain` and `-Tplain-ext`.
+//!
+//! The format is line based and does not require any additional dependencies:
+//! ```txt
+//! graph scale width height
+//! node name x y width height label style shape color fillcolor
+//! edge tail head n x1 y1 .. xn yn [label xl yl] style color
+//! stop
+//! ```
+//! The `plain-ext` format additionally provides the ports of the edges as `tail:port`.
+//!
+//! _Note_: all coordinates and sizes are in inches.
+//!
+//! # Example:
+//! ```rust
+//! use graphviz_rust::layout::{plain, Point};
+//!
+//! let output = "graph 1 0.75 1.5\n\
+//!               node a 0.375 1.25 0.75 0.5 a solid ellipse black lightgrey\n\
+//!               node b 0.375 0.25 0.75 0.5 b solid ellipse black lightgrey\n\
+//!               edge a b 4 0.375 0.99 0.375 0.88 0.375 0.75 0.375 0.63 solid black\n\
+//!               stop\n";
+//! let layout = plain::parse(output.as_bytes()).unwrap();
+//! assert_eq!(layout.node("a").unwrap().center, Point::new(0.375, 1.25));
+//! assert_eq!(layout.edges[0].points.len(), 4);
+//! ```
+//!
+//! [`plain`]: https://graphviz.org/docs/outputs/plain/
+use crate::layout::Point;
+
+/// The layout received from the `plain` output.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct PlainLayout {
+    pub scale: f64,
+    pub width: f64,
+    pub height: f64,
+    pub nodes: Vec<PlainNode>,
+    pub edges: Vec<PlainEdge>,
+}
+
+impl PlainLayout {
+    /// Finds the node by the name.
+    pub fn node(&self, name: &str) -> Option<&PlainNode> {
+        self.nodes.iter().find(|n| n.name == name)
+    }
+}
+
+/// The node line of the `plain` output.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct PlainNode {
+    pub name: String,
+    pub center: Point,
+    pub width: f64,
+    pub height: f64,
+    pub label: String,
+    pub style: String,
+    pub shape: String,
+    pub color: String,
+    pub fillcolor: String,
+}
+
+/// The edge line of the `plain` output.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct PlainEdge {
+    pub tail: String,
+    /// the port of the tail. It is provided by the `plain-ext` format only
+    pub tail_port: Option<String>,
+    pub head: String,
+    /// the port of the head. It is provided by the `plain-ext` format only
+    pub head_port: Option<String>,
+    /// the control points of the B-spline
+    pub points: Vec<Point>,
+    pub label: Option<PlainLabel>,
+    pub style: String,
+    pub color: String,
+}
+
+/// The label of the edge with the position of its center.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct PlainLabel {
+    pub text: String,
+    pub pos: Point,
+}
+
+/// Parses the `plain` or `plain-ext` output into the [PlainLayout].
+pub fn parse(output: &[u8]) -> Result<PlainLayout, String> {
+    let output = std::str::from_utf8(output).map_err(|e| e.to_string())?;
+    let mut layout = PlainLayout::default();
+    let mut has_graph = false;
+    for (idx, line) in output.lines().enumerate() {
+        let tokens = tokenize(line).map_err(|e| format!("line {}: {}", idx + 1, e))?;
+        let mut line = Line { tokens, pos: 0 };
+        let res = match line.next_raw().as_deref() {
+            None => Ok(()),
+            Some("graph") => {
+                has_graph = true;
+                process_graph(&mut line, &mut layout)
+            }
+            Some("node") => process_node(&mut line).map(|n| layout.nodes.push(n)),
+            Some("edge") => process_edge(&mut line).map(|e| layout.edges.push(e)),
+            Some("stop") => return finish(layout, has_graph),
+            Some(s) => Err(format!("unexpected statement '{}'", s)),
+        };
+        res.and_then(|_| line.end())
+            .map_err(|e| format!("line {}: {}", idx + 1, e))?;
+    }
+    finish(layout, has_graph)
+}
+
+fn finish(layout: PlainLayout, has_graph: bool) -> Result<PlainLayout, String> {
+    if has_graph {
+        Ok(layout)
+    } else {
+        Err("the graph statement is missing".to_string())
+    }
+}
+
+fn process_graph(line: &mut Line, layout: &mut PlainLayout) -> Result<(), String> {
+    layout.scale = line.next_f64("scale")?;
+    layout.width = line.next_f64("width")?;
+    layout.height = line.next_f64("height")?;
+    Ok(())
+}
+
+fn process_node(line: &mut Line) -> Result<PlainNode, String> {
+    Ok(PlainNode {
+        name: line.next_str("name")?,
+        center: Point::new(line.next_f64("x")?, line.next_f64("y")?),
+        width: line.next_f64("width")?,
+        height: line.next_f64("height")?,
+        label: line.next_str("label")?,
+        style: line.next_str("style")?,
+        shape: line.next_str("shape")?,
+        color: line.next_str("color")?,
+        fillcolor: line.next_str("fillcolor")?,
+    })
+}
+
+fn process_edge(line: &mut Line) -> Result<PlainEdge, String> {
+    let (tail, tail_port) = split_port(&line.next_raw_req("tail")?);
+    let (head, head_port) = split_port(&line.next_raw_req("head")?);
+    let n = line.next_raw_req("n")?;
+    let n = n
+        .parse::<usize>()
+        .map_err(|_| format!("the number of points '{}' is invalid", n))?;
+    let mut points = vec![];
+    for _ in 0..n {
+        points.push(Point::new(line.next_f64("x")?, line.next_f64("y")?));
+    }
+    // the label is optional thus the rest is either [label xl yl] style color or style color
+    let label = if line.remaining() == 5 {
+        Some(PlainLabel {
+            text: line.next_str("label")?,
+            pos: Point::new(line.next_f64("xl")?, line.next_f64("yl")?),
+        })
+    } else {
+        None
+    };
+    Ok(PlainEdge {
+        tail,
+        tail_port,
+        head,
+        head_port,
+        points,
+        label,
+        style: line.next_str("style")?,
+        color: line.next_str("color")?,
+    })
+}
+
+struct Line {
+    tokens: Vec<String>,
+    pos: usize,
+}
+
+impl Line {
+    fn next_raw(&mut self) -> Option<String> {
+        let t = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        t
+    }
+    fn next_raw_req(&mut self, name: &str) -> Result<String, String> {
+        self.next_raw()
+            .ok_or_else(|| format!("the field '{}' is missing", name))
+    }
+    fn next_str(&mut self, name: &str) -> Result<String, String> {
+        self.next_raw_req(name).map(|t| unquote(&t))
+    }
+    fn next_f64(&mut self, name: &str) -> Result<f64, String> {
+        let t = self.next_raw_req(name)?;
+        t.parse::<f64>()
+            .map_err(|_| format!("the field '{}' is not a number: '{}'", name, t))
+    }
+    fn remaining(&self) -> usize {
+        self.tokens.len().saturating_sub(self.pos)
+    }
+    fn end(&self) -> Result<(), String> {
+        if self.remaining() > 0 {
+            Err(format!(
+                "unexpected tokens: {}",
+                self.tokens[self.pos..].join(" ")
+            ))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// Splits the line into the tokens keeping the quotes and html brackets.
+fn tokenize(line: &str) -> Result<Vec<String>, String> {
+    let mut tokens = vec![];
+    let mut token = String::new();
+    let mut chars = line.chars();
+    let mut in_quotes = false;
+    let mut html_depth = 0;
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' if in_quotes => {
+                token.push(c);
+                if let Some(n) = chars.next() {
+                    token.push(n)
+                }
+            }
+            '"' if html_depth == 0 => {
+                in_quotes = !in_quotes;
+                token.push(c)
+            }
+            '<' if !in_quotes => {
+                html_depth += 1;
+                token.push(c)
+            }
+            '>' if !in_quotes && html_depth > 0 => {
+                html_depth -= 1;
+                token.push(c)
+            }
+            c if c.is_whitespace() && !in_quotes && html_depth == 0 => {
+                if !token.is_empty() {
+                    tokens.push(std::mem::take(&mut token))
+                }
+            }
+            c => token.push(c),
+        }
+    }
+    if in_quotes {
+        Err("the quoted string is not closed".to_string())
+    } else if html_depth > 0 {
+        Err("the html string is not closed".to_string())
+    } else {
+        if !token.is_empty() {
+            tokens.push(token)
+        }
+        Ok(tokens)
+    }
+}
+
+/// Splits `name:port` where both parts can be quoted.
+fn split_port(token: &str) -> (String, Option<String>) {
+    let mut in_quotes = false;
+    let mut escaped = false;
+    for (idx, c) in token.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if in_quotes => escaped = true,
+            '"' => in_quotes = !in_quotes,
+            ':' if !in_quotes => return (unquote(&token[..idx]), Some(unquote(&token[idx + 1..]))),
+            _ => (),
+        }
+    }
+    (unquote(token), None)
+}
+
+fn unquote(token: &str) -> String {
+    match token.strip_prefix('"').and_then(|t| t.strip_suffix('"')) {
+        Some(t) => {
+            let mut res = String::new();
+            let mut chars = t.chars();
+            while let Some(c) = chars.next() {
+                match c {
+                    '\\' => match chars.next() {
+                        Some('"') => res.push('"'),
+                        Some('\\') => res.push('\\'),
+                        Some('\n') | None => (),
+                        Some(n) => {
+                            res.push('\\');
+                            res.push(n)
+                        }
+                    },
+                    c => res.push(c),
+                }
+            }
+            res
+        }
+        None => token.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::layout::{
+        plain::{parse, tokenize, PlainLabel},
+        Point,
+    };
+
+    #[test]
+    fn plain_test() {
+        let output = r#"graph 1 1.9167 2.5
+node a 0.95833 2.25 0.75 0.5 a solid ellipse black lightgrey
+node "b c" 0.375 0.25 0.75 0.5 "label \"x\"" filled box red yellow
+node d 1.5417 1.25 0.75 0.5 <<b>d</b> and <i>e</i>> solid ellipse black lightgrey
+edge a "b c" 4 0.84 1.99 0.73 1.72 0.58 1.27 0.44 0.77 "a to b" 0.79 1.25 dashed black
+edge a d 4 1.08 1.99 1.15 1.84 1.24 1.65 1.33 1.5 solid black
+stop
+"#;
+        let layout = parse(output.as_bytes()).unwrap();
+        assert_eq!(layout.scale, 1.);
+        assert_eq!(layout.width, 1.9167);
+        assert_eq!(layout.height, 2.5);
+        assert_eq!(layout.nodes.len(), 3);
+
+        let bc = layout.node("b c").unwrap();
+        assert_eq!(bc.center, Point::new(0.375, 0.25));
+        assert_eq!(bc.label, "label \"x\"");
+        assert_eq!(bc.style, "filled");
+        assert_eq!(bc.shape, "box");
+        assert_eq!(bc.color, "red");
+        assert_eq!(bc.fillcolor, "yellow");
+        assert_eq!(layout.node("d").unwrap().label, "<<b>d</b> and <i>e</i>>");
+
+        let e = &layout.edges[0];
+        assert_eq!((e.tail.as_str(), e.head.as_str()), ("a", "b c"));
+        assert_eq!(e.points.len(), 4);
+        assert_eq!(e.points[3], Point::new(0.44, 0.77));
+        assert_eq!(
+            e.label,
+            Some(PlainLabel {
+                text: "a to b".to_string(),
+                pos: Point::new(0.79, 1.25)
+            })
+        );
+        assert_eq!(e.style, "dashed");
+        assert_eq!(layout.edges[1].label, None);
+        assert_eq!(layout.edges[1].color, "black");
+    }
+
+    #[test]
+    fn plain_ext_test() {
+        let output = "graph 1 2 2\n\
+                      node a 1 1 0.75 0.5 a solid record black lightgrey\n\
+                      node b 1 0 0.75 0.5 b solid ellipse black lightgrey\n\
+                      edge a:p1:s \"b\":\"x y\" 4 1 1 1 0.8 1 0.6 1 0.4 solid black\n\
+                      stop";
+        let layout = parse(output.as_bytes()).unwrap();
+        let e = &layout.edges[0];
+        assert_eq!(e.tail, "a");
+        assert_eq!(e.tail_port, Some("p1:s".to_string()));
+        assert_eq!(e.head, "b");
+        assert_eq!(e.head_port, Some("x y".to_string()));
+    }
+
+    #[test]
+    fn tokenize_test() {
+        assert_eq!(
+            tokenize(r#"node "a \"b\"" <x <y> z> 1"#).unwrap(),
+            vec!["node", r#""a \"b\"""#, "<x <y> z>", "1"]
+        );
+        assert!(tokenize(r#"node "a"#).is_err());
+        assert!(tokenize("node <a").is_err());
+    }
+
+    #[test]
+    fn error_test() {
+        assert!(parse(b"node a 1 1 1 1 a solid box black white\nstop").is_err());
+        let err = parse(b"graph 1 2 2\nnode a 1 x 1 1 a solid box black white\nstop").unwrap_err();
+        assert!(err.starts_with("line 2:"), "{}", err);
+        assert!(parse(b"graph 1 2 2\nedge a b 4 1 1 solid black\nstop").is_err());
+        assert!(parse(b"graph 1 2 2 3\nstop").is_err());
+        assert!(parse(b"graph 1 2 2\nsomething\nstop").is_err());
+    }
+}
@@ -0,0 +1,357 @@
+//! Parser for the drawing operations of the [`xdot`] format.
+//!
+//! The `-Txdot` output is the graph in the dot notation with the attributes
+//! `_draw_`, `_ldraw_`, `_hdraw_`, `_tdraw_`, `_hldraw_` and `_tldraw_`
+//! containing the operations. The whole output can be read with [dot::parse](crate::layout::dot::parse)
+//! and the separate attribute values with [parse_ops].
+//!
+//! # Example:
+//! ```rust
+//! use graphviz_rust::layout::{
+//!     draw::{DrawOp, Paint, TextAlign},
+//!     xdot::parse_ops,
+//!     Point,
+//! };
+//!
+//! let ops = parse_ops("c 7 -#ff0000 e 27 18 27 18 F 14 11 -Times-Roman T 27 14.3 0 7 1 -a").unwrap();
+//! assert_eq!(ops[0], DrawOp::PenColor(Paint::Color("#ff0000".to_string())));
+//! assert_eq!(
+//!     ops[3],
+//!     DrawOp::Text {
+//!         pos: Point::new(27.0, 14.3),
+//!         align: TextAlign::Center,
+//!         width: 7.0,
+//!         text: "a".to_string()
+//!     }
+//! );
+//! ```
+//!
+//! [`xdot`]: https://graphviz.org/docs/outputs/canon/#xdot
+use crate::layout::{
+    draw::{ColorStop, DrawOp, Paint, TextAlign},
+    Point,
+};
+
+/// Parses the value of the xdot attribute into the list of operations.
+pub fn parse_ops(value: &str) -> Result<Vec<DrawOp>, String> {
+    let mut lexer = Lexer::new(value);
+    let mut ops = vec![];
+    while let Some(op) = lexer.next_op()? {
+        ops.push(op);
+    }
+    Ok(ops)
+}
+
+/// Parses the color given in the `c` and `C` operations.
+///
+/// The gradients are given in the forms
+/// `[x0 y0 x1 y1 n v1 c1 .. vn cn]` (linear) and `(x0 y0 r0 x1 y1 r1 n v1 c1 .. vn cn)` (radial).
+pub fn parse_paint(value: &str) -> Result<Paint, String> {
+    let value = value.trim();
+    if let Some(grad) = value.strip_prefix('[') {
+        let grad = grad
+            .strip_suffix(']')
+            .ok_or_else(|| format!("the linear gradient '{}' is not closed", value))?;
+        let mut lexer = Lexer::new(grad);
+        let start = lexer.point()?;
+        let end = lexer.point()?;
+        let stops = lexer.stops()?;
+        lexer.end()?;
+        Ok(Paint::LinearGradient { start, end, stops })
+    } else if let Some(grad) = value.strip_prefix('(') {
+        let grad = grad
+            .strip_suffix(')')
+            .ok_or_else(|| format!("the radial gradient '{}' is not closed", value))?;
+        let mut lexer = Lexer::new(grad);
+        let start = lexer.point()?;
+        let start_radius = lexer.number()?;
+        let end = lexer.point()?;
+        let end_radius = lexer.number()?;
+        let stops = lexer.stops()?;
+        lexer.end()?;
+        Ok(Paint::RadialGradient {
+            start,
+            start_radius,
+            end,
+            end_radius,
+            stops,
+        })
+    } else {
+        Ok(Paint::Color(value.to_string()))
+    }
+}
+
+struct Lexer<'a> {
+    input: &'a str,
+    pos: usize,
+}
+
+impl<'a> Lexer<'a> {
+    fn new(input: &'a str) -> Self {
+        Lexer { input, pos: 0 }
+    }
+
+    fn skip_ws(&mut self) {
+        let rest = &self.input[self.pos..];
+        self.pos += rest.len() - rest.trim_start().len();
+    }
+
+    fn token(&mut self) -> Option<&'a str> {
+        self.skip_ws();
+        let rest = &self.input[self.pos..];
+        if rest.is_empty() {
+            return None;
+        }
+        let len = rest.find(char::is_whitespace).unwrap_or(rest.len());
+        self.pos += len;
+        Some(&rest[..len])
+    }
+
+    fn req_token(&mut self) -> Result<&'a str, String> {
+        self.token()
+            .ok_or_else(|| format!("unexpected end of '{}'", self.input))
+    }
+
+    fn number(&mut self) -> Result<f64, String> {
+        let t = self.req_token()?;
+        t.parse::<f64>()
+            .map_err(|_| format!("'{}' is not a number in '{}'", t, self.input))
+    }
+
+    fn count(&mut self) -> Result<usize, String> {
+        let t = self.req_token()?;
+        t.parse::<usize>()
+            .map_err(|_| format!("'{}' is not a count in '{}'", t, self.input))
+    }
+
+    fn point(&mut self) -> Result<Point, String> {
+        Ok(Point::new(self.number()?, self.number()?))
+    }
+
+    fn points(&mut self) -> Result<Vec<Point>, String> {
+        let n = self.count()?;
+        (0..n).map(|_| self.point()).collect()
+    }
+
+    /// Reads the string in the form `n -b1b2..bn` where n is the number of bytes.
+    fn string(&mut self) -> Result<String, String> {
+        let n = self.count()?;
+        self.skip_ws();
+        let rest = &self.input[self.pos..];
+        let bytes = rest
+            .strip_prefix('-')
+            .ok_or_else(|| format!("the string should start with '-' in '{}'", self.input))?;
+        let s = bytes
+            .get(..n)
+            .ok_or_else(|| format!("the string of {} bytes is invalid in '{}'", n, self.input))?;
+        self.pos += 1 + n;
+        Ok(s.to_string())
+    }
+
+    fn stops(&mut self) -> Result<Vec<ColorStop>, String> {
+        let n = self.count()?;
+        (0..n)
+            .map(|_| {
+                let offset = self.number()?;
+                let color = self.string()?;
+                Ok(ColorStop { offset, color })
+            })
+            .collect()
+    }
+
+    fn end(&mut self) -> Result<(), String> {
+        match self.token() {
+            Some(t) => Err(format!("unexpected '{}' in '{}'", t, self.input)),
+            None => Ok(()),
+        }
+    }
+
+    fn next_op(&mut self) -> Result<Option<DrawOp>, String> {
+        let op = match self.token() {
+            None => return Ok(None),
+            Some(op) => op,
+        };
+        let op = match op {
+            "E" | "e" => DrawOp::Ellipse {
+                filled: op == "E",
+                center: self.point()?,
+                width: self.number()?,
+                height: self.number()?,
+            },
+            "P" | "p" => DrawOp::Polygon {
+                filled: op == "P",
+                points: self.points()?,
+            },
+            "L" => DrawOp::Polyline {
+                points: self.points()?,
+            },
+            "B" | "b" => DrawOp::BSpline {
+                filled: op == "b",
+                points: self.points()?,
+            },
+            "T" => DrawOp::Text {
+                pos: self.point()?,
+                align: TextAlign::parse(self.req_token()?)?,
+                width: self.number()?,
+                text: self.string()?,
+            },
+            "t" => {
+                let t = self.req_token()?;
+                DrawOp::FontChar(
+                    t.parse::<u32>()
+                        .map_err(|_| format!("'{}' is not a font characteristic", t))?,
+                )
+            }
+            "C" => DrawOp::FillColor(parse_paint(&self.string()?)?),
+            "c" => DrawOp::PenColor(parse_paint(&self.string()?)?),
+            "F" => DrawOp::Font {
+                size: self.number()?,
+                name: self.string()?,
+            },
+            "S" => DrawOp::Style(self.string()?),
+            "I" => DrawOp::Image {
+                pos: self.point()?,
+                width: self.number()?,
+                height: self.number()?,
+                name: self.string()?,
+            },
+            op => return Err(format!("the drawing operation '{}' is unknown", op)),
+        };
+        Ok(Some(op))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::layout::{
+        draw::{ColorStop, DrawOp, Paint},
+        xdot::{parse_ops, parse_paint},
+        Point,
+    };
+
+    #[test]
+    fn shapes_test() {
+        let ops = parse_ops(
+            "E 27 18 27 18 p 3 0 0 1 1 2 0 L 2 0 0 1 1 B 4 0 0 1 1 2 2 3 3 b 4 0 0 1 1 2 2 3 3 ",
+        )
+        .unwrap();
+        assert_eq!(
+            ops,
+            vec![
+                DrawOp::Ellipse {
+                    filled: true,
+                    center: Point::new(27., 18.),
+                    width: 27.,
+                    height: 18.
+                },
+                DrawOp::Polygon {
+                    filled: false,
+                    points: vec![Point::new(0., 0.), Point::new(1., 1.), Point::new(2., 0.)]
+                },
+                DrawOp::Polyline {
+                    points: vec![Point::new(0., 0.), Point::new(1., 1.)]
+                },
+                DrawOp::BSpline {
+                    filled: false,
+                    points: vec![
+                        Point::new(0., 0.),
+                        Point::new(1., 1.),
+                        Point::new(2., 2.),
+                        Point::new(3., 3.)
+                    ]
+                },
+                DrawOp::BSpline {
+                    filled: true,
+                    points: vec![
+                        Point::new(0., 0.),
+                        Point::new(1., 1.),
+                        Point::new(2., 2.),
+                        Point::new(3., 3.)
+                    ]
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn text_test() {
+        let ops = parse_ops(
+            "F 14 11 -Times-Roman t 3 T 10 20 -1 30 8 -héllo w I 1 2 3 4 5 -a.png S 6 -dashed",
+        )
+        .unwrap();
+        assert_eq!(
+            ops[0],
+            DrawOp::Font {
+                size: 14.,
+                name: "Times-Roman".to_string()
+            }
+        );
+        assert_eq!(ops[1], DrawOp::FontChar(3));
+        match &ops[2] {
+            DrawOp::Text { text, .. } => assert_eq!(text, "héllo w"),
+            op => panic!("unexpected {:?}", op),
+        }
+        assert_eq!(
+            ops[3],
+            DrawOp::Image {
+                pos: Point::new(1., 2.),
+                width: 3.,
+                height: 4.,
+                name: "a.png".to_string()
+            }
+        );
+        assert_eq!(ops[4], DrawOp::Style("dashed".to_string()));
+    }
+
+    #[test]
+    fn paint_test() {
+        assert_eq!(
+            parse_paint("#ff000080").unwrap(),
+            Paint::Color("#ff000080".to_string())
+        );
+        let stops = vec![
+            ColorStop {
+                offset: 0.,
+                color: "white".to_string(),
+            },
+            ColorStop {
+                offset: 1.,
+                color: "#0000ff".to_string(),
+            },
+        ];
+        assert_eq!(
+            parse_paint("[0 0 100 0 2 0 5 -white 1 7 -#0000ff]").unwrap(),
+            Paint::LinearGradient {
+                start: Point::new(0., 0.),
+                end: Point::new(100., 0.),
+                stops: stops.clone()
+            }
+        );
+        assert_eq!(
+            parse_paint("(50 50 0 50 50 30 2 0 5 -white 1 7 -#0000ff)").unwrap(),
+            Paint::RadialGradient {
+                start: Point::new(50., 50.),
+                start_radius: 0.,
+                end: Point::new(50., 50.),
+                end_radius: 30.,
+                stops
+            }
+        );
+        let ops = parse_ops("C 33 -[0 0 100 0 2 0 5 -white 1 3 -red]").unwrap();
+        assert!(matches!(
+            &ops[0],
+            DrawOp::FillColor(Paint::LinearGradient { stops, .. }) if stops[1].color == "red"
+        ));
+    }
+
+    #[test]
+    fn error_test() {
+        assert!(parse_ops("X 1 2").is_err());
+        assert!(parse_paint("[0 0 100 0 1 0 white]").is_err());
+        assert!(parse_ops("E 1 2 3").is_err());
+        assert!(parse_ops("P 3 0 0 1 1").is_err());
+        assert!(parse_ops("c 10 -red").is_err());
+        assert!(parse_ops("c 3 red").is_err());
+        assert!(parse_paint("[0 0 1 1 1 0 red").is_err());
+    }
+}
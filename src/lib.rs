@@ -7,6 +7,14 @@
 //!  - [exec]: executes the [`dot` command line executable] given a [Graph].
 //!  - [exec_dot]: executes the [`dot` command line executable] given a string in
 //!    the dot [`notation`].
+//!  - [exec_formats]: executes the [`dot` command line executable] once and renders a [Graph]
+//!    into several formats sharing the same layout.
+//!  - [exec_with]: executes a [Graph] with the given [cmd::Executor], e.g. [cmd::MockExecutor] in tests.
+//!  - [exec_layout]: computes the layout of a [Graph] and attaches it to the nodes, edges and subgraphs.
+//!  - [exec_image_map]: renders a [Graph] into the html fragment with the clickable png image.
+//!
+//! The conversions from and into the `petgraph` graphs are available in the `petgraph` module
+//! with the `petgraph` feature.
 //!
 //! # Examples:
 //! ```rust
@@ -114,14 +122,24 @@ use crate::printer::{DotPrinter, PrinterContext};
 pub mod attributes;
 #[cfg(feature = "graphviz-exec")]
 pub mod cmd;
+pub mod imagemap;
+pub mod layout;
+pub mod model;
 mod parser;
+#[cfg(feature = "petgraph")]
+pub mod petgraph;
 pub mod printer;
+pub mod svg;
 
 #[macro_use]
 extern crate pest_derive;
 extern crate pest;
 
 /// Parses a string into a [Graph].
+///
+/// The html labels are kept as [Id::Html](dot_structures::Id::Html) strings,
+/// the typed tree of the label is built by [Label::from_id](attributes::html::Label::from_id)
+/// or by [parse_labels](attributes::html::parse_labels) keeping the positions in the source.
 pub fn parse(dot: &str) -> Result<Graph, String> {
     parser::parse(dot)
 }
@@ -132,15 +150,30 @@ pub fn print(graph: Graph, ctx: &mut PrinterContext) -> String {
 }
 
 #[cfg(feature = "graphviz-exec")]
-use cmd::CommandArg;
+use cmd::{CommandArg, DotExecutor, Executor, Format};
+#[cfg(feature = "graphviz-exec")]
+use layout::merge::LayoutGraph;
 #[cfg(feature = "graphviz-exec")]
-use std::io;
+use std::{collections::HashMap, io};
 
 /// Executes the [`dot` command line executable](https://graphviz.org/doc/info/command.html)
 /// using the given [Graph], [PrinterContext] and command line arguments.
 #[cfg(feature = "graphviz-exec")]
 pub fn exec(graph: Graph, ctx: &mut PrinterContext, args: Vec<CommandArg>) -> io::Result<Vec<u8>> {
-    cmd::exec(print(graph, ctx), args)
+    exec_with(&DotExecutor, graph, ctx, args)
+}
+
+/// Executes the given [Graph] with the given [Executor].
+///
+/// The [cmd::MockExecutor] allows testing the rendering code without the installed graphviz.
+#[cfg(feature = "graphviz-exec")]
+pub fn exec_with<E: Executor + ?Sized>(
+    executor: &E,
+    graph: Graph,
+    ctx: &mut PrinterContext,
+    args: Vec<CommandArg>,
+) -> io::Result<Vec<u8>> {
+    executor.exec(print(graph, ctx), args)
 }
 
 /// Executes the [`dot` command line executable](https://graphviz.org/doc/info/command.html)
@@ -150,6 +183,162 @@ pub fn exec_dot(dot_graph: String, args: Vec<CommandArg>) -> io::Result<Vec<u8>>
     cmd::exec(dot_graph, args)
 }
 
+/// Executes the [`dot` command line executable](https://graphviz.org/doc/info/command.html)
+/// once for the given [Graph] and returns the output for every given [Format].
+///
+/// The layout is computed only one time and shared by all outputs.
+/// The `args` can not contain [CommandArg::Format], [CommandArg::OutputFormat], [CommandArg::Output]
+/// or [CommandArg::AutoOutput] since the outputs are generated for every format.
+///
+/// # Example:
+/// ```no_run
+/// use dot_generator::*;
+/// use dot_structures::*;
+/// use graphviz_rust::{cmd::Format, exec_formats, printer::PrinterContext};
+///
+/// let g = graph!(di id!("id"); edge!(node_id!("a") => node_id!("b")));
+/// let outputs = exec_formats(
+///     g,
+///     &mut PrinterContext::default(),
+///     vec![Format::Svg, Format::Png, Format::Json],
+///     vec![],
+/// )
+/// .unwrap();
+/// let svg = &outputs[&Format::Svg];
+/// ```
+#[cfg(feature = "graphviz-exec")]
+pub fn exec_formats(
+    graph: Graph,
+    ctx: &mut PrinterContext,
+    formats: Vec<Format>,
+    args: Vec<CommandArg>,
+) -> io::Result<HashMap<Format, Vec<u8>>> {
+    cmd::exec_formats(&DotExecutor, print(graph, ctx), formats, args)
+}
+
+/// Executes the given [Graph] once with the given [Executor] and returns the output for every given [Format].
+///
+/// See [exec_formats].
+#[cfg(feature = "graphviz-exec")]
+pub fn exec_formats_with<E: Executor + ?Sized>(
+    executor: &E,
+    graph: Graph,
+    ctx: &mut PrinterContext,
+    formats: Vec<Format>,
+    args: Vec<CommandArg>,
+) -> io::Result<HashMap<Format, Vec<u8>>> {
+    cmd::exec_formats(executor, print(graph, ctx), formats, args)
+}
+
+/// Executes the [`dot` command line executable](https://graphviz.org/doc/info/command.html)
+/// once for the given string dot notation and returns the output for every given [Format].
+///
+/// See [exec_formats].
+#[cfg(feature = "graphviz-exec")]
+pub fn exec_dot_formats(
+    dot_graph: String,
+    formats: Vec<Format>,
+    args: Vec<CommandArg>,
+) -> io::Result<HashMap<Format, Vec<u8>>> {
+    cmd::exec_formats(&DotExecutor, dot_graph, formats, args)
+}
+
+/// Executes the [`dot` command line executable](https://graphviz.org/doc/info/command.html)
+/// with the `-Tdot` format and attaches the computed layout to the elements of the given [Graph].
+///
+/// The `args` can not contain [CommandArg::Format], [CommandArg::OutputFormat], [CommandArg::Output]
+/// or [CommandArg::AutoOutput] since the layout is read from the `-Tdot` output.
+/// See [layout::merge] for the details of the matching.
+///
+/// # Example:
+/// ```no_run
+/// use dot_generator::*;
+/// use dot_structures::*;
+/// use graphviz_rust::{
+///     cmd::{CommandArg, Layout},
+///     exec_layout,
+///     printer::PrinterContext,
+/// };
+///
+/// let g = graph!(di id!("id"); edge!(node_id!("a") => node_id!("b")));
+/// let laid_out = exec_layout(
+///     g,
+///     &mut PrinterContext::default(),
+///     vec![CommandArg::Layout(Layout::Neato)],
+/// )
+/// .unwrap();
+/// let pos = laid_out.node("a").unwrap().pos;
+/// ```
+#[cfg(feature = "graphviz-exec")]
+pub fn exec_layout(
+    graph: Graph,
+    ctx: &mut PrinterContext,
+    args: Vec<CommandArg>,
+) -> io::Result<LayoutGraph> {
+    exec_layout_with(&DotExecutor, graph, ctx, args)
+}
+
+/// Computes the layout of the given [Graph] with the given [Executor].
+///
+/// See [exec_layout].
+#[cfg(feature = "graphviz-exec")]
+pub fn exec_layout_with<E: Executor + ?Sized>(
+    executor: &E,
+    graph: Graph,
+    ctx: &mut PrinterContext,
+    mut args: Vec<CommandArg>,
+) -> io::Result<LayoutGraph> {
+    cmd::check_no_outputs(&args)?;
+    args.push(Format::Dot.into());
+    let output = executor.exec(print(graph.clone(), ctx), args)?;
+    layout::dot::parse(&output)
+        .and_then(|layout| layout::merge::merge(graph, &layout))
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+/// Executes the [`dot` command line executable](https://graphviz.org/doc/info/command.html)
+/// once rendering the given [Graph] into the png image and the `cmapx` image map
+/// and returns the html fragment with the embedded image wired to the map.
+///
+/// The areas are generated for the elements having the `href` or `URL` attribute.
+/// The `args` should not contain [CommandArg::Format] or [CommandArg::Output].
+/// See [imagemap] for the details.
+///
+/// # Example:
+/// ```no_run
+/// use dot_generator::*;
+/// use dot_structures::*;
+/// use graphviz_rust::{exec_image_map, printer::PrinterContext};
+///
+/// let g = graph!(di id!("g"); node!("a"; attr!("href", esc "https://a.org")));
+/// let fragment = exec_image_map(g, &mut PrinterContext::default(), vec![]).unwrap();
+/// ```
+#[cfg(feature = "graphviz-exec")]
+pub fn exec_image_map(
+    graph: Graph,
+    ctx: &mut PrinterContext,
+    args: Vec<CommandArg>,
+) -> io::Result<String> {
+    exec_image_map_with(&DotExecutor, graph, ctx, args)
+}
+
+/// Renders the html fragment with the clickable png image with the given [Executor].
+///
+/// See [exec_image_map].
+#[cfg(feature = "graphviz-exec")]
+pub fn exec_image_map_with<E: Executor + ?Sized>(
+    executor: &E,
+    graph: Graph,
+    ctx: &mut PrinterContext,
+    args: Vec<CommandArg>,
+) -> io::Result<String> {
+    let formats = vec![Format::Png, Format::Cmapx];
+    let outputs = cmd::exec_formats(executor, print(graph, ctx), formats, args)?;
+    let map = imagemap::parse(&outputs[&Format::Cmapx])
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+    Ok(imagemap::html(&outputs[&Format::Png], &map))
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -200,7 +389,7 @@ mod tests {
     fn print_test() {
         let mut g = graph!(id!("id"));
 
-        for el in (1..10000).into_iter() {
+        for el in 1..10000 {
             if el % 2 == 0 {
                 g.add_stmt(stmt!(node!(el)))
             } else {
@@ -211,19 +400,126 @@ mod tests {
         assert_eq!(148898, g.print(&mut PrinterContext::default()).len())
     }
 
-    #[cfg(windows)]
-    const LS: &'static str = "\r\n";
-    #[cfg(not(windows))]
-    const LS: &'static str = "\n";
+    #[test]
+    #[cfg(feature = "graphviz-exec")]
+    fn exec_layout_test() {
+        use crate::{
+            cmd::{CommandArg, Format, MockExecutor},
+            exec_layout_with,
+            layout::Point,
+        };
+
+        let output = r#"digraph id {
+            graph [bb="0,0,54,108"];
+            node [label="\N"];
+            a [height=0.5, pos="27,90", width=0.75];
+            b [height=0.5, pos="27,18", width=0.75];
+            a -> b [pos="e,27,36.104 27,71.697 27,63.983 27,54.712 27,46.112"];
+        }"#;
+        let executor = MockExecutor::new(output.as_bytes().to_vec());
+        let g = graph!(di id!("id"); edge!(node_id!("a") => node_id!("b")));
+        let res =
+            exec_layout_with(&executor, g.clone(), &mut PrinterContext::default(), vec![]).unwrap();
+
+        assert_eq!(res.graph, g);
+        assert_eq!(res.node("b").unwrap().pos, Some(Point::new(27., 18.)));
+        assert_eq!(res.layout.edges[0].splines.len(), 1);
+        assert_eq!(
+            executor.calls()[0].args,
+            vec![CommandArg::Format(Format::Dot)]
+        );
+
+        let executor = MockExecutor::new(b"not a graph".to_vec());
+        assert!(
+            exec_layout_with(&executor, g.clone(), &mut PrinterContext::default(), vec![]).is_err()
+        );
+
+        let executor = MockExecutor::new(output.as_bytes().to_vec());
+        let err = exec_layout_with(
+            &executor,
+            g,
+            &mut PrinterContext::default(),
+            vec![CommandArg::Format(Format::Svg)],
+        )
+        .unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+        assert!(executor.calls().is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "graphviz-exec")]
+    fn exec_image_map_test() {
+        use crate::{
+            cmd::{Format, MockExecutor},
+            exec_image_map_with,
+        };
+
+        let cmapx = r#"<map id="g" name="g">
+<area shape="poly" id="node1" href="https://a.org" title="a" alt="" coords="54,18 52,25 47,31"/>
+</map>"#;
+        let executor = MockExecutor::new(vec![])
+            .with_output(Format::Png, b"png".to_vec())
+            .with_output(Format::Cmapx, cmapx.as_bytes().to_vec());
+        let g = graph!(di id!("g"); node!("a"; attr!("href", esc "https://a.org")));
+        let html =
+            exec_image_map_with(&executor, g.clone(), &mut PrinterContext::default(), vec![])
+                .unwrap();
+
+        assert!(
+            html.starts_with(r##"<img src="data:image/png;base64,cG5n" usemap="#g" alt="g"/>"##)
+        );
+        assert!(html.contains(r#"<area shape="poly" id="node1" href="https://a.org""#));
+        assert_eq!(executor.calls().len(), 1);
+
+        let executor = MockExecutor::new(vec![]).with_output(Format::Cmapx, b"<map>".to_vec());
+        assert!(exec_image_map_with(&executor, g, &mut PrinterContext::default(), vec![]).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "graphviz-exec")]
+    fn exec_mock_test() {
+        use crate::{
+            attributes::{color_name, shape, NodeAttributes},
+            cmd::{CommandArg, Format, MockExecutor},
+            exec_with,
+        };
+
+        let g = graph!(id!("id");
+            node!("nod"),
+            subgraph!("sb";
+                edge!(node_id!("a") => subgraph!(;
+                    node!("n";
+                    NodeAttributes::color(color_name::black), NodeAttributes::shape(shape::egg))
+                ))
+            ),
+            edge!(node_id!("a1") => node_id!(esc "a2"))
+        );
+        let graph_str = "graph id {\n  nod\n  subgraph sb {\n    a -- subgraph  {n[color=black shape=egg]}\n  }\n  a1 -- \"a2\"\n}";
+
+        let executor = MockExecutor::new(b"<svg/>".to_vec());
+        let out = exec_with(
+            &executor,
+            g,
+            &mut PrinterContext::default(),
+            vec![CommandArg::Format(Format::Svg)],
+        )
+        .unwrap();
+
+        assert_eq!(out, b"<svg/>".to_vec());
+        let calls = executor.calls();
+        assert_eq!(calls.len(), 1);
+        assert_eq!(calls[0].graph, graph_str);
+        assert_eq!(calls[0].args, vec![CommandArg::Format(Format::Svg)]);
+    }
 
     #[test]
     #[cfg(feature = "graphviz-exec")]
     fn exec_test() {
-        use std::{fs, process::Command};
+        use std::fs;
 
         use crate::{
             attributes::{color_name, shape, NodeAttributes},
-            cmd::{CommandArg, Format},
+            cmd::{self, CommandArg, Format},
             exec,
         };
 
@@ -242,16 +538,11 @@ mod tests {
         let mut ctx = PrinterContext::default();
         assert_eq!(graph_str, g.print(&mut ctx));
 
-        let child = Command::new("dot")
-            .arg("-V")
-            .output()
-            .expect("dot command failed to start");
-
-        let output = String::from_utf8_lossy(&child.stderr);
-        let version = output
-            .strip_prefix("dot - ")
-            .and_then(|v| v.strip_suffix(LS))
-            .expect("the version of client is unrecognizable ");
+        // the rest requires the installed graphviz
+        let version = match cmd::version() {
+            Ok(version) => version,
+            Err(_) => return,
+        };
         println!("{}", version);
 
         let out_svg = exec(g.clone(), &mut ctx, vec![CommandArg::Format(Format::Svg)]).unwrap();
@@ -276,13 +567,19 @@ mod tests {
 
     #[test]
     #[cfg(feature = "graphviz-exec")]
+    #[allow(clippy::clone_on_copy)]
     fn output_exec_from_test() {
         use crate::{
             attributes::{color_name, shape, NodeAttributes},
-            cmd::Format,
+            cmd::{self, Format},
             exec_dot,
         };
 
+        if cmd::version().is_err() {
+            // the graphviz is not installed
+            return;
+        }
+
         let g = graph!(id!("id");
              node!("nod"),
              subgraph!("sb";
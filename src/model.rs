@@ -0,0 +1,1024 @@
+//! The semantic model of the graph built from the [Graph] structure.
+//!
+//! The [Graph] keeps the statements as they are written and the same node can be declared
+//! implicitly in the edges and redeclared in several subgraphs. The [GraphModel] resolves
+//! the statements according to the dot rules:
+//!  - a node is created at the first mention and gets the `node [..]` defaults in effect at that moment
+//!  - every edge gets the `edge [..]` defaults in effect, the chains and the subgraph ends are expanded
+//!  - a subgraph inherits the defaults and the graph attributes of the parent at the moment
+//!    of its creation and the subgraphs with the same name in the same parent are merged
+//!  - a node or an edge belongs to the subgraph where it is mentioned and to all its ancestors
+//!  - the edges with the same ends are merged in the strict graph
+//!
+//! The model provides the lookup of the nodes by the id, the adjacency, the membership in the subgraphs
+//! and can be converted back to the [Graph] with the effective attributes.
+//! Every effective value keeps the [Source] statement, see [resolve].
+//!
+//! # Example:
+//! ```rust
+//! use graphviz_rust::{model::GraphModel, parse};
+//!
+//! let g = parse(
+//!     r#"digraph {
+//!         node [color=red]
+//!         subgraph cluster_a { node [shape=box] a -> b }
+//!         a -> c [label=x]
+//!     }"#,
+//! )
+//! .unwrap();
+//! let model = GraphModel::new(&g);
+//! let a = model.node_index("a").unwrap();
+//! let b = model.node("b").unwrap();
+//! assert_eq!(b.attributes.value("shape").as_deref(), Some("box"));
+//! assert_eq!(b.attributes.value("color").as_deref(), Some("red"));
+//! assert_eq!(model.node("c").unwrap().attributes.value("shape"), None);
+//! assert_eq!(model.successors(a).count(), 2);
+//! assert_eq!(model.node_subgraphs(a).next().unwrap().name(), "cluster_a");
+//! ```
+use std::{
+    collections::HashMap,
+    fmt::{Display, Formatter},
+};
+
+use dot_structures::{
+    Attribute, Edge, EdgeTy, Graph, GraphAttributes, Id, Node, NodeId, Port, Stmt, Subgraph, Vertex,
+};
+
+/// The statement setting the value of the attribute.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Source {
+    /// the indexes of the statements from the graph down to the statement setting the value.
+    /// The subgraph in the edge is addressed by the index of the edge statement
+    /// followed by the index of the vertex.
+    pub path: Vec<usize>,
+    pub kind: SourceKind,
+}
+
+/// How the value of the attribute gets to the element.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SourceKind {
+    /// the attribute is set on the element itself
+    Explicit,
+    /// the `node [..]` or `edge [..]` default in effect at the creation of the element
+    Default,
+    /// the graph attribute of the parent graph at the creation of the subgraph
+    Inherited,
+}
+
+impl Display for Source {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let path: Vec<String> = self.path.iter().map(|i| i.to_string()).collect();
+        let kind = match self.kind {
+            SourceKind::Explicit => "explicit",
+            SourceKind::Default => "default",
+            SourceKind::Inherited => "inherited",
+        };
+        write!(f, "statement {} ({})", path.join("."), kind)
+    }
+}
+
+/// The attributes in the order of the first declaration
+/// where the subsequent declarations override the values.
+///
+/// The attributes of the [GraphModel] keep the [Source] of every value.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct AttributeMap(Vec<(Attribute, Option<Source>)>);
+
+impl AttributeMap {
+    /// Returns the value of the attribute.
+    pub fn get(&self, key: &str) -> Option<&Id> {
+        self.entry(key).map(|(a, _)| &a.1)
+    }
+
+    /// Returns the statement setting the value of the attribute.
+    pub fn source(&self, key: &str) -> Option<&Source> {
+        self.entry(key).and_then(|(_, s)| s.as_ref())
+    }
+
+    fn entry(&self, key: &str) -> Option<&(Attribute, Option<Source>)> {
+        self.0.iter().find(|(a, _)| id_str(&a.0) == key)
+    }
+
+    /// Returns the value of the attribute without quotes.
+    pub fn value(&self, key: &str) -> Option<String> {
+        self.get(key).map(id_str)
+    }
+
+    /// Sets the value of the attribute keeping the position of the previous one.
+    pub fn set(&mut self, attr: Attribute) {
+        self.insert(attr, None)
+    }
+
+    pub fn extend<I: IntoIterator<Item = Attribute>>(&mut self, attrs: I) {
+        attrs.into_iter().for_each(|a| self.set(a))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Attribute> {
+        self.0.iter().map(|(a, _)| a)
+    }
+
+    /// Returns the attributes with the statements setting the values.
+    pub fn iter_with_sources(&self) -> impl Iterator<Item = (&Attribute, Option<&Source>)> {
+        self.0.iter().map(|(a, s)| (a, s.as_ref()))
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn to_vec(&self) -> Vec<Attribute> {
+        self.iter().cloned().collect()
+    }
+
+    fn insert(&mut self, attr: Attribute, source: Option<Source>) {
+        let key = id_str(&attr.0);
+        match self.0.iter_mut().find(|(a, _)| id_str(&a.0) == key) {
+            Some(entry) => *entry = (attr, source),
+            None => self.0.push((attr, source)),
+        }
+    }
+
+    fn insert_all(&mut self, attrs: &[Attribute], path: &[usize]) {
+        for a in attrs {
+            let source = Source {
+                path: path.to_vec(),
+                kind: SourceKind::Explicit,
+            };
+            self.insert(a.clone(), Some(source));
+        }
+    }
+
+    /// Returns the copy of the attributes with the given kind of the sources.
+    fn with_kind(&self, kind: SourceKind) -> AttributeMap {
+        let mut map = self.clone();
+        map.0
+            .iter_mut()
+            .filter_map(|(_, s)| s.as_mut())
+            .for_each(|s| s.kind = kind);
+        map
+    }
+}
+
+/// The element of the [GraphModel].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Element {
+    Graph,
+    /// the index in [GraphModel::subgraphs]
+    Subgraph(usize),
+    /// the index in [GraphModel::nodes]
+    Node(usize),
+    /// the index in [GraphModel::edges]
+    Edge(usize),
+}
+
+/// The effective attributes of the element with the statements setting their values.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Resolved {
+    pub element: Element,
+    /// the name of the graph, the subgraph or the node and `tail -> head` for the edge
+    pub name: String,
+    pub attributes: Vec<(Attribute, Source)>,
+}
+
+/// Resolves the effective attributes of all elements of the graph according to the dot rules
+/// and reports the statement setting every value.
+///
+/// # Example:
+/// ```rust
+/// use graphviz_rust::{
+///     model::{resolve, Element, SourceKind},
+///     parse,
+/// };
+///
+/// let g = parse("digraph { node [color=red] subgraph s { a } }").unwrap();
+/// let resolved = resolve(&g);
+/// let a = resolved.iter().find(|r| r.element == Element::Node(0)).unwrap();
+/// let (attr, source) = &a.attributes[0];
+/// assert_eq!(attr.1.to_string(), "red");
+/// assert_eq!(source.path, vec![0]);
+/// assert_eq!(source.kind, SourceKind::Default);
+/// ```
+pub fn resolve(graph: &Graph) -> Vec<Resolved> {
+    GraphModel::new(graph).resolve()
+}
+
+impl From<Vec<Attribute>> for AttributeMap {
+    fn from(attrs: Vec<Attribute>) -> Self {
+        let mut map = AttributeMap::default();
+        map.extend(attrs);
+        map
+    }
+}
+
+/// The graph with the resolved nodes, edges and subgraphs.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GraphModel {
+    pub id: Id,
+    pub strict: bool,
+    pub directed: bool,
+    /// the graph attributes
+    pub attributes: AttributeMap,
+    /// the `node [..]` defaults at the end of the graph
+    pub node_defaults: AttributeMap,
+    /// the `edge [..]` defaults at the end of the graph
+    pub edge_defaults: AttributeMap,
+    /// the nodes in the order of creation
+    pub nodes: Vec<NodeModel>,
+    /// the edges in the order of creation
+    pub edges: Vec<EdgeModel>,
+    /// all subgraphs in the order of creation
+    pub subgraphs: Vec<SubgraphModel>,
+    /// the indexes of the nodes by the names
+    index: HashMap<String, usize>,
+    /// the indexes of the edges starting at every node
+    out_edges: Vec<Vec<usize>>,
+    /// the indexes of the edges ending at every node
+    in_edges: Vec<Vec<usize>>,
+    /// the indexes of the subgraphs by the parents and the names
+    subgraph_index: HashMap<(Option<usize>, String), usize>,
+    /// the indexes of the first subgraphs with the names
+    subgraph_names: HashMap<String, usize>,
+    /// the indexes of the edges by the ends, it is filled for the strict graph only
+    edge_index: HashMap<(usize, usize), usize>,
+}
+
+/// The node with the effective attributes.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NodeModel {
+    pub id: Id,
+    pub attributes: AttributeMap,
+    /// the indexes of the subgraphs containing the node in [GraphModel::subgraphs]
+    pub subgraphs: Vec<usize>,
+}
+
+impl NodeModel {
+    /// Returns the id without quotes.
+    pub fn name(&self) -> String {
+        id_str(&self.id)
+    }
+}
+
+/// The edge with the effective attributes.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EdgeModel {
+    /// the index of the tail in [GraphModel::nodes]
+    pub tail: usize,
+    pub tail_port: Option<Port>,
+    /// the index of the head in [GraphModel::nodes]
+    pub head: usize,
+    pub head_port: Option<Port>,
+    pub attributes: AttributeMap,
+    /// the indexes of the subgraphs containing the edge in [GraphModel::subgraphs]
+    pub subgraphs: Vec<usize>,
+}
+
+/// The subgraph with the effective attributes.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SubgraphModel {
+    pub id: Id,
+    /// the index of the parent subgraph or none for the subgraphs of the graph
+    pub parent: Option<usize>,
+    /// the graph attributes including the ones inherited from the parent
+    pub attributes: AttributeMap,
+    /// the `node [..]` defaults at the end of the subgraph
+    pub node_defaults: AttributeMap,
+    /// the `edge [..]` defaults at the end of the subgraph
+    pub edge_defaults: AttributeMap,
+    /// the indexes of the nodes including the nodes of the nested subgraphs
+    pub nodes: Vec<usize>,
+    /// the indexes of the edges including the edges of the nested subgraphs
+    pub edges: Vec<usize>,
+    /// the indexes of the direct children
+    pub subgraphs: Vec<usize>,
+}
+
+impl SubgraphModel {
+    /// Returns the id without quotes. It is empty for the anonymous subgraphs.
+    pub fn name(&self) -> String {
+        id_str(&self.id)
+    }
+
+    pub fn is_cluster(&self) -> bool {
+        self.name().starts_with("cluster")
+    }
+}
+
+impl From<&Graph> for GraphModel {
+    fn from(graph: &Graph) -> Self {
+        GraphModel::new(graph)
+    }
+}
+
+impl From<&GraphModel> for Graph {
+    fn from(model: &GraphModel) -> Self {
+        model.to_graph()
+    }
+}
+
+impl GraphModel {
+    /// Builds the model from the graph.
+    pub fn new(graph: &Graph) -> Self {
+        let (id, strict, directed, stmts) = match graph {
+            Graph::Graph { id, strict, stmts } => (id, *strict, false, stmts),
+            Graph::DiGraph { id, strict, stmts } => (id, *strict, true, stmts),
+        };
+        let mut model = GraphModel {
+            id: id.clone(),
+            strict,
+            directed,
+            attributes: AttributeMap::default(),
+            node_defaults: AttributeMap::default(),
+            edge_defaults: AttributeMap::default(),
+            nodes: vec![],
+            edges: vec![],
+            subgraphs: vec![],
+            index: HashMap::new(),
+            out_edges: vec![],
+            in_edges: vec![],
+            subgraph_index: HashMap::new(),
+            subgraph_names: HashMap::new(),
+            edge_index: HashMap::new(),
+        };
+        model.stmts(stmts, None, &[]);
+        model
+    }
+
+    /// Finds the node by the id without quotes.
+    pub fn node(&self, id: &str) -> Option<&NodeModel> {
+        self.node_index(id).map(|idx| &self.nodes[idx])
+    }
+
+    /// Returns the index of the node with the given id without quotes.
+    pub fn node_index(&self, id: &str) -> Option<usize> {
+        self.index.get(id).copied()
+    }
+
+    /// Finds the first subgraph with the given id without quotes.
+    pub fn subgraph(&self, id: &str) -> Option<&SubgraphModel> {
+        self.subgraph_names.get(id).map(|idx| &self.subgraphs[*idx])
+    }
+
+    pub fn clusters(&self) -> impl Iterator<Item = &SubgraphModel> {
+        self.subgraphs.iter().filter(|s| s.is_cluster())
+    }
+
+    /// Returns the edges starting at the node.
+    pub fn out_edges(&self, node: usize) -> impl Iterator<Item = &EdgeModel> {
+        self.out_edges[node].iter().map(|e| &self.edges[*e])
+    }
+
+    /// Returns the edges ending at the node.
+    pub fn in_edges(&self, node: usize) -> impl Iterator<Item = &EdgeModel> {
+        self.in_edges[node].iter().map(|e| &self.edges[*e])
+    }
+
+    /// Returns the heads of the edges starting at the node.
+    pub fn successors(&self, node: usize) -> impl Iterator<Item = usize> + '_ {
+        self.out_edges(node).map(|e| e.head)
+    }
+
+    /// Returns the tails of the edges ending at the node.
+    pub fn predecessors(&self, node: usize) -> impl Iterator<Item = usize> + '_ {
+        self.in_edges(node).map(|e| e.tail)
+    }
+
+    /// Returns the nodes connected with the node regardless of the direction.
+    pub fn neighbors(&self, node: usize) -> impl Iterator<Item = usize> + '_ {
+        // the loops are taken from the outgoing edges only
+        self.successors(node)
+            .chain(self.predecessors(node).filter(move |t| *t != node))
+    }
+
+    /// Returns the subgraphs containing the node.
+    pub fn node_subgraphs(&self, node: usize) -> impl Iterator<Item = &SubgraphModel> {
+        self.nodes[node]
+            .subgraphs
+            .iter()
+            .map(|s| &self.subgraphs[*s])
+    }
+
+    /// Returns the effective attributes of all elements with the statements setting the values.
+    pub fn resolve(&self) -> Vec<Resolved> {
+        let resolved = |element, name: String, attrs: &AttributeMap| Resolved {
+            element,
+            name,
+            attributes: attrs
+                .iter_with_sources()
+                .filter_map(|(a, s)| s.map(|s| (a.clone(), s.clone())))
+                .collect(),
+        };
+        let mut result = vec![resolved(Element::Graph, id_str(&self.id), &self.attributes)];
+        for (idx, sub) in self.subgraphs.iter().enumerate() {
+            result.push(resolved(
+                Element::Subgraph(idx),
+                sub.name(),
+                &sub.attributes,
+            ));
+        }
+        for (idx, node) in self.nodes.iter().enumerate() {
+            result.push(resolved(Element::Node(idx), node.name(), &node.attributes));
+        }
+        let op = if self.directed { "->" } else { "--" };
+        for (idx, edge) in self.edges.iter().enumerate() {
+            let name = format!(
+                "{} {} {}",
+                self.nodes[edge.tail].name(),
+                op,
+                self.nodes[edge.head].name()
+            );
+            result.push(resolved(Element::Edge(idx), name, &edge.attributes));
+        }
+        result
+    }
+
+    /// Converts the model back to the graph.
+    ///
+    /// The nodes and edges are declared with the effective attributes,
+    /// the subgraphs keep the attributes differing from the parent and the membership.
+    pub fn to_graph(&self) -> Graph {
+        let mut stmts = vec![];
+        if !self.attributes.is_empty() {
+            stmts.push(Stmt::GAttribute(GraphAttributes::Graph(
+                self.attributes.to_vec(),
+            )));
+        }
+        for node in self.nodes.iter() {
+            stmts.push(Stmt::Node(Node::new(
+                NodeId(node.id.clone(), None),
+                node.attributes.to_vec(),
+            )));
+        }
+        for (idx, sub) in self.subgraphs.iter().enumerate() {
+            if sub.parent.is_none() {
+                stmts.push(Stmt::Subgraph(
+                    self.subgraph_to_graph(idx, &self.attributes),
+                ));
+            }
+        }
+        for edge in self.edges.iter().filter(|e| e.subgraphs.is_empty()) {
+            stmts.push(Stmt::Edge(self.edge_to_graph(edge)));
+        }
+        let id = self.id.clone();
+        let strict = self.strict;
+        if self.directed {
+            Graph::DiGraph { id, strict, stmts }
+        } else {
+            Graph::Graph { id, strict, stmts }
+        }
+    }
+
+    fn subgraph_to_graph(&self, idx: usize, parent: &AttributeMap) -> Subgraph {
+        let sub = &self.subgraphs[idx];
+        let mut stmts = vec![];
+        let mut attrs: Vec<Attribute> = sub
+            .attributes
+            .iter()
+            .filter(|a| parent.get(&id_str(&a.0)) != Some(&a.1))
+            .cloned()
+            .collect();
+        // the inherited attributes absent in the subgraph are reset to the default
+        for a in parent.iter() {
+            if sub.attributes.get(&id_str(&a.0)).is_none() {
+                attrs.push(Attribute(a.0.clone(), Id::Escaped("\"\"".to_string())));
+            }
+        }
+        if !attrs.is_empty() {
+            stmts.push(Stmt::GAttribute(GraphAttributes::Graph(attrs)));
+        }
+        let is_leaf = |memberships: &[usize]| {
+            memberships.contains(&idx)
+                && !sub.subgraphs.iter().any(|c| {
+                    memberships
+                        .iter()
+                        .any(|m| *m == *c || self.is_ancestor(*c, *m))
+                })
+        };
+        for node in self.nodes.iter().filter(|n| is_leaf(&n.subgraphs)) {
+            stmts.push(Stmt::Node(Node::new(NodeId(node.id.clone(), None), vec![])));
+        }
+        for child in sub.subgraphs.iter() {
+            stmts.push(Stmt::Subgraph(
+                self.subgraph_to_graph(*child, &sub.attributes),
+            ));
+        }
+        for edge in self.edges.iter().filter(|e| is_leaf(&e.subgraphs)) {
+            stmts.push(Stmt::Edge(self.edge_to_graph(edge)));
+        }
+        Subgraph {
+            id: sub.id.clone(),
+            stmts,
+        }
+    }
+
+    fn edge_to_graph(&self, edge: &EdgeModel) -> Edge {
+        let tail = NodeId(self.nodes[edge.tail].id.clone(), edge.tail_port.clone());
+        let head = NodeId(self.nodes[edge.head].id.clone(), edge.head_port.clone());
+        Edge {
+            ty: EdgeTy::Pair(Vertex::N(tail), Vertex::N(head)),
+            attributes: edge.attributes.to_vec(),
+        }
+    }
+
+    /// Checks if the subgraph `anc` is an ancestor of the subgraph `sub`.
+    fn is_ancestor(&self, anc: usize, sub: usize) -> bool {
+        let mut parent = self.subgraphs[sub].parent;
+        while let Some(p) = parent {
+            if p == anc {
+                return true;
+            }
+            parent = self.subgraphs[p].parent;
+        }
+        false
+    }
+
+    /// Returns the subgraph and all its ancestors.
+    fn scope_chain(&self, scope: Option<usize>) -> Vec<usize> {
+        let mut chain = vec![];
+        let mut current = scope;
+        while let Some(s) = current {
+            chain.push(s);
+            current = self.subgraphs[s].parent;
+        }
+        chain.reverse();
+        chain
+    }
+
+    fn defaults(&self, scope: Option<usize>) -> (&AttributeMap, &AttributeMap) {
+        match scope {
+            Some(s) => (
+                &self.subgraphs[s].node_defaults,
+                &self.subgraphs[s].edge_defaults,
+            ),
+            None => (&self.node_defaults, &self.edge_defaults),
+        }
+    }
+
+    /// Processes the statements where the `path` is the path of the enclosing statement.
+    fn stmts(&mut self, stmts: &[Stmt], scope: Option<usize>, path: &[usize]) -> Vec<usize> {
+        let mut nodes = vec![];
+        for (i, stmt) in stmts.iter().enumerate() {
+            let path = [path, &[i]].concat();
+            match stmt {
+                Stmt::Node(node) => {
+                    let idx = self.add_node(&node.id.0, scope);
+                    self.nodes[idx]
+                        .attributes
+                        .insert_all(&node.attributes, &path);
+                    nodes.push(idx);
+                }
+                Stmt::Subgraph(sub) => nodes.extend(self.add_subgraph(sub, scope, &path)),
+                Stmt::Attribute(attr) => self
+                    .graph_attrs(scope)
+                    .insert_all(std::slice::from_ref(attr), &path),
+                Stmt::GAttribute(GraphAttributes::Graph(attrs)) => {
+                    self.graph_attrs(scope).insert_all(attrs, &path)
+                }
+                Stmt::GAttribute(GraphAttributes::Node(attrs)) => {
+                    let defaults = match scope {
+                        Some(s) => &mut self.subgraphs[s].node_defaults,
+                        None => &mut self.node_defaults,
+                    };
+                    defaults.insert_all(attrs, &path)
+                }
+                Stmt::GAttribute(GraphAttributes::Edge(attrs)) => {
+                    let defaults = match scope {
+                        Some(s) => &mut self.subgraphs[s].edge_defaults,
+                        None => &mut self.edge_defaults,
+                    };
+                    defaults.insert_all(attrs, &path)
+                }
+                Stmt::Edge(edge) => nodes.extend(self.add_edges(edge, scope, &path)),
+            }
+        }
+        nodes
+    }
+
+    fn graph_attrs(&mut self, scope: Option<usize>) -> &mut AttributeMap {
+        match scope {
+            Some(s) => &mut self.subgraphs[s].attributes,
+            None => &mut self.attributes,
+        }
+    }
+
+    /// Finds or creates the node and adds it to the subgraphs of the scope.
+    fn add_node(&mut self, id: &Id, scope: Option<usize>) -> usize {
+        let name = id_str(id);
+        let idx = match self.node_index(&name) {
+            Some(idx) => idx,
+            None => {
+                let attributes = self.defaults(scope).0.with_kind(SourceKind::Default);
+                self.nodes.push(NodeModel {
+                    id: id.clone(),
+                    attributes,
+                    subgraphs: vec![],
+                });
+                self.index.insert(name, self.nodes.len() - 1);
+                self.out_edges.push(vec![]);
+                self.in_edges.push(vec![]);
+                self.nodes.len() - 1
+            }
+        };
+        for s in self.scope_chain(scope) {
+            if !self.nodes[idx].subgraphs.contains(&s) {
+                self.nodes[idx].subgraphs.push(s);
+                self.subgraphs[s].nodes.push(idx);
+            }
+        }
+        idx
+    }
+
+    /// Finds or creates the subgraph and processes its statements returning the mentioned nodes.
+    fn add_subgraph(&mut self, sub: &Subgraph, scope: Option<usize>, path: &[usize]) -> Vec<usize> {
+        let name = id_str(&sub.id);
+        let existing = if name.is_empty() {
+            None
+        } else {
+            self.subgraph_index.get(&(scope, name.clone())).copied()
+        };
+        let idx = match existing {
+            Some(idx) => idx,
+            None => {
+                let (node_defaults, edge_defaults) = self.defaults(scope);
+                let attributes = match scope {
+                    Some(s) => &self.subgraphs[s].attributes,
+                    None => &self.attributes,
+                };
+                let attributes = attributes.with_kind(SourceKind::Inherited);
+                let model = SubgraphModel {
+                    id: sub.id.clone(),
+                    parent: scope,
+                    attributes,
+                    node_defaults: node_defaults.clone(),
+                    edge_defaults: edge_defaults.clone(),
+                    nodes: vec![],
+                    edges: vec![],
+                    subgraphs: vec![],
+                };
+                self.subgraphs.push(model);
+                let idx = self.subgraphs.len() - 1;
+                if !name.is_empty() {
+                    self.subgraph_names.entry(name.clone()).or_insert(idx);
+                    self.subgraph_index.insert((scope, name), idx);
+                }
+                if let Some(s) = scope {
+                    self.subgraphs[s].subgraphs.push(idx);
+                }
+                idx
+            }
+        };
+        self.stmts(&sub.stmts, Some(idx), path)
+    }
+
+    /// Returns the nodes of the vertex with the ports.
+    fn add_vertex(
+        &mut self,
+        vertex: &Vertex,
+        scope: Option<usize>,
+        path: &[usize],
+    ) -> Vec<(usize, Option<Port>)> {
+        match vertex {
+            Vertex::N(NodeId(id, port)) => vec![(self.add_node(id, scope), port.clone())],
+            Vertex::S(sub) => {
+                let mut nodes = vec![];
+                for n in self.add_subgraph(sub, scope, path) {
+                    if !nodes.contains(&(n, None)) {
+                        nodes.push((n, None));
+                    }
+                }
+                nodes
+            }
+        }
+    }
+
+    fn add_edges(&mut self, edge: &Edge, scope: Option<usize>, path: &[usize]) -> Vec<usize> {
+        let vertices = match &edge.ty {
+            EdgeTy::Pair(tail, head) => vec![tail, head],
+            EdgeTy::Chain(vertices) => vertices.iter().collect(),
+        };
+        let ends: Vec<_> = vertices
+            .into_iter()
+            .enumerate()
+            .map(|(j, v)| self.add_vertex(v, scope, &[path, &[j]].concat()))
+            .collect();
+        for pair in ends.windows(2) {
+            for (tail, tail_port) in pair[0].iter() {
+                for (head, head_port) in pair[1].iter() {
+                    self.add_edge(
+                        (*tail, tail_port.clone()),
+                        (*head, head_port.clone()),
+                        &edge.attributes,
+                        scope,
+                        path,
+                    );
+                }
+            }
+        }
+        ends.into_iter()
+            .flat_map(|e| e.into_iter().map(|(n, _)| n))
+            .collect()
+    }
+
+    fn add_edge(
+        &mut self,
+        (tail, tail_port): (usize, Option<Port>),
+        (head, head_port): (usize, Option<Port>),
+        attrs: &[Attribute],
+        scope: Option<usize>,
+        path: &[usize],
+    ) {
+        // the undirected edges are looked up by the ordered ends
+        let key = if self.directed {
+            (tail, head)
+        } else {
+            (tail.min(head), tail.max(head))
+        };
+        let existing = if self.strict {
+            self.edge_index.get(&key).copied()
+        } else {
+            None
+        };
+        let idx = match existing {
+            Some(idx) => idx,
+            None => {
+                let attributes = self.defaults(scope).1.with_kind(SourceKind::Default);
+                self.edges.push(EdgeModel {
+                    tail,
+                    tail_port,
+                    head,
+                    head_port,
+                    attributes,
+                    subgraphs: vec![],
+                });
+                let idx = self.edges.len() - 1;
+                self.out_edges[tail].push(idx);
+                self.in_edges[head].push(idx);
+                if self.strict {
+                    self.edge_index.insert(key, idx);
+                }
+                idx
+            }
+        };
+        self.edges[idx].attributes.insert_all(attrs, path);
+        for s in self.scope_chain(scope) {
+            if !self.edges[idx].subgraphs.contains(&s) {
+                self.edges[idx].subgraphs.push(s);
+                self.subgraphs[s].edges.push(idx);
+            }
+        }
+    }
+}
+
+/// Returns the value of the id removing the quotes, the escaped quotes and the line continuations.
+pub(crate) fn id_str(id: &Id) -> String {
+    match id {
+        Id::Escaped(v) => v
+            .strip_prefix('"')
+            .and_then(|v| v.strip_suffix('"'))
+            .unwrap_or(v)
+            .replace("\\\r\n", "")
+            .replace("\\\n", "")
+            .replace("\\\"", "\""),
+        Id::Html(v) | Id::Plain(v) => v.clone(),
+        Id::Anonymous(_) => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use dot_generator::*;
+    use dot_structures::*;
+
+    use crate::{
+        model::{resolve, AttributeMap, Element, GraphModel, Source, SourceKind},
+        parse,
+    };
+
+    const GRAPH: &str = r#"
+    digraph g {
+        label=top
+        node [color=red]
+        a
+        subgraph cluster_x {
+            node [shape=box]
+            edge [style=dashed]
+            b -> c -> {d e}
+            subgraph cluster_y { a; f [color=blue] }
+        }
+        node [color=green]
+        g -> b [label=x]
+        subgraph cluster_x { h }
+        "a" [fontsize=10]
+    }
+    "#;
+
+    fn model() -> GraphModel {
+        GraphModel::new(&parse(GRAPH).unwrap())
+    }
+
+    #[test]
+    fn attribute_map_test() {
+        let mut map = AttributeMap::from(vec![attr!("a", "1"), attr!("b", "2")]);
+        map.set(attr!("a", esc "3"));
+        assert_eq!(map.len(), 2);
+        assert_eq!(map.get("a"), Some(&id!(esc "3")));
+        assert_eq!(map.value("a").as_deref(), Some("3"));
+        assert_eq!(map.to_vec()[1], attr!("b", "2"));
+        assert_eq!(map.get("c"), None);
+    }
+
+    #[test]
+    fn nodes_test() {
+        let model = model();
+        let names: Vec<String> = model.nodes.iter().map(|n| n.name()).collect();
+        assert_eq!(names, vec!["a", "b", "c", "d", "e", "f", "g", "h"]);
+
+        let a = model.node("a").unwrap();
+        assert_eq!(a.attributes.value("color").as_deref(), Some("red"));
+        assert_eq!(a.attributes.value("shape"), None);
+        assert_eq!(a.attributes.value("fontsize").as_deref(), Some("10"));
+
+        let d = model.node("d").unwrap();
+        assert_eq!(d.attributes.value("shape").as_deref(), Some("box"));
+        assert_eq!(
+            model
+                .node("f")
+                .unwrap()
+                .attributes
+                .value("color")
+                .as_deref(),
+            Some("blue")
+        );
+        assert_eq!(
+            model
+                .node("g")
+                .unwrap()
+                .attributes
+                .value("color")
+                .as_deref(),
+            Some("green")
+        );
+        assert_eq!(
+            model
+                .node("h")
+                .unwrap()
+                .attributes
+                .value("shape")
+                .as_deref(),
+            Some("box")
+        );
+    }
+
+    #[test]
+    fn edges_test() {
+        let model = model();
+        let idx = |n: &str| model.node_index(n).unwrap();
+        assert_eq!(model.edges.len(), 4);
+        let succ: Vec<usize> = model.successors(idx("c")).collect();
+        assert_eq!(succ, vec![idx("d"), idx("e")]);
+        let pred: Vec<usize> = model.predecessors(idx("b")).collect();
+        assert_eq!(pred, vec![idx("g")]);
+        assert_eq!(model.neighbors(idx("b")).count(), 2);
+
+        let cd = model.out_edges(idx("c")).next().unwrap();
+        assert_eq!(cd.attributes.value("style").as_deref(), Some("dashed"));
+        let gb = model.in_edges(idx("b")).next().unwrap();
+        assert_eq!(gb.attributes.value("style"), None);
+        assert_eq!(gb.attributes.value("label").as_deref(), Some("x"));
+
+        let g = parse("digraph { a -> a; a -> b; c -> a }").unwrap();
+        let model = GraphModel::new(&g);
+        let neighbors: Vec<usize> = model.neighbors(0).collect();
+        assert_eq!(neighbors, vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn subgraphs_test() {
+        let model = model();
+        assert_eq!(model.subgraphs.len(), 3);
+        let x = model.subgraph("cluster_x").unwrap();
+        assert_eq!(x.attributes.value("label").as_deref(), Some("top"));
+        assert_eq!(x.subgraphs.len(), 2);
+        assert_eq!(x.nodes.len(), 7);
+        assert_eq!(x.edges.len(), 3);
+
+        let y = model.subgraph("cluster_y").unwrap();
+        assert_eq!(y.parent, Some(0));
+        let a: Vec<String> = model
+            .node_subgraphs(model.node_index("a").unwrap())
+            .map(|s| s.name())
+            .collect();
+        assert_eq!(a, vec!["cluster_x", "cluster_y"]);
+        assert_eq!(model.clusters().count(), 2);
+    }
+
+    #[test]
+    fn strict_test() {
+        let g =
+            parse("strict graph { a -- b [color=red]; b -- a [style=bold]; a -- b -- c }").unwrap();
+        let model = GraphModel::new(&g);
+        assert_eq!(model.edges.len(), 2);
+        assert_eq!(model.edges[0].attributes.len(), 2);
+
+        let g = parse("graph { a -- b; b -- a }").unwrap();
+        assert_eq!(GraphModel::new(&g).edges.len(), 2);
+    }
+
+    #[test]
+    fn to_graph_test() {
+        let model = model();
+        let graph = model.to_graph();
+        let restored = GraphModel::new(&graph);
+        assert_eq!(restored.nodes.len(), model.nodes.len());
+        for (r, m) in restored.nodes.iter().zip(model.nodes.iter()) {
+            assert_eq!(r.id, m.id);
+            assert_eq!(r.attributes.to_vec(), m.attributes.to_vec());
+            assert_eq!(r.subgraphs, m.subgraphs);
+        }
+        assert_eq!(restored.attributes.to_vec(), model.attributes.to_vec());
+        assert_eq!(restored.subgraphs.len(), model.subgraphs.len());
+        for (r, m) in restored.subgraphs.iter().zip(model.subgraphs.iter()) {
+            assert_eq!(r.id, m.id);
+            assert_eq!(r.attributes.to_vec(), m.attributes.to_vec());
+            assert_eq!(r.nodes.len(), m.nodes.len());
+            assert_eq!(r.edges.len(), m.edges.len());
+        }
+        assert_eq!(restored.edges.len(), model.edges.len());
+        for e in model.edges.iter() {
+            assert!(restored.edges.iter().any(|r| r.tail == e.tail
+                && r.head == e.head
+                && r.attributes.to_vec() == e.attributes.to_vec()));
+        }
+
+        let g = graph!(id!("g"); edge!(node_id!("a") => node_id!("b")));
+        assert_eq!(
+            GraphModel::new(&g).to_graph(),
+            graph!(id!("g");
+                node!("a"),
+                node!("b"),
+                edge!(node_id!("a") => node_id!("b"))
+            )
+        );
+    }
+
+    #[test]
+    fn source_test() {
+        let model = model();
+        let a = model.node("a").unwrap();
+        assert_eq!(
+            a.attributes.source("color"),
+            Some(&Source {
+                path: vec![1],
+                kind: SourceKind::Default
+            })
+        );
+        let fontsize = a.attributes.source("fontsize").unwrap();
+        assert_eq!(fontsize.path, vec![7]);
+        assert_eq!(fontsize.kind, SourceKind::Explicit);
+
+        let d = model.node("d").unwrap();
+        assert_eq!(d.attributes.source("shape").unwrap().path, vec![3, 0]);
+        let f = model.node("f").unwrap();
+        assert_eq!(f.attributes.source("color").unwrap().path, vec![3, 3, 1]);
+
+        let y = model.subgraph("cluster_y").unwrap();
+        assert_eq!(
+            y.attributes.source("label"),
+            Some(&Source {
+                path: vec![0],
+                kind: SourceKind::Inherited
+            })
+        );
+
+        let cd = model
+            .out_edges(model.node_index("c").unwrap())
+            .next()
+            .unwrap();
+        assert_eq!(
+            cd.attributes.source("style").unwrap().to_string(),
+            "statement 3.1 (default)"
+        );
+    }
+
+    #[test]
+    fn resolve_test() {
+        let g = parse("digraph g { a -> subgraph s { b c [color=red] } [style=bold] }").unwrap();
+        let resolved = resolve(&g);
+        assert_eq!(resolved.len(), 1 + 1 + 3 + 2);
+        assert_eq!(resolved[0].element, Element::Graph);
+        assert_eq!(resolved[0].name, "g");
+
+        let c = &resolved[4];
+        assert_eq!(c.element, Element::Node(2));
+        assert_eq!(c.attributes[0].0, attr!("color", "red"));
+        assert_eq!(c.attributes[0].1.path, vec![0, 1, 1]);
+
+        let ac = &resolved[6];
+        assert_eq!(ac.name, "a -> c");
+        assert_eq!(ac.attributes[0].0, attr!("style", "bold"));
+        assert_eq!(ac.attributes[0].1.path, vec![0]);
+    }
+}
@@ -2,7 +2,13 @@
 //!
 //! The grammar can be viewed in `/grammar/dot.pest`
 //!
+//! The html labels are kept as [Id::Html] strings with the outer brackets,
+//! the typed tree of the label is built by [Label::from_id](crate::attributes::html::Label::from_id)
+//! or by [parse_labels](crate::attributes::html::parse_labels) keeping the positions in the source.
+//!
 //! ['notation']: https://graphviz.org/doc/info/lang.html
+use std::ops::Range;
+
 use dot_structures::*;
 use pest::{
     error::Error,
@@ -22,6 +28,24 @@ pub(crate) fn parse(dot: &str) -> Result<Graph, String> {
         .map_err(|v| v.to_string())
 }
 
+/// Returns the attributes with the html values in the order of appearance
+/// along with the byte ranges of the values in the source.
+pub(crate) fn parse_html_attributes(dot: &str) -> Result<Vec<(Attribute, Range<usize>)>, String> {
+    let file = do_parse(dot, Rule::file).map_err(|v| v.to_string())?;
+    let attrs = file
+        .flatten()
+        .filter(|p| p.as_rule() == Rule::bare_attr)
+        .filter_map(|p| {
+            let range = p.clone().into_inner().nth(1)?.as_span();
+            match process_bare_attr(p) {
+                attr @ Attribute(_, Id::Html(_)) => Some((attr, range.start()..range.end())),
+                _ => None,
+            }
+        })
+        .collect();
+    Ok(attrs)
+}
+
 fn down(rule: Pair<Rule>) -> Pair<Rule> {
     rule.into_inner().next().unwrap()
 }
@@ -237,7 +261,7 @@ mod test {
         Rule, Stmt, Vertex,
     };
 
-    fn _parse(input: &str, ty: Rule) -> Pair<Rule> {
+    fn _parse(input: &str, ty: Rule) -> Pair<'_, Rule> {
         match do_parse(input, ty) {
             Ok(mut r) => r.next().unwrap(),
             Err(e) => panic!("parsing err: {}", e),
@@ -306,6 +330,30 @@ mod test {
             result,
             id!(html r#"<<tr><td>address_id:!@#$%^&*()_+/.,"\| int</td></tr>>"#)
         );
+
+        let result = process_id(_parse("<<b>x</b> >", Rule::id));
+        assert_eq!(result, id!(html "<<b>x</b> >"));
+    }
+
+    #[test]
+    fn html_balanced_test() {
+        let g: Graph = parse(
+            r#"
+        graph G {
+            a [label=<x>, xlabel=<<b>y</b>>]
+            b [label=<<TABLE><TR><TD><TABLE><TR><TD>z</TD></TR></TABLE></TD></TR></TABLE>
+            >]
+        }
+        "#,
+        )
+        .unwrap();
+        assert_eq!(
+            g,
+            graph!(id!("G");
+                node!("a"; attr!("label", html "<x>"), attr!("xlabel", html "<<b>y</b>>")),
+                node!("b"; attr!("label", html "<<TABLE><TR><TD><TABLE><TR><TD>z</TD></TR></TABLE></TD></TR></TABLE>\n            >"))
+            )
+        );
     }
 
     #[test]
@@ -416,6 +464,32 @@ mod test {
         );
     }
 
+    #[test]
+    fn anonymous_subgraph_test() {
+        let result = process_stmt(_parse("{rank=same; a b}", Rule::stmt));
+        match result {
+            Stmt::Subgraph(Subgraph {
+                id: Id::Anonymous(_),
+                stmts,
+            }) => assert_eq!(stmts.len(), 3),
+            _ => panic!("the subgraph is expected"),
+        }
+
+        let result = process_edge_stmt(_parse("a -> {b c}", Rule::edge_stmt));
+        assert!(matches!(result.ty, EdgeTy::Pair(_, Vertex::S(_))));
+
+        for input in ["{a b} -> c", "subgraph {a} -> c", "subgraph s {a} -> c"] {
+            match process_stmt(_parse(input, Rule::stmt)) {
+                Stmt::Edge(Edge {
+                    ty: EdgeTy::Pair(Vertex::S(_), Vertex::N(_)),
+                    ..
+                }) => (),
+                s => panic!("the edge is expected for '{}', got {:?}", input, s),
+            }
+        }
+        assert!(parse("digraph { {a b} -> c; subgraph {d} -> {e} }").is_ok());
+    }
+
     #[test]
     fn stmt_test() {
         let result = process_stmt(_parse("a=b", Rule::stmt));
@@ -0,0 +1,263 @@
+//! Conversions between the [`petgraph`] graphs and the [Graph] structure.
+//!
+//! The module is available with the `petgraph` feature.
+//!
+//! The [to_dot] function converts any petgraph graph, e.g. [`petgraph::Graph`] or
+//! [`StableGraph`], into the [Graph] that can be printed with the [PrinterContext](crate::printer::PrinterContext)
+//! or executed. The nodes get the ids from their indexes and the attributes from the given closures.
+//!
+//! The [from_graph] and [from_graph_stable] functions convert the [Graph] into the petgraph graph
+//! resolving the statements with the [GraphModel]: the chains of edges and the subgraphs in the edges
+//! are expanded, the implicitly declared nodes are created and every node keeps the subgraphs
+//! it belongs to.
+//!
+//! # Example:
+//! ```rust
+//! use dot_generator::*;
+//! use dot_structures::*;
+//! use graphviz_rust::{
+//!     parse,
+//!     petgraph::{from_graph, to_dot},
+//!     printer::{DotPrinter, PrinterContext},
+//! };
+//! use petgraph::{graph::DiGraph, visit::EdgeRef};
+//!
+//! let mut g = DiGraph::<&str, u32>::new();
+//! let a = g.add_node("a");
+//! let b = g.add_node("b");
+//! g.add_edge(a, b, 7);
+//!
+//! let dot = to_dot(
+//!     &g,
+//!     id!("g"),
+//!     |n| vec![attr!("label", n.1)],
+//!     |e| vec![attr!("weight", e.weight())],
+//! );
+//! assert_eq!(
+//!     dot.print(&mut PrinterContext::new(true, 0, "".to_string(), 90)),
+//!     "digraph g {0[label=a];1[label=b];0 -> 1 [weight=7];}"
+//! );
+//!
+//! let g = from_graph::<petgraph::Directed, u32>(&parse("digraph { a -> {b c} }").unwrap()).unwrap();
+//! assert_eq!(g.node_count(), 3);
+//! assert_eq!(g.edge_count(), 2);
+//! ```
+//!
+//! [`petgraph`]: https://docs.rs/petgraph
+//! [`petgraph::Graph`]: ::petgraph::Graph
+//! [`StableGraph`]: ::petgraph::stable_graph::StableGraph
+use ::petgraph::{
+    graph::IndexType,
+    stable_graph::StableGraph,
+    visit::{EdgeRef, GraphProp, IntoEdgeReferences, IntoNodeReferences, NodeIndexable, NodeRef},
+    EdgeType,
+};
+use dot_structures::{Attribute, Edge, EdgeTy, Graph, Id, Node, NodeId, Port, Stmt, Vertex};
+
+use crate::model::GraphModel;
+
+/// The node of the petgraph graph converted from the [Graph].
+#[derive(Debug, Clone, PartialEq)]
+pub struct DotNode {
+    pub id: Id,
+    /// the effective attributes including the defaults
+    pub attributes: Vec<Attribute>,
+    /// the ids of the subgraphs containing the node from the outermost one
+    pub subgraphs: Vec<Id>,
+}
+
+/// The edge of the petgraph graph converted from the [Graph].
+#[derive(Debug, Clone, PartialEq)]
+pub struct DotEdge {
+    pub tail_port: Option<Port>,
+    pub head_port: Option<Port>,
+    /// the effective attributes including the defaults
+    pub attributes: Vec<Attribute>,
+}
+
+/// Converts the petgraph graph into the [Graph] with the given id.
+///
+/// The nodes are identified by their indexes and the attributes are produced by the closures.
+pub fn to_dot<G, NF, EF>(graph: G, id: Id, node_attrs: NF, edge_attrs: EF) -> Graph
+where
+    G: IntoNodeReferences + IntoEdgeReferences + NodeIndexable + GraphProp,
+    NF: Fn(&G::NodeRef) -> Vec<Attribute>,
+    EF: Fn(&G::EdgeRef) -> Vec<Attribute>,
+{
+    let node_id = |n: G::NodeId| NodeId(Id::Plain(graph.to_index(n).to_string()), None);
+    let mut stmts = vec![];
+    for n in graph.node_references() {
+        stmts.push(Stmt::Node(Node::new(node_id(n.id()), node_attrs(&n))));
+    }
+    for e in graph.edge_references() {
+        stmts.push(Stmt::Edge(Edge {
+            ty: EdgeTy::Pair(
+                Vertex::N(node_id(e.source())),
+                Vertex::N(node_id(e.target())),
+            ),
+            attributes: edge_attrs(&e),
+        }));
+    }
+    if graph.is_directed() {
+        Graph::DiGraph {
+            id,
+            strict: false,
+            stmts,
+        }
+    } else {
+        Graph::Graph {
+            id,
+            strict: false,
+            stmts,
+        }
+    }
+}
+
+/// Converts the [Graph] into the petgraph graph.
+///
+/// Returns an error if the edge type of the petgraph graph does not match the graph.
+pub fn from_graph<Ty: EdgeType, Ix: IndexType>(
+    graph: &Graph,
+) -> Result<::petgraph::Graph<DotNode, DotEdge, Ty, Ix>, String> {
+    let model = GraphModel::new(graph);
+    if model.directed != Ty::is_directed() {
+        return Err(format!(
+            "the graph is {} but the petgraph graph is {}",
+            direction(model.directed),
+            direction(Ty::is_directed())
+        ));
+    }
+    let mut result = ::petgraph::Graph::with_capacity(model.nodes.len(), model.edges.len());
+    for node in model.nodes.iter() {
+        result.add_node(DotNode {
+            id: node.id.clone(),
+            attributes: node.attributes.to_vec(),
+            subgraphs: node
+                .subgraphs
+                .iter()
+                .map(|s| model.subgraphs[*s].id.clone())
+                .collect(),
+        });
+    }
+    for edge in model.edges.iter() {
+        result.add_edge(
+            Ix::new(edge.tail).into(),
+            Ix::new(edge.head).into(),
+            DotEdge {
+                tail_port: edge.tail_port.clone(),
+                head_port: edge.head_port.clone(),
+                attributes: edge.attributes.to_vec(),
+            },
+        );
+    }
+    Ok(result)
+}
+
+/// Converts the [Graph] into the stable petgraph graph, see [from_graph].
+pub fn from_graph_stable<Ty: EdgeType, Ix: IndexType>(
+    graph: &Graph,
+) -> Result<StableGraph<DotNode, DotEdge, Ty, Ix>, String> {
+    from_graph(graph).map(StableGraph::from)
+}
+
+fn direction(directed: bool) -> &'static str {
+    if directed {
+        "directed"
+    } else {
+        "undirected"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ::petgraph::{
+        graph::{NodeIndex, UnGraph},
+        stable_graph::StableDiGraph,
+        visit::EdgeRef,
+        Directed, Undirected,
+    };
+    use dot_generator::*;
+    use dot_structures::*;
+
+    use crate::{
+        parse,
+        petgraph::{from_graph, from_graph_stable, to_dot},
+    };
+
+    #[test]
+    fn to_dot_test() {
+        let mut g = UnGraph::<u32, &str>::new_undirected();
+        let a = g.add_node(1);
+        let b = g.add_node(2);
+        g.add_edge(a, b, "x");
+        let dot = to_dot(
+            &g,
+            id!("g"),
+            |_| vec![],
+            |e| vec![attr!("label", e.weight())],
+        );
+        assert_eq!(
+            dot,
+            graph!(id!("g");
+                node!("0"),
+                node!("1"),
+                edge!(node_id!("0") => node_id!("1"); attr!("label", "x"))
+            )
+        );
+    }
+
+    #[test]
+    fn to_dot_stable_test() {
+        let mut g = StableDiGraph::<&str, ()>::new();
+        let a = g.add_node("a");
+        let b = g.add_node("b");
+        let c = g.add_node("c");
+        g.add_edge(a, c, ());
+        g.remove_node(b);
+        let dot = to_dot(&g, id!("g"), |n| vec![attr!("label", n.1)], |_| vec![]);
+        assert_eq!(
+            dot,
+            graph!(di id!("g");
+                node!("0"; attr!("label", "a")),
+                node!("2"; attr!("label", "c")),
+                edge!(node_id!("0") => node_id!("2"))
+            )
+        );
+    }
+
+    #[test]
+    fn from_graph_test() {
+        let g = parse(
+            r#"digraph {
+                node [shape=box]
+                a -> b:n -> subgraph s { c d } [color=red]
+                subgraph cluster_x { e; a }
+            }"#,
+        )
+        .unwrap();
+        let pg = from_graph::<Directed, u32>(&g).unwrap();
+        assert_eq!(pg.node_count(), 5);
+        assert_eq!(pg.edge_count(), 3);
+
+        let a = &pg[NodeIndex::new(0)];
+        assert_eq!(a.id, id!("a"));
+        assert_eq!(a.attributes, vec![attr!("shape", "box")]);
+        assert_eq!(a.subgraphs, vec![id!("cluster_x")]);
+        assert_eq!(pg[NodeIndex::new(2)].subgraphs, vec![id!("s")]);
+
+        let bc = pg.edge_references().nth(1).unwrap();
+        assert_eq!(pg[bc.source()].id, id!("b"));
+        assert_eq!(pg[bc.target()].id, id!("c"));
+        assert_eq!(bc.weight().tail_port, Some(port!(, "n")));
+        assert_eq!(bc.weight().attributes, vec![attr!("color", "red")]);
+    }
+
+    #[test]
+    fn from_graph_stable_test() {
+        let g = parse("graph { a -- b -- a }").unwrap();
+        let pg = from_graph_stable::<Undirected, u32>(&g).unwrap();
+        assert_eq!(pg.node_count(), 2);
+        assert_eq!(pg.edge_count(), 2);
+        assert!(from_graph::<Directed, u32>(&g).is_err());
+    }
+}
@@ -0,0 +1,520 @@
+//! Post-processing of the svg rendered by graphviz.
+//!
+//! The svg output of graphviz wraps every node, edge and cluster into the group
+//! `<g id="node1" class="node"><title>a</title>...</g>` where the title is the name of the element.
+//! That allows mapping the groups back to the elements of the source [Graph] and
+//! applying the following transformations:
+//!  - strip the xml prolog and the doctype to embed the svg into html
+//!  - strip the comments
+//!  - add the values of the `class` attribute to the class of the group
+//!  - add the `data-id` attribute with the name of the node, the cluster or the graph
+//!    and the `data-tail` and `data-head` attributes with the names of the edge ends
+//!  - make the svg responsive removing the fixed `width` and `height` and keeping the `viewBox`
+//!
+//! All transformations are enabled by default and can be switched off in the [SvgContext].
+//!
+//! # Example:
+//! ```rust
+//! use dot_generator::*;
+//! use dot_structures::*;
+//! use graphviz_rust::svg::{process, SvgContext};
+//!
+//! let g = graph!(di id!("g"); node!("a"; attr!("class", "important")));
+//! let svg = r#"<?xml version="1.0" encoding="UTF-8" standalone="no"?>
+//! <!-- Generated by graphviz -->
+//! <svg width="62pt" height="44pt" viewBox="0.00 0.00 62.00 44.00" xmlns="http://www.w3.org/2000/svg">
+//! <g id="graph0" class="graph">
+//! <title>g</title>
+//! <!-- a -->
+//! <g id="node1" class="node">
+//! <title>a</title>
+//! <ellipse fill="none" stroke="black" cx="27" cy="-18" rx="27" ry="18"/>
+//! </g>
+//! </g>
+//! </svg>
+//! "#;
+//! let result = process(svg.as_bytes(), &g, &SvgContext::default()).unwrap();
+//! assert!(result.starts_with(r#"<svg viewBox="0.00 0.00 62.00 44.00""#));
+//! assert!(result.contains(r#"<g id="node1" class="node important" data-id="a">"#));
+//! assert!(!result.contains("<!--"));
+//! ```
+use std::collections::HashMap;
+
+use dot_structures::{Graph, Port};
+
+use crate::model::{id_str, AttributeMap, GraphModel};
+
+/// The set of the transformations applied by [process].
+#[derive(Debug, Clone, PartialEq)]
+pub struct SvgContext {
+    /// strip the xml prolog and the doctype
+    strip_prolog: bool,
+    /// strip the comments
+    strip_comments: bool,
+    /// add the classes from the `class` attribute
+    classes: bool,
+    /// add the `data-` attributes with the ids of the elements
+    data_ids: bool,
+    /// remove the `width` and `height` of the svg
+    responsive: bool,
+}
+
+impl Default for SvgContext {
+    fn default() -> Self {
+        SvgContext {
+            strip_prolog: true,
+            strip_comments: true,
+            classes: true,
+            data_ids: true,
+            responsive: true,
+        }
+    }
+}
+
+impl SvgContext {
+    /// Keep the xml prolog and the doctype.
+    pub fn keep_prolog(&mut self) -> &mut SvgContext {
+        self.strip_prolog = false;
+        self
+    }
+    /// Keep the comments.
+    pub fn keep_comments(&mut self) -> &mut SvgContext {
+        self.strip_comments = false;
+        self
+    }
+    /// Keep the fixed `width` and `height` of the svg.
+    pub fn keep_size(&mut self) -> &mut SvgContext {
+        self.responsive = false;
+        self
+    }
+    /// Do not add the classes from the `class` attribute.
+    pub fn without_classes(&mut self) -> &mut SvgContext {
+        self.classes = false;
+        self
+    }
+    /// Do not add the `data-` attributes.
+    pub fn without_data_ids(&mut self) -> &mut SvgContext {
+        self.data_ids = false;
+        self
+    }
+}
+
+/// Applies the transformations from the [SvgContext] to the svg rendered from the given [Graph].
+pub fn process(svg: &[u8], graph: &Graph, ctx: &SvgContext) -> Result<String, String> {
+    let svg = std::str::from_utf8(svg).map_err(|e| e.to_string())?;
+    if !svg.contains("<svg") {
+        return Err("the svg element is not found".to_string());
+    }
+    let mut svg = svg.to_string();
+    if ctx.strip_prolog {
+        svg = remove_between(&svg, "<?", "?>");
+        svg = remove_between(&svg, "<!DOCTYPE", ">");
+    }
+    if ctx.strip_comments {
+        svg = remove_between(&svg, "<!--", "-->");
+    }
+    if ctx.strip_prolog || ctx.strip_comments {
+        svg = svg
+            .lines()
+            .filter(|l| !l.trim().is_empty())
+            .collect::<Vec<_>>()
+            .join("\n");
+        svg.push('\n');
+    }
+    if ctx.responsive {
+        svg = responsive(&svg);
+    }
+    if ctx.classes || ctx.data_ids {
+        svg = Groups::new(&GraphModel::new(graph)).process(&svg, ctx);
+    }
+    Ok(svg)
+}
+
+/// Removes all fragments starting with `start` and ending with `end`.
+fn remove_between(text: &str, start: &str, end: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(s) = rest.find(start) {
+        result.push_str(&rest[..s]);
+        match rest[s..].find(end) {
+            Some(e) => rest = &rest[s + e + end.len()..],
+            None => rest = "",
+        }
+    }
+    result.push_str(rest);
+    result
+}
+
+/// Removes the `width` and `height` of the svg element adding the `viewBox` if it is absent.
+fn responsive(svg: &str) -> String {
+    let start = match svg.find("<svg") {
+        Some(start) => start,
+        None => return svg.to_string(),
+    };
+    let end = svg[start..]
+        .find('>')
+        .map(|e| start + e)
+        .unwrap_or(svg.len());
+    let mut tag = svg[start..end].to_string();
+    let width = attr_value(&tag, "width").map(str::to_string);
+    let height = attr_value(&tag, "height").map(str::to_string);
+    tag = remove_attr(&tag, "width");
+    tag = remove_attr(&tag, "height");
+    if attr_value(&tag, "viewBox").is_none() {
+        if let (Some(w), Some(h)) = (width, height) {
+            let num = |v: &str| v.trim_end_matches(char::is_alphabetic).to_string();
+            tag = format!("{} viewBox=\"0 0 {} {}\"", tag, num(&w), num(&h));
+        }
+    }
+    format!("{}{}{}", &svg[..start], tag, &svg[end..])
+}
+
+/// Returns the value of the attribute in the tag.
+pub(crate) fn attr_value<'a>(tag: &'a str, name: &str) -> Option<&'a str> {
+    let (start, _) = attr_bounds(tag, name)?;
+    let value = &tag[start + name.len() + 2..];
+    value.find('"').map(|e| &value[..e])
+}
+
+/// Returns the start of the name and the end of the value of the attribute in the tag.
+fn attr_bounds(tag: &str, name: &str) -> Option<(usize, usize)> {
+    let pattern = format!("{}=\"", name);
+    let mut from = 0;
+    while let Some(pos) = tag[from..].find(pattern.as_str()) {
+        let pos = from + pos;
+        if tag[..pos].ends_with(char::is_whitespace) {
+            let value_start = pos + pattern.len();
+            let value_end = tag[value_start..].find('"')? + value_start + 1;
+            return Some((pos, value_end));
+        }
+        from = pos + pattern.len();
+    }
+    None
+}
+
+/// Sets the value of the attribute keeping its position or appends the attribute.
+fn set_attr(tag: &str, name: &str, value: &str) -> String {
+    match attr_bounds(tag, name) {
+        Some((start, end)) => format!("{}{}=\"{}\"{}", &tag[..start], name, value, &tag[end..]),
+        None => format!("{} {}=\"{}\"", tag, name, value),
+    }
+}
+
+fn remove_attr(tag: &str, name: &str) -> String {
+    match attr_bounds(tag, name) {
+        Some((start, end)) => {
+            let ws_start = tag[..start].trim_end().len();
+            format!("{}{}", &tag[..ws_start], &tag[end..])
+        }
+        None => tag.to_string(),
+    }
+}
+
+/// The elements of the graph indexed by the titles of the svg groups.
+struct Groups {
+    graph: Element,
+    nodes: HashMap<String, Element>,
+    clusters: HashMap<String, Element>,
+    edges: HashMap<String, Vec<Element>>,
+}
+
+#[derive(Default, Clone)]
+struct Element {
+    class: Option<String>,
+    data: Vec<(&'static str, String)>,
+}
+
+impl Groups {
+    fn new(model: &GraphModel) -> Self {
+        let element = |attrs: &AttributeMap, data: Vec<(&'static str, String)>| Element {
+            class: attrs.value("class"),
+            data,
+        };
+        let graph = element(&model.attributes, vec![("data-id", id_str(&model.id))]);
+        let nodes = model
+            .nodes
+            .iter()
+            .map(|n| {
+                let e = element(&n.attributes, vec![("data-id", n.name())]);
+                (n.name(), e)
+            })
+            .collect();
+        let clusters = model
+            .clusters()
+            .map(|s| {
+                let e = element(&s.attributes, vec![("data-id", s.name())]);
+                (s.name(), e)
+            })
+            .collect();
+        let mut edges: HashMap<String, Vec<Element>> = HashMap::new();
+        let op = if model.directed { "->" } else { "--" };
+        for e in model.edges.iter() {
+            let (tail, head) = (model.nodes[e.tail].name(), model.nodes[e.head].name());
+            let end = |name: &String, port: Option<&Port>, attr: &str| {
+                let port = match port {
+                    Some(Port(Some(id), Some(dir))) => Some(format!("{}:{}", id_str(id), dir)),
+                    Some(Port(Some(id), None)) => Some(id_str(id)),
+                    Some(Port(None, Some(dir))) => Some(dir.clone()),
+                    _ => e.attributes.value(attr),
+                };
+                match port {
+                    Some(port) => format!("{}:{}", name, port),
+                    None => name.clone(),
+                }
+            };
+            let title = format!(
+                "{}{}{}",
+                end(&tail, e.tail_port.as_ref(), "tailport"),
+                op,
+                end(&head, e.head_port.as_ref(), "headport")
+            );
+            let data = vec![("data-tail", tail), ("data-head", head)];
+            edges
+                .entry(title)
+                .or_default()
+                .push(element(&e.attributes, data));
+        }
+        // the edges are taken in the order of the groups
+        edges.values_mut().for_each(|v| v.reverse());
+        Groups {
+            graph,
+            nodes,
+            clusters,
+            edges,
+        }
+    }
+
+    fn find(&mut self, kind: &str, title: &str) -> Option<Element> {
+        match kind {
+            "graph" => Some(self.graph.clone()),
+            "node" => self.nodes.get(title).cloned(),
+            "cluster" => self.clusters.get(title).cloned(),
+            "edge" => self.edges.get_mut(title).and_then(|v| v.pop()),
+            _ => None,
+        }
+    }
+
+    fn process(mut self, svg: &str, ctx: &SvgContext) -> String {
+        let mut result = String::with_capacity(svg.len());
+        let mut rest = svg;
+        while let Some(start) = rest.find("<g ") {
+            let end = match rest[start..].find('>') {
+                Some(end) => start + end,
+                None => break,
+            };
+            result.push_str(&rest[..start]);
+            let tag = &rest[start..end];
+            let element = attr_value(tag, "class")
+                .and_then(|class| class.split_whitespace().next())
+                .zip(title(&rest[end..]))
+                .and_then(|(kind, title)| self.find(kind, &unescape(title)));
+            match element {
+                Some(element) => result.push_str(&update_tag(tag, &element, ctx)),
+                None => result.push_str(tag),
+            }
+            rest = &rest[end..];
+        }
+        result.push_str(rest);
+        result
+    }
+}
+
+/// Returns the content of the title going right after the group tag.
+fn title(text: &str) -> Option<&str> {
+    let text = text.strip_prefix('>')?.trim_start();
+    let text = text.strip_prefix("<title>")?;
+    text.find("</title>").map(|e| &text[..e])
+}
+
+fn update_tag(tag: &str, element: &Element, ctx: &SvgContext) -> String {
+    let mut tag = tag.to_string();
+    if ctx.classes {
+        if let Some(class) = &element.class {
+            let current = attr_value(&tag, "class").unwrap_or_default().to_string();
+            let mut classes: Vec<String> = current.split_whitespace().map(str::to_string).collect();
+            for c in class.split(|c: char| c == ',' || c.is_whitespace()) {
+                let c = escape(c);
+                if !c.is_empty() && !classes.contains(&c) {
+                    classes.push(c);
+                }
+            }
+            let classes = classes.join(" ");
+            tag = set_attr(&tag, "class", &classes);
+        }
+    }
+    if ctx.data_ids {
+        for (name, value) in element.data.iter() {
+            tag = set_attr(&tag, name, &escape(value));
+        }
+    }
+    tag
+}
+
+pub(crate) fn escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Replaces the xml entities and the character references.
+pub(crate) fn unescape(value: &str) -> String {
+    let mut result = String::with_capacity(value.len());
+    let mut rest = value;
+    while let Some(start) = rest.find('&') {
+        result.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let decoded = rest.find(';').and_then(|end| {
+            let entity = &rest[1..end];
+            let ch = match entity {
+                "amp" => Some('&'),
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                _ => entity
+                    .strip_prefix("#x")
+                    .map(|h| u32::from_str_radix(h, 16))
+                    .or_else(|| entity.strip_prefix('#').map(|d| d.parse::<u32>()))
+                    .and_then(|c| c.ok())
+                    .and_then(char::from_u32),
+            };
+            ch.map(|c| (c, end))
+        });
+        match decoded {
+            Some((c, end)) => {
+                result.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                result.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    result.push_str(rest);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use dot_generator::*;
+    use dot_structures::*;
+
+    use crate::svg::{process, unescape, SvgContext};
+
+    const SVG: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="no"?>
+<!DOCTYPE svg PUBLIC "-//W3C//DTD SVG 1.1//EN"
+ "http://www.w3.org/Graphics/SVG/1.1/DTD/svg11.dtd">
+<!-- Generated by graphviz version 2.43.0 (0)
+ -->
+<!-- Title: g Pages: 1 -->
+<svg width="116pt" height="152pt"
+ viewBox="0.00 0.00 116.00 152.00" xmlns="http://www.w3.org/2000/svg" xmlns:xlink="http://www.w3.org/1999/xlink">
+<g id="graph0" class="graph" transform="scale(1 1) rotate(0) translate(4 148)">
+<title>g</title>
+<g id="clust1" class="cluster">
+<title>cluster_x</title>
+<polygon fill="none" stroke="black" points="8,-8 8,-136 80,-136 80,-8 8,-8"/>
+</g>
+<!-- a -->
+<g id="node1" class="node">
+<title>a</title>
+<ellipse fill="none" stroke="black" cx="44" cy="-106" rx="27" ry="18"/>
+</g>
+<!-- b&amp;c -->
+<g id="node2" class="node">
+<title>b&amp;c</title>
+<ellipse fill="none" stroke="black" cx="44" cy="-34" rx="27" ry="18"/>
+</g>
+<!-- a&#45;&gt;b&amp;c -->
+<g id="edge1" class="edge">
+<title>a&#45;&gt;b&amp;c</title>
+<path fill="none" stroke="black" d="M44,-87.7C44,-80 44,-70.7 44,-62.1"/>
+</g>
+<!-- a&#45;&gt;b&amp;c -->
+<g id="edge2" class="edge">
+<title>a&#45;&gt;b&amp;c</title>
+<path fill="none" stroke="black" d="M44,-87.7C44,-80 44,-70.7 44,-62.1"/>
+</g>
+</g>
+</svg>
+"#;
+
+    fn graph() -> Graph {
+        graph!(di id!("g");
+            attr!("class", "diagram"),
+            subgraph!("cluster_x"; stmt!(attr!("class", "group")), node!("a"; attr!("class", esc "main big"))),
+            node!(esc "b&c"),
+            edge!(node_id!("a") => node_id!(esc "b&c"); attr!("class", "first")),
+            edge!(node_id!("a") => node_id!(esc "b&c"))
+        )
+    }
+
+    #[test]
+    fn default_test() {
+        let result = process(SVG.as_bytes(), &graph(), &SvgContext::default()).unwrap();
+        assert!(result.starts_with("<svg\n viewBox=\"0.00 0.00 116.00 152.00\" xmlns"));
+        assert!(!result.contains("<!"));
+        assert!(!result.contains("<?"));
+        assert!(result.contains(
+            r#"<g id="graph0" class="graph diagram" transform="scale(1 1) rotate(0) translate(4 148)" data-id="g">"#
+        ));
+        assert!(result.contains(r#"<g id="clust1" class="cluster group" data-id="cluster_x">"#));
+        assert!(result.contains(r#"<g id="node1" class="node main big" data-id="a">"#));
+        assert!(result.contains(r#"<g id="node2" class="node" data-id="b&amp;c">"#));
+        assert!(result
+            .contains(r#"<g id="edge1" class="edge first" data-tail="a" data-head="b&amp;c">"#));
+        assert!(result.contains(r#"<g id="edge2" class="edge" data-tail="a" data-head="b&amp;c">"#));
+    }
+
+    #[test]
+    fn keep_test() {
+        let mut ctx = SvgContext::default();
+        ctx.keep_prolog()
+            .keep_comments()
+            .keep_size()
+            .without_classes()
+            .without_data_ids();
+        let result = process(SVG.as_bytes(), &graph(), &ctx).unwrap();
+        assert_eq!(result, SVG);
+    }
+
+    #[test]
+    fn view_box_test() {
+        let svg = r#"<svg width="62pt" height="44pt" xmlns="http://www.w3.org/2000/svg"></svg>"#;
+        let mut ctx = SvgContext::default();
+        ctx.without_data_ids().without_classes();
+        let result = process(svg.as_bytes(), &graph(), &ctx).unwrap();
+        assert_eq!(
+            result.trim(),
+            r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 62 44"></svg>"#
+        );
+    }
+
+    #[test]
+    fn error_test() {
+        assert!(process(b"<html></html>", &graph(), &SvgContext::default()).is_err());
+        assert!(process(&[0xff, 0xfe], &graph(), &SvgContext::default()).is_err());
+    }
+
+    #[test]
+    fn layout_attributes_test() {
+        let g = graph!(di id!("g");
+            attr!("bb", esc "garbage"),
+            node!("a"; attr!("class", "main"), attr!("pos", esc "x,y")),
+            node!(esc "b&c"; attr!("_draw_", esc "broken")),
+            edge!(node_id!("a") => node_id!(esc "b&c"); attr!("pos", esc "e,1"))
+        );
+        let result = process(SVG.as_bytes(), &g, &SvgContext::default()).unwrap();
+        assert!(result.contains(r#"<g id="node1" class="node main" data-id="a">"#));
+        assert!(result.contains(r#"<g id="edge1" class="edge" data-tail="a" data-head="b&amp;c">"#));
+    }
+
+    #[test]
+    fn unescape_test() {
+        assert_eq!(unescape("a&#45;&gt;b&amp;c&#x41;"), "a->b&cA");
+        assert_eq!(unescape("a & b&unknown;"), "a & b&unknown;");
+    }
+}